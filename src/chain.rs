@@ -66,6 +66,19 @@ impl Chain {
     }
   }
 
+  /// Mint terms of the ticks which `mint` instructions can mint. They are
+  /// the same on every chain, so that instructions behave on test chains as
+  /// they do on mainnet.
+  pub(crate) fn orddefi_mint_terms(self, tick: &str) -> Option<orddefi::MintTerms> {
+    match tick {
+      "odfi" | "odgv" => Some(orddefi::MintTerms {
+        limit: orddefi::Quantity::from_integer(1000),
+        supply: orddefi::Quantity::from_integer(21_000_000),
+      }),
+      _ => None,
+    }
+  }
+
   pub(crate) fn genesis_block(self) -> Block {
     bitcoin::blockdata::constants::genesis_block(self.network())
   }
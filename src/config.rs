@@ -1,4 +1,4 @@
-use super::*;
+use {super::*, fee_rate::FeeRateArgument};
 
 #[derive(Deserialize, Default, PartialEq, Debug)]
 #[serde(deny_unknown_fields)]
@@ -6,12 +6,52 @@ pub(crate) struct Config {
   pub(crate) hidden: HashSet<InscriptionId>,
   pub(crate) bitcoin_rpc_pass: Option<String>,
   pub(crate) bitcoin_rpc_user: Option<String>,
+  pub(crate) max_fee_rate: Option<FeeRate>,
+  pub(crate) min_fee_rate: Option<FeeRate>,
+  #[serde(default)]
+  pub(crate) profiles: BTreeMap<String, Profile>,
+}
+
+/// Defaults for inscribing wallet commands, selected with `--profile`.
+#[derive(Deserialize, Default, PartialEq, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Profile {
+  pub(crate) change: Option<Address<NetworkUnchecked>>,
+  pub(crate) destination: Option<Address<NetworkUnchecked>>,
+  pub(crate) fee_rate: Option<FeeRateArgument>,
+  pub(crate) origin: Option<Address<NetworkUnchecked>>,
+  pub(crate) postage: Option<u64>,
+  pub(crate) wallet: Option<String>,
 }
 
 impl Config {
   pub(crate) fn is_hidden(&self, inscription_id: InscriptionId) -> bool {
     self.hidden.contains(&inscription_id)
   }
+
+  /// Addresses are checked against `chain` here, so a profile for another
+  /// chain is refused before anything is inscribed.
+  pub(crate) fn profile(&self, name: &str, chain: Chain) -> Result<Profile> {
+    let profile = self
+      .profiles
+      .get(name)
+      .ok_or_else(|| anyhow!("no profile named `{name}` in config file"))?;
+
+    for (field, address) in [
+      ("change", &profile.change),
+      ("destination", &profile.destination),
+      ("origin", &profile.origin),
+    ] {
+      if let Some(address) = address {
+        address
+          .clone()
+          .require_network(chain.network())
+          .with_context(|| format!("invalid `{field}` address in profile `{name}`"))?;
+      }
+    }
+
+    Ok(profile.clone())
+  }
 }
 
 #[cfg(test)]
@@ -37,6 +77,60 @@ mod tests {
     assert!(!config.is_hidden(b));
   }
 
+  #[test]
+  fn profiles_are_validated_against_chain() {
+    let config = serde_yaml::from_str::<Config>(
+      "
+hidden: []
+profiles:
+  mm1:
+    origin: bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+    fee_rate: auto:3
+    postage: 546
+    wallet: mm
+",
+    )
+    .unwrap();
+
+    assert_eq!(
+      config.profile("mm1", Chain::Mainnet).unwrap(),
+      Profile {
+        origin: Some(
+          "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+            .parse()
+            .unwrap()
+        ),
+        fee_rate: Some(FeeRateArgument::Auto(3)),
+        postage: Some(546),
+        wallet: Some("mm".into()),
+        ..Default::default()
+      }
+    );
+
+    assert_eq!(
+      config
+        .profile("mm1", Chain::Regtest)
+        .unwrap_err()
+        .to_string(),
+      "invalid `origin` address in profile `mm1`"
+    );
+
+    assert_eq!(
+      config
+        .profile("mm2", Chain::Mainnet)
+        .unwrap_err()
+        .to_string(),
+      "no profile named `mm2` in config file"
+    );
+  }
+
+  #[test]
+  fn profiles_reject_unknown_fields() {
+    assert!(
+      serde_yaml::from_str::<Config>("{ hidden: [], profiles: { mm1: { fee: 1 } } }").is_err()
+    );
+  }
+
   #[test]
   fn example_config_file_is_valid() {
     let _: Config = serde_yaml::from_reader(File::open("ord.yaml").unwrap()).unwrap();
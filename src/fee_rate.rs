@@ -22,6 +22,21 @@ impl TryFrom<f64> for FeeRate {
   }
 }
 
+impl Display for FeeRate {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl<'de> Deserialize<'de> for FeeRate {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    Self::try_from(f64::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+  }
+}
+
 impl FeeRate {
   pub fn fee(&self, vsize: usize) -> Amount {
     #[allow(clippy::cast_possible_truncation)]
@@ -34,6 +49,102 @@ impl FeeRate {
   }
 }
 
+/// A fee rate given on the command line, either in sats/vB or as
+/// `auto:<BLOCKS>`, which asks Bitcoin Core for a fee rate that should
+/// confirm within `<BLOCKS>` blocks.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) enum FeeRateArgument {
+  Auto(u16),
+  Fixed(FeeRate),
+}
+
+impl FromStr for FeeRateArgument {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s.strip_prefix("auto:") {
+      Some(blocks) => {
+        let blocks = blocks
+          .parse::<u16>()
+          .with_context(|| format!("invalid confirmation target: {blocks}"))?;
+
+        ensure!(blocks > 0, "confirmation target must be at least one block");
+
+        Ok(Self::Auto(blocks))
+      }
+      None => Ok(Self::Fixed(s.parse()?)),
+    }
+  }
+}
+
+/// Config files may give fixed fee rates as numbers.
+impl<'de> Deserialize<'de> for FeeRateArgument {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Value {
+      Number(FeeRate),
+      String(String),
+    }
+
+    match Value::deserialize(deserializer)? {
+      Value::Number(fee_rate) => Ok(Self::Fixed(fee_rate)),
+      Value::String(s) => s.parse().map_err(serde::de::Error::custom),
+    }
+  }
+}
+
+impl From<FeeRate> for FeeRateArgument {
+  fn from(fee_rate: FeeRate) -> Self {
+    Self::Fixed(fee_rate)
+  }
+}
+
+impl FeeRateArgument {
+  /// Fixed fee rates are used as given. Estimated fee rates are clamped to
+  /// the `--min-fee-rate` and `--max-fee-rate` limits.
+  pub(crate) fn resolve(self, client: &Client, options: &Options) -> Result<FeeRate> {
+    match self {
+      Self::Fixed(fee_rate) => Ok(fee_rate),
+      Self::Auto(blocks) => {
+        let estimate = client.estimate_smart_fee(blocks, None)?;
+
+        let fee_rate = estimate.fee_rate.ok_or_else(|| {
+          anyhow!(
+            "Bitcoin Core could not estimate fee rate for confirmation within {blocks} blocks: {}",
+            estimate.errors.unwrap_or_default().join(", ")
+          )
+        })?;
+
+        let (min, max) = options.fee_rate_limits()?;
+
+        Ok(Self::clamp(
+          FeeRate::try_from(fee_rate.to_sat() as f64 / 1000.0)?,
+          min,
+          max,
+        ))
+      }
+    }
+  }
+
+  fn clamp(fee_rate: FeeRate, min: Option<FeeRate>, max: Option<FeeRate>) -> FeeRate {
+    let mut fee_rate = fee_rate.0;
+
+    if let Some(min) = min {
+      fee_rate = fee_rate.max(min.0);
+    }
+
+    if let Some(max) = max {
+      fee_rate = fee_rate.min(max.0);
+    }
+
+    FeeRate(fee_rate)
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -67,4 +178,59 @@ mod tests {
       Amount::from_sat(123456789)
     );
   }
+
+  #[test]
+  fn parse_argument() {
+    assert_eq!(
+      "1.5".parse::<FeeRateArgument>().unwrap(),
+      FeeRateArgument::Fixed(FeeRate(1.5))
+    );
+    assert_eq!(
+      "auto:6".parse::<FeeRateArgument>().unwrap(),
+      FeeRateArgument::Auto(6)
+    );
+    assert!("auto".parse::<FeeRateArgument>().is_err());
+    assert!("auto:".parse::<FeeRateArgument>().is_err());
+    assert!("auto:0".parse::<FeeRateArgument>().is_err());
+    assert!("auto:-1".parse::<FeeRateArgument>().is_err());
+    assert!("-1".parse::<FeeRateArgument>().is_err());
+  }
+
+  #[test]
+  fn clamp() {
+    assert_eq!(
+      FeeRateArgument::clamp(FeeRate(5.0), None, None),
+      FeeRate(5.0)
+    );
+    assert_eq!(
+      FeeRateArgument::clamp(FeeRate(5.0), Some(FeeRate(10.0)), None),
+      FeeRate(10.0)
+    );
+    assert_eq!(
+      FeeRateArgument::clamp(FeeRate(50.0), Some(FeeRate(10.0)), Some(FeeRate(20.0))),
+      FeeRate(20.0)
+    );
+    assert_eq!(
+      FeeRateArgument::clamp(FeeRate(15.0), Some(FeeRate(10.0)), Some(FeeRate(20.0))),
+      FeeRate(15.0)
+    );
+  }
+
+  #[test]
+  fn deserialize() {
+    assert_eq!(
+      serde_yaml::from_str::<FeeRate>("2.5").unwrap(),
+      FeeRate(2.5)
+    );
+    assert!(serde_yaml::from_str::<FeeRate>("-2.5").is_err());
+    assert_eq!(
+      serde_yaml::from_str::<FeeRateArgument>("2.5").unwrap(),
+      FeeRateArgument::Fixed(FeeRate(2.5))
+    );
+    assert_eq!(
+      serde_yaml::from_str::<FeeRateArgument>("auto:3").unwrap(),
+      FeeRateArgument::Auto(3)
+    );
+    assert!(serde_yaml::from_str::<FeeRateArgument>("auto:0").is_err());
+  }
 }
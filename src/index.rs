@@ -2,7 +2,8 @@ use {
   self::{
     entry::{
       Entry, HeaderValue, InscriptionEntry, InscriptionEntryValue, InscriptionIdValue,
-      OutPointValue, RuneEntryValue, RuneIdValue, SatPointValue, SatRange, TxidValue,
+      OrdDeFiBalanceValue, OrdDeFiReceiptValue, OrdDeFiTransferableValue, OutPointValue, PoolValue,
+      RuneEntryValue, RuneIdValue, SatPointValue, SatRange, TxOutValue, TxidValue,
     },
     reorg::*,
     runes::{Rune, RuneId},
@@ -41,7 +42,7 @@ mod updater;
 #[cfg(test)]
 pub(crate) mod testing;
 
-const SCHEMA_VERSION: u64 = 16;
+const SCHEMA_VERSION: u64 = 18;
 
 macro_rules! define_table {
   ($name:ident, $key:ty, $value:ty) => {
@@ -56,16 +57,24 @@ macro_rules! define_multimap_table {
   };
 }
 
+define_multimap_table! { ORDDEFI_SCRIPT_TO_INSCRIPTION_ID, &[u8], InscriptionIdValue }
 define_multimap_table! { SATPOINT_TO_SEQUENCE_NUMBER, &SatPointValue, u32 }
 define_multimap_table! { SAT_TO_SEQUENCE_NUMBER, u64, u32 }
+define_multimap_table! { SCRIPT_PUBKEY_TO_OUTPOINTS, &[u8], &OutPointValue }
 define_multimap_table! { SEQUENCE_NUMBER_TO_CHILDREN, u32, u32 }
 define_table! { HEIGHT_TO_BLOCK_HEADER, u32, &HeaderValue }
 define_table! { HEIGHT_TO_LAST_SEQUENCE_NUMBER, u32, u32 }
 define_table! { HOME_INSCRIPTIONS, u32, InscriptionIdValue }
 define_table! { INSCRIPTION_ID_TO_SEQUENCE_NUMBER, InscriptionIdValue, u32 }
 define_table! { INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER, i32, u32 }
+define_table! { ORDDEFI_INSCRIPTION_ID_TO_RECEIPT, InscriptionIdValue, OrdDeFiReceiptValue }
+define_table! { ORDDEFI_INSCRIPTION_ID_TO_TRANSFERABLE, InscriptionIdValue, OrdDeFiTransferableValue }
+define_table! { ORDDEFI_PAIR_TO_POOL, &str, PoolValue }
+define_table! { ORDDEFI_SCRIPT_TICK_TO_BALANCE, (&[u8], &str), OrdDeFiBalanceValue }
+define_table! { ORDDEFI_TICK_TO_SUPPLY, &str, u128 }
 define_table! { OUTPOINT_TO_RUNE_BALANCES, &OutPointValue, &[u8] }
 define_table! { OUTPOINT_TO_SAT_RANGES, &OutPointValue, &[u8] }
+define_table! { OUTPOINT_TO_TX_OUT, &OutPointValue, TxOutValue }
 define_table! { OUTPOINT_TO_VALUE, &OutPointValue, u64}
 define_table! { RUNE_ID_TO_RUNE_ENTRY, RuneIdValue, RuneEntryValue }
 define_table! { RUNE_TO_RUNE_ID, u128, RuneIdValue }
@@ -99,6 +108,8 @@ pub(crate) enum Statistic {
   SatRanges,
   UnboundInscriptions,
   IndexTransactions,
+  IndexOrdDeFi,
+  IndexAddresses,
 }
 
 impl Statistic {
@@ -195,6 +206,8 @@ pub struct Index {
   genesis_block_coinbase_transaction: Transaction,
   genesis_block_coinbase_txid: Txid,
   height_limit: Option<u32>,
+  index_addresses: bool,
+  index_orddefi: bool,
   index_runes: bool,
   index_sats: bool,
   index_transactions: bool,
@@ -237,6 +250,8 @@ impl Index {
       redb::Durability::Immediate
     };
 
+    let index_addresses;
+    let index_orddefi;
     let index_runes;
     let index_sats;
     let index_transactions;
@@ -248,7 +263,7 @@ impl Index {
     let database = match Database::builder()
       .set_cache_size(db_cache_size)
       .set_repair_callback(move |progress: &mut RepairSession| {
-        once.call_once(|| println!("Index file `{}` needs recovery. This can take a long time, especially for the --index-sats index.", index_path.display()));
+        once.call_once(|| eprintln!("Index file `{}` needs recovery. This can take a long time, especially for the --index-sats index.", index_path.display()));
 
         if !(cfg!(test) || log_enabled!(log::Level::Info) || integration_test()) {
           let mut guard = progress_bar.lock().unwrap();
@@ -293,6 +308,8 @@ impl Index {
           }
 
 
+          index_addresses = Self::is_statistic_set(&statistics, Statistic::IndexAddresses)?;
+          index_orddefi = Self::is_statistic_set(&statistics, Statistic::IndexOrdDeFi)?;
           index_runes = Self::is_statistic_set(&statistics, Statistic::IndexRunes)?;
           index_sats = Self::is_statistic_set(&statistics, Statistic::IndexSats)?;
           index_transactions = Self::is_statistic_set(&statistics, Statistic::IndexTransactions)?;
@@ -311,15 +328,23 @@ impl Index {
 
         tx.set_durability(durability);
 
+        tx.open_multimap_table(ORDDEFI_SCRIPT_TO_INSCRIPTION_ID)?;
         tx.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
         tx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
+        tx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINTS)?;
         tx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
         tx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
         tx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
         tx.open_table(HOME_INSCRIPTIONS)?;
         tx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
         tx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
+        tx.open_table(ORDDEFI_INSCRIPTION_ID_TO_RECEIPT)?;
+        tx.open_table(ORDDEFI_INSCRIPTION_ID_TO_TRANSFERABLE)?;
+        tx.open_table(ORDDEFI_PAIR_TO_POOL)?;
+        tx.open_table(ORDDEFI_SCRIPT_TICK_TO_BALANCE)?;
+        tx.open_table(ORDDEFI_TICK_TO_SUPPLY)?;
         tx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+        tx.open_table(OUTPOINT_TO_TX_OUT)?;
         tx.open_table(OUTPOINT_TO_VALUE)?;
         tx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
         tx.open_table(RUNE_TO_RUNE_ID)?;
@@ -338,10 +363,14 @@ impl Index {
             outpoint_to_sat_ranges.insert(&OutPoint::null().store(), [].as_slice())?;
           }
 
+          index_addresses = options.index_addresses;
+          index_orddefi = options.index_orddefi;
           index_runes = options.index_runes();
           index_sats = options.index_sats;
           index_transactions = options.index_transactions;
 
+          Self::set_statistic(&mut statistics, Statistic::IndexAddresses, u64::from(index_addresses))?;
+          Self::set_statistic(&mut statistics, Statistic::IndexOrdDeFi, u64::from(index_orddefi))?;
           Self::set_statistic(&mut statistics, Statistic::IndexRunes, u64::from(index_runes))?;
           Self::set_statistic(&mut statistics, Statistic::IndexSats, u64::from(index_sats))?;
           Self::set_statistic(&mut statistics, Statistic::IndexTransactions, u64::from(index_transactions))?;
@@ -366,6 +395,8 @@ impl Index {
       first_inscription_height: options.first_inscription_height(),
       genesis_block_coinbase_transaction,
       height_limit: options.height_limit,
+      index_addresses,
+      index_orddefi,
       index_runes,
       index_sats,
       index_transactions,
@@ -382,9 +413,27 @@ impl Index {
   }
 
   pub(crate) fn check_sync(&self, utxos: &BTreeMap<OutPoint, Amount>) -> Result<bool> {
+    let rtx = self.database.begin_read()?;
+    let outpoint_to_value = rtx.open_table(OUTPOINT_TO_VALUE)?;
+    for outpoint in utxos.keys() {
+      if outpoint_to_value.get(&outpoint.store())?.is_none() {
+        return Err(anyhow!(
+          "output in Bitcoin Core wallet but not in ord index: {outpoint}"
+        ));
+      }
+    }
+
     Ok(true)
   }
 
+  pub(crate) fn has_address_index(&self) -> bool {
+    self.index_addresses
+  }
+
+  pub(crate) fn has_orddefi_index(&self) -> bool {
+    self.index_orddefi
+  }
+
   pub(crate) fn has_rune_index(&self) -> bool {
     self.index_runes
   }
@@ -511,8 +560,15 @@ impl Index {
 
     let mut tables: BTreeMap<String, TableInfo> = BTreeMap::new();
 
+    insert_multimap_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      ORDDEFI_SCRIPT_TO_INSCRIPTION_ID,
+    );
     insert_multimap_table_info(&mut tables, &wtx, total_bytes, SATPOINT_TO_SEQUENCE_NUMBER);
     insert_multimap_table_info(&mut tables, &wtx, total_bytes, SAT_TO_SEQUENCE_NUMBER);
+    insert_multimap_table_info(&mut tables, &wtx, total_bytes, SCRIPT_PUBKEY_TO_OUTPOINTS);
     insert_multimap_table_info(&mut tables, &wtx, total_bytes, SEQUENCE_NUMBER_TO_CHILDREN);
     insert_table_info(&mut tables, &wtx, total_bytes, HEIGHT_TO_BLOCK_HEADER);
     insert_table_info(
@@ -534,8 +590,29 @@ impl Index {
       total_bytes,
       INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER,
     );
+    insert_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      ORDDEFI_INSCRIPTION_ID_TO_RECEIPT,
+    );
+    insert_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      ORDDEFI_INSCRIPTION_ID_TO_TRANSFERABLE,
+    );
+    insert_table_info(&mut tables, &wtx, total_bytes, ORDDEFI_PAIR_TO_POOL);
+    insert_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      ORDDEFI_SCRIPT_TICK_TO_BALANCE,
+    );
+    insert_table_info(&mut tables, &wtx, total_bytes, ORDDEFI_TICK_TO_SUPPLY);
     insert_table_info(&mut tables, &wtx, total_bytes, OUTPOINT_TO_RUNE_BALANCES);
     insert_table_info(&mut tables, &wtx, total_bytes, OUTPOINT_TO_SAT_RANGES);
+    insert_table_info(&mut tables, &wtx, total_bytes, OUTPOINT_TO_TX_OUT);
     insert_table_info(&mut tables, &wtx, total_bytes, OUTPOINT_TO_VALUE);
     insert_table_info(&mut tables, &wtx, total_bytes, RUNE_ID_TO_RUNE_ENTRY);
     insert_table_info(&mut tables, &wtx, total_bytes, RUNE_TO_RUNE_ID);
@@ -986,6 +1063,33 @@ impl Index {
     Ok(balances)
   }
 
+  pub(crate) fn get_address_outputs(
+    &self,
+    script_pubkey: &Script,
+  ) -> Result<BTreeMap<OutPoint, Amount>> {
+    let rtx = self.database.begin_read()?;
+
+    let outpoint_to_tx_out = rtx.open_table(OUTPOINT_TO_TX_OUT)?;
+
+    let mut outputs = BTreeMap::new();
+
+    for result in rtx
+      .open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINTS)?
+      .get(script_pubkey.as_bytes())?
+    {
+      let outpoint = OutPoint::load(*result?.value());
+
+      let (_script_pubkey, value) = outpoint_to_tx_out
+        .get(&outpoint.store())?
+        .ok_or_else(|| anyhow!("address index is missing output {outpoint}"))?
+        .value();
+
+      outputs.insert(outpoint, Amount::from_sat(value));
+    }
+
+    Ok(outputs)
+  }
+
   pub(crate) fn get_runic_outputs(&self, outpoints: &[OutPoint]) -> Result<BTreeSet<OutPoint>> {
     let rtx = self.database.begin_read()?;
 
@@ -1061,6 +1165,148 @@ impl Index {
     Ok(result)
   }
 
+  #[cfg(test)]
+  pub(crate) fn get_orddefi_balance(
+    &self,
+    script: &Script,
+    tick: &str,
+  ) -> Result<orddefi::Balance> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(ORDDEFI_SCRIPT_TICK_TO_BALANCE)?
+        .get((script.as_bytes(), tick))?
+        .map(|balance| orddefi::Balance::load(balance.value()))
+        .unwrap_or_default(),
+    )
+  }
+
+  pub(crate) fn get_orddefi_balances(
+    &self,
+    script: &Script,
+  ) -> Result<BTreeMap<String, orddefi::Balance>> {
+    let mut balances = BTreeMap::new();
+
+    for result in self
+      .database
+      .begin_read()?
+      .open_table(ORDDEFI_SCRIPT_TICK_TO_BALANCE)?
+      .range((script.as_bytes(), "")..)?
+    {
+      let (key, balance) = result?;
+      let (owner, tick) = key.value();
+
+      if owner != script.as_bytes() {
+        break;
+      }
+
+      balances.insert(tick.into(), orddefi::Balance::load(balance.value()));
+    }
+
+    Ok(balances)
+  }
+
+  pub(crate) fn get_orddefi_pool(&self, ltick: &str, rtick: &str) -> Result<Option<orddefi::Pool>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(ORDDEFI_PAIR_TO_POOL)?
+        .get(orddefi::pair(ltick, rtick).as_str())?
+        .map(|pool| orddefi::Pool::load(pool.value())),
+    )
+  }
+
+  pub(crate) fn get_orddefi_pools(&self) -> Result<Vec<(String, orddefi::Pool)>> {
+    let mut pools = Vec::new();
+
+    for result in self
+      .database
+      .begin_read()?
+      .open_table(ORDDEFI_PAIR_TO_POOL)?
+      .iter()?
+    {
+      let (pair, pool) = result?;
+      pools.push((pair.value().into(), orddefi::Pool::load(pool.value())));
+    }
+
+    Ok(pools)
+  }
+
+  pub(crate) fn get_orddefi_ticks_paginated(
+    &self,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<(String, orddefi::Quantity)>, bool)> {
+    let mut ticks = self
+      .database
+      .begin_read()?
+      .open_table(ORDDEFI_TICK_TO_SUPPLY)?
+      .iter()?
+      .skip(page_size.saturating_mul(page_index))
+      .take(page_size.saturating_add(1))
+      .map(|result| {
+        result.map(|(tick, supply)| (tick.value().into(), orddefi::Quantity(supply.value())))
+      })
+      .collect::<Result<Vec<(String, orddefi::Quantity)>, StorageError>>()?;
+
+    let more = ticks.len() > page_size;
+
+    if more {
+      ticks.pop();
+    }
+
+    Ok((ticks, more))
+  }
+
+  pub(crate) fn get_orddefi_receipt(
+    &self,
+    inscription_id: InscriptionId,
+  ) -> Result<Option<orddefi::Receipt>> {
+    let rtx = self.database.begin_read()?;
+
+    let inscription_id_to_receipt = rtx.open_table(ORDDEFI_INSCRIPTION_ID_TO_RECEIPT)?;
+
+    let Some(receipt) = inscription_id_to_receipt.get(&inscription_id.store())? else {
+      return Ok(None);
+    };
+
+    let (destination, height, instruction, status) = receipt.value();
+
+    Ok(Some(orddefi::Receipt {
+      destination: ScriptBuf::from_bytes(destination.to_vec()),
+      height,
+      instruction: instruction.parse()?,
+      status: orddefi::Status::from_code(status)
+        .ok_or_else(|| anyhow!("invalid OrdDeFi status code {status}"))?,
+    }))
+  }
+
+  pub(crate) fn get_orddefi_history(
+    &self,
+    script: &Script,
+  ) -> Result<Vec<(InscriptionId, orddefi::Receipt)>> {
+    let mut history = Vec::new();
+
+    for result in self
+      .database
+      .begin_read()?
+      .open_multimap_table(ORDDEFI_SCRIPT_TO_INSCRIPTION_ID)?
+      .get(script.as_bytes())?
+    {
+      let inscription_id = InscriptionId::load(result?.value());
+
+      if let Some(receipt) = self.get_orddefi_receipt(inscription_id)? {
+        history.push((inscription_id, receipt));
+      }
+    }
+
+    history.sort_by_key(|(_, receipt)| receipt.height);
+
+    Ok(history)
+  }
+
   pub(crate) fn block_header(&self, hash: BlockHash) -> Result<Option<Header>> {
     self.client.get_block_header(&hash).into_option()
   }
@@ -3388,13 +3634,16 @@ mod tests {
         .unwrap();
       context.rpc_server.mine_blocks(1);
       assert_regex_match!(
-        crate::subcommand::wallet::get_unspent_outputs(
+        crate::subcommand::wallet::get_indexed_unspent_outputs_with_address(
           &crate::subcommand::wallet::bitcoin_rpc_client_for_wallet_command(
             "ord".to_string(),
             &context.options,
           )
           .unwrap(),
-          &context.index
+          None,
+          &context.index,
+          &None,
+          0,
         )
         .unwrap_err()
         .to_string(),
@@ -5580,4 +5829,503 @@ mod tests {
       );
     }
   }
+
+  fn inscribe_orddefi(context: &Context, instruction: &str, auth: bool) -> InscriptionId {
+    let height = usize::try_from(context.index.block_count().unwrap()).unwrap();
+
+    context.mine_blocks(1);
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(height, 0, 0, Witness::new())],
+      op_return: auth.then(|| {
+        script::Builder::new()
+          .push_opcode(opcodes::all::OP_RETURN)
+          .push_slice(b"orddefi:auth")
+          .into_script()
+      }),
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        height + 1,
+        1,
+        0,
+        inscription("text/plain;charset=utf-8", instruction).to_witness(),
+      )],
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    InscriptionId { txid, index: 0 }
+  }
+
+  #[track_caller]
+  fn assert_orddefi_status(
+    context: &Context,
+    inscription_id: InscriptionId,
+    status: orddefi::Status,
+  ) {
+    assert_eq!(
+      context
+        .index
+        .get_orddefi_receipt(inscription_id)
+        .unwrap()
+        .unwrap()
+        .status,
+      status,
+    );
+  }
+
+  #[track_caller]
+  fn assert_orddefi_balance(context: &Context, tick: &str, available: &str, transferable: &str) {
+    assert_eq!(
+      context
+        .index
+        .get_orddefi_balance(&ScriptBuf::new(), tick)
+        .unwrap(),
+      orddefi::Balance {
+        available: available.parse().unwrap(),
+        transferable: transferable.parse().unwrap(),
+      },
+    );
+  }
+
+  #[test]
+  fn address_index_tracks_unspent_outputs() {
+    let context = Context::builder().arg("--index-addresses").build();
+
+    assert!(context.index.has_address_index());
+
+    let coinbase = OutPoint {
+      txid: context.mine_blocks(1)[0].txdata[0].txid(),
+      vout: 0,
+    };
+
+    assert!(context
+      .index
+      .get_address_outputs(&ScriptBuf::new())
+      .unwrap()
+      .contains_key(&coinbase));
+
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      outputs: 2,
+      op_return: Some(
+        script::Builder::new()
+          .push_opcode(opcodes::all::OP_RETURN)
+          .into_script(),
+      ),
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    let outputs = context
+      .index
+      .get_address_outputs(&ScriptBuf::new())
+      .unwrap();
+
+    assert!(!outputs.contains_key(&coinbase));
+    assert!(outputs.contains_key(&OutPoint { txid, vout: 0 }));
+    assert!(outputs.contains_key(&OutPoint { txid, vout: 1 }));
+    assert!(!outputs.contains_key(&OutPoint { txid, vout: 2 }));
+
+    assert_eq!(
+      outputs[&OutPoint { txid, vout: 0 }] + outputs[&OutPoint { txid, vout: 1 }],
+      Amount::from_sat(50 * COIN_VALUE)
+    );
+  }
+
+  #[test]
+  fn address_index_includes_outputs_below_first_inscription_height() {
+    let context = Context::builder()
+      .args(["--index-addresses", "--first-inscription-height", "10"])
+      .build();
+
+    let coinbase = OutPoint {
+      txid: context.mine_blocks(1)[0].txdata[0].txid(),
+      vout: 0,
+    };
+
+    assert!(context
+      .index
+      .get_address_outputs(&ScriptBuf::new())
+      .unwrap()
+      .contains_key(&coinbase));
+  }
+
+  #[test]
+  fn addresses_are_only_indexed_with_flag() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(1);
+
+    assert!(!context.index.has_address_index());
+    assert!(context
+      .index
+      .get_address_outputs(&ScriptBuf::new())
+      .unwrap()
+      .is_empty());
+  }
+
+  #[test]
+  fn orddefi_instructions_are_only_indexed_with_flag() {
+    let context = Context::builder().build();
+
+    let id = inscribe_orddefi(
+      &context,
+      r#"{"p":"orddefi","op":"mint","tick":"odfi","amt":"1000"}"#,
+      true,
+    );
+
+    assert!(!context.index.has_orddefi_index());
+    assert_eq!(context.index.get_orddefi_receipt(id).unwrap(), None);
+    assert_orddefi_balance(&context, "odfi", "0", "0");
+  }
+
+  #[test]
+  fn orddefi_mint_requires_authentication() {
+    let context = Context::builder().arg("--index-orddefi").build();
+
+    let id = inscribe_orddefi(
+      &context,
+      r#"{"p":"orddefi","op":"mint","tick":"odfi","amt":"1000"}"#,
+      true,
+    );
+
+    assert_eq!(
+      context.index.get_orddefi_receipt(id).unwrap(),
+      Some(orddefi::Receipt {
+        destination: ScriptBuf::new(),
+        height: 3,
+        instruction: r#"{"p":"orddefi","op":"mint","tick":"odfi","amt":"1000"}"#
+          .parse()
+          .unwrap(),
+        status: orddefi::Status::Executed,
+      }),
+    );
+
+    let id = inscribe_orddefi(
+      &context,
+      r#"{"p":"orddefi","op":"mint","tick":"odfi","amt":"1000"}"#,
+      false,
+    );
+
+    assert_orddefi_status(
+      &context,
+      id,
+      orddefi::Status::Aborted(orddefi::AbortReason::BadAuth),
+    );
+
+    assert_orddefi_balance(&context, "odfi", "1000", "0");
+  }
+
+  #[test]
+  fn orddefi_mint_rules() {
+    let context = Context::builder().arg("--index-orddefi").build();
+
+    let id = inscribe_orddefi(
+      &context,
+      r#"{"p":"orddefi","op":"mint","tick":"abcd","amt":"1000"}"#,
+      true,
+    );
+
+    assert_orddefi_status(
+      &context,
+      id,
+      orddefi::Status::Aborted(orddefi::AbortReason::UnknownTick),
+    );
+
+    let id = inscribe_orddefi(
+      &context,
+      r#"{"p":"orddefi","op":"mint","tick":"odfi","amt":"1000.1"}"#,
+      true,
+    );
+
+    assert_orddefi_status(
+      &context,
+      id,
+      orddefi::Status::Aborted(orddefi::AbortReason::MintLimit),
+    );
+
+    let id = inscribe_orddefi(
+      &context,
+      r#"{"p":"orddefi","op":"mint","tick":"odf","amt":"1000"}"#,
+      true,
+    );
+
+    assert_orddefi_status(
+      &context,
+      id,
+      orddefi::Status::Aborted(orddefi::AbortReason::InvalidInstruction),
+    );
+
+    assert_orddefi_balance(&context, "odfi", "0", "0");
+  }
+
+  #[test]
+  fn orddefi_transfer_is_credited_when_inscription_is_sent() {
+    let context = Context::builder().arg("--index-orddefi").build();
+
+    inscribe_orddefi(
+      &context,
+      r#"{"p":"orddefi","op":"mint","tick":"odfi","amt":"1000"}"#,
+      true,
+    );
+
+    let id = inscribe_orddefi(
+      &context,
+      r#"{"p":"orddefi","op":"transfer","tick":"odfi","amt":"400"}"#,
+      false,
+    );
+
+    assert_orddefi_status(&context, id, orddefi::Status::Executed);
+    assert_orddefi_balance(&context, "odfi", "600", "400");
+
+    let id = inscribe_orddefi(
+      &context,
+      r#"{"p":"orddefi","op":"transfer","tick":"odfi","amt":"601"}"#,
+      false,
+    );
+
+    assert_orddefi_status(
+      &context,
+      id,
+      orddefi::Status::Aborted(orddefi::AbortReason::InsufficientBalance),
+    );
+
+    // the 400 odfi transfer inscription was revealed in block 6
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(6, 1, 0, Witness::new())],
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_orddefi_balance(&context, "odfi", "1000", "0");
+  }
+
+  #[test]
+  fn orddefi_direct_transfer_credits_recipient() {
+    let context = Context::builder().arg("--index-orddefi").build();
+
+    inscribe_orddefi(
+      &context,
+      r#"{"p":"orddefi","op":"mint","tick":"odfi","amt":"1000"}"#,
+      true,
+    );
+
+    let recipient = "bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw"
+      .parse::<Address<NetworkUnchecked>>()
+      .unwrap()
+      .assume_checked();
+
+    let id = inscribe_orddefi(
+      &context,
+      &format!(r#"{{"p":"orddefi","op":"transfer","tick":"odfi","amt":"250","to":"{recipient}"}}"#),
+      true,
+    );
+
+    assert_orddefi_status(&context, id, orddefi::Status::Executed);
+    assert_orddefi_balance(&context, "odfi", "750", "0");
+    assert_eq!(
+      context
+        .index
+        .get_orddefi_balance(&recipient.script_pubkey(), "odfi")
+        .unwrap()
+        .available,
+      "250".parse().unwrap(),
+    );
+  }
+
+  #[test]
+  fn orddefi_balances_pools_and_history() {
+    let context = Context::builder().arg("--index-orddefi").build();
+
+    let mut ids = Vec::new();
+
+    for (tick, auth) in [("odfi", false), ("odfi", true), ("odgv", true)] {
+      ids.push(inscribe_orddefi(
+        &context,
+        &format!(r#"{{"p":"orddefi","op":"mint","tick":"{tick}","amt":"1000"}}"#),
+        auth,
+      ));
+    }
+
+    ids.push(inscribe_orddefi(
+      &context,
+      r#"{"p":"orddefi","op":"addlp","ltick":"odfi","lamt":"100","rtick":"odgv","ramt":"400"}"#,
+      true,
+    ));
+
+    let pool = context
+      .index
+      .get_orddefi_pool("odfi", "odgv")
+      .unwrap()
+      .unwrap();
+
+    assert_eq!(
+      context.index.get_orddefi_pools().unwrap(),
+      [("odfi-odgv".into(), pool)],
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_orddefi_balances(&ScriptBuf::new())
+        .unwrap(),
+      [("odfi", "900"), ("odfi-odgv", "200"), ("odgv", "600")]
+        .into_iter()
+        .map(|(tick, available)| {
+          (
+            tick.into(),
+            orddefi::Balance {
+              available: available.parse().unwrap(),
+              transferable: orddefi::Quantity::ZERO,
+            },
+          )
+        })
+        .collect(),
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_orddefi_balances(&ScriptBuf::from_bytes(vec![0x51]))
+        .unwrap(),
+      BTreeMap::new(),
+    );
+
+    let history = context
+      .index
+      .get_orddefi_history(&ScriptBuf::new())
+      .unwrap();
+
+    assert_eq!(history.iter().map(|(id, _)| *id).collect::<Vec<_>>(), ids);
+    assert_eq!(
+      history
+        .iter()
+        .map(|(_, receipt)| (receipt.height, receipt.status))
+        .collect::<Vec<_>>(),
+      [
+        (3, orddefi::Status::Aborted(orddefi::AbortReason::BadAuth)),
+        (6, orddefi::Status::Executed),
+        (9, orddefi::Status::Executed),
+        (12, orddefi::Status::Executed),
+      ],
+    );
+  }
+
+  #[test]
+  fn orddefi_liquidity_and_swaps() {
+    let context = Context::builder().arg("--index-orddefi").build();
+
+    for tick in ["odfi", "odgv"] {
+      inscribe_orddefi(
+        &context,
+        &format!(r#"{{"p":"orddefi","op":"mint","tick":"{tick}","amt":"1000"}}"#),
+        true,
+      );
+    }
+
+    let id = inscribe_orddefi(
+      &context,
+      r#"{"p":"orddefi","op":"swap","ltick":"odfi","rtick":"odgv","spend":"odfi","amt":"100"}"#,
+      true,
+    );
+
+    assert_orddefi_status(
+      &context,
+      id,
+      orddefi::Status::Aborted(orddefi::AbortReason::PoolNotFound),
+    );
+
+    let id = inscribe_orddefi(
+      &context,
+      r#"{"p":"orddefi","op":"addlp","ltick":"odgv","lamt":"400","rtick":"odfi","ramt":"100"}"#,
+      true,
+    );
+
+    assert_orddefi_status(&context, id, orddefi::Status::Executed);
+    assert_orddefi_balance(&context, "odfi", "900", "0");
+    assert_orddefi_balance(&context, "odgv", "600", "0");
+    assert_orddefi_balance(&context, "odfi-odgv", "200", "0");
+
+    let mut pool = orddefi::Pool::default();
+    pool
+      .add_liquidity("100".parse().unwrap(), "400".parse().unwrap())
+      .unwrap();
+    assert_eq!(
+      context.index.get_orddefi_pool("odgv", "odfi").unwrap(),
+      Some(pool)
+    );
+
+    inscribe_orddefi(
+      &context,
+      r#"{"p":"orddefi","op":"swap","ltick":"odfi","rtick":"odgv","spend":"odfi","amt":"100"}"#,
+      true,
+    );
+
+    let output = pool.swap(true, "100".parse().unwrap()).unwrap();
+    assert_eq!(
+      context.index.get_orddefi_pool("odfi", "odgv").unwrap(),
+      Some(pool)
+    );
+    assert_eq!(
+      context
+        .index
+        .get_orddefi_balance(&ScriptBuf::new(), "odgv")
+        .unwrap()
+        .available,
+      orddefi::Quantity::from_integer(600)
+        .checked_add(output)
+        .unwrap(),
+    );
+
+    inscribe_orddefi(
+      &context,
+      r#"{"p":"orddefi","op":"rmlp","ltick":"odfi","rtick":"odgv","amt":"200"}"#,
+      true,
+    );
+
+    assert_eq!(
+      context.index.get_orddefi_pool("odfi", "odgv").unwrap(),
+      Some(orddefi::Pool::default())
+    );
+    assert_orddefi_balance(&context, "odfi", "1000", "0");
+    assert_orddefi_balance(&context, "odgv", "1000", "0");
+    assert_orddefi_balance(&context, "odfi-odgv", "0", "0");
+  }
+
+  #[test]
+  fn orddefi_state_is_rolled_back_on_reorg() {
+    let mut context = Context::builder().arg("--index-orddefi").build();
+    context.index.set_durability(redb::Durability::Immediate);
+
+    inscribe_orddefi(
+      &context,
+      r#"{"p":"orddefi","op":"mint","tick":"odfi","amt":"1000"}"#,
+      true,
+    );
+
+    context.mine_blocks(6);
+
+    let id = inscribe_orddefi(
+      &context,
+      r#"{"p":"orddefi","op":"mint","tick":"odfi","amt":"1000"}"#,
+      true,
+    );
+
+    assert_orddefi_balance(&context, "odfi", "2000", "0");
+
+    context.rpc_server.invalidate_tip();
+    context.mine_blocks(2);
+
+    assert_eq!(context.index.get_orddefi_receipt(id).unwrap(), None);
+    assert_orddefi_balance(&context, "odfi", "1000", "0");
+  }
 }
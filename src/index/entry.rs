@@ -309,6 +309,66 @@ impl Entry for InscriptionId {
   }
 }
 
+pub(super) type OrdDeFiBalanceValue = (
+  u128, // available
+  u128, // transferable
+);
+
+impl Entry for orddefi::Balance {
+  type Value = OrdDeFiBalanceValue;
+
+  fn load((available, transferable): Self::Value) -> Self {
+    Self {
+      available: orddefi::Quantity(available),
+      transferable: orddefi::Quantity(transferable),
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    (self.available.0, self.transferable.0)
+  }
+}
+
+pub(super) type OrdDeFiReceiptValue = (
+  &'static [u8], // destination
+  u32,           // height
+  &'static str,  // instruction
+  u8,            // status
+);
+
+pub(super) type OrdDeFiTransferableValue = (
+  &'static [u8], // owner
+  &'static str,  // tick
+  u128,          // amount
+);
+
+pub(super) type TxOutValue = (
+  &'static [u8], // script pubkey
+  u64,           // value
+);
+
+pub(super) type PoolValue = (
+  u128, // left
+  u128, // right
+  u128, // shares
+);
+
+impl Entry for orddefi::Pool {
+  type Value = PoolValue;
+
+  fn load((left, right, shares): Self::Value) -> Self {
+    Self {
+      left: orddefi::Quantity(left),
+      right: orddefi::Quantity(right),
+      shares: orddefi::Quantity(shares),
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    (self.left.0, self.right.0, self.shares.0)
+  }
+}
+
 pub(super) type OutPointValue = [u8; 36];
 
 impl Entry for OutPoint {
@@ -494,6 +554,29 @@ mod tests {
     );
   }
 
+  #[test]
+  fn orddefi_pool_entry() {
+    let pool = orddefi::Pool {
+      left: orddefi::Quantity(1),
+      right: orddefi::Quantity(2),
+      shares: orddefi::Quantity(3),
+    };
+
+    assert_eq!(pool.store(), (1, 2, 3));
+    assert_eq!(orddefi::Pool::load((1, 2, 3)), pool);
+  }
+
+  #[test]
+  fn orddefi_balance_entry() {
+    let balance = orddefi::Balance {
+      available: orddefi::Quantity(1),
+      transferable: orddefi::Quantity(2),
+    };
+
+    assert_eq!(balance.store(), (1, 2));
+    assert_eq!(orddefi::Balance::load((1, 2)), balance);
+  }
+
   #[test]
   fn header() {
     let expected = [
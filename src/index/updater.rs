@@ -1,5 +1,8 @@
 use {
-  self::{inscription_updater::InscriptionUpdater, rune_updater::RuneUpdater},
+  self::{
+    inscription_updater::InscriptionUpdater, orddefi_updater::OrdDeFiUpdater,
+    rune_updater::RuneUpdater,
+  },
   super::{fetcher::Fetcher, *},
   futures::future::try_join_all,
   std::sync::mpsc,
@@ -7,6 +10,7 @@ use {
 };
 
 mod inscription_updater;
+mod orddefi_updater;
 mod rune_updater;
 
 pub(crate) struct BlockData {
@@ -54,6 +58,15 @@ impl<'index> Updater<'_> {
   }
 
   pub(crate) fn update_index(&mut self) -> Result {
+    // authenticating OrdDeFi instructions looks up commit transactions and
+    // the outputs they spend, which bitcoind can only serve with a txindex
+    if self.index.index_orddefi {
+      ensure!(
+        self.index.client.get_index_info()?.txindex.is_some(),
+        "`--index-orddefi` requires bitcoind to be run with `-txindex`"
+      );
+    }
+
     let mut wtx = self.index.begin_write()?;
     let starting_height = u32::try_from(self.index.client.get_block_count()?).unwrap() + 1;
 
@@ -82,7 +95,12 @@ impl<'index> Updater<'_> {
       Some(progress_bar)
     };
 
-    let rx = Self::fetch_blocks_from(self.index, self.height, self.index.index_sats)?;
+    let rx = Self::fetch_blocks_from(
+      self.index,
+      self.height,
+      self.index.index_sats,
+      self.index.index_addresses,
+    )?;
 
     let (mut outpoint_sender, mut value_receiver) = Self::spawn_fetcher(self.index)?;
 
@@ -160,6 +178,7 @@ impl<'index> Updater<'_> {
     index: &Index,
     mut height: u32,
     index_sats: bool,
+    index_addresses: bool,
   ) -> Result<mpsc::Receiver<BlockData>> {
     let (tx, rx) = mpsc::sync_channel(32);
 
@@ -176,7 +195,13 @@ impl<'index> Updater<'_> {
         }
       }
 
-      match Self::get_block_with_retries(&client, height, index_sats, first_inscription_height) {
+      match Self::get_block_with_retries(
+        &client,
+        height,
+        index_sats,
+        index_addresses,
+        first_inscription_height,
+      ) {
         Ok(Some(block)) => {
           if let Err(err) = tx.send(block.into()) {
             log::info!("Block receiver disconnected: {err}");
@@ -199,6 +224,7 @@ impl<'index> Updater<'_> {
     client: &Client,
     height: u32,
     index_sats: bool,
+    index_addresses: bool,
     first_inscription_height: u32,
   ) -> Result<Option<Block>> {
     let mut errors = 0;
@@ -209,7 +235,7 @@ impl<'index> Updater<'_> {
         .and_then(|option| {
           option
             .map(|hash| {
-              if index_sats || height >= first_inscription_height {
+              if index_sats || index_addresses || height >= first_inscription_height {
                 Ok(client.get_block(&hash)?)
               } else {
                 Ok(Block {
@@ -431,10 +457,12 @@ impl<'index> Updater<'_> {
       home_inscription_count,
       home_inscriptions: &mut home_inscriptions,
       id_to_sequence_number: &mut inscription_id_to_sequence_number,
+      index_orddefi: self.index.index_orddefi,
       index_transactions: self.index.index_transactions,
       inscription_number_to_sequence_number: &mut inscription_number_to_sequence_number,
       lost_sats,
       next_sequence_number,
+      orddefi_events: Vec::new(),
       outpoint_to_value: &mut outpoint_to_value,
       reward: Height(self.height).subsidy(),
       sat_to_sequence_number: &mut sat_to_sequence_number,
@@ -575,6 +603,33 @@ impl<'index> Updater<'_> {
       &inscription_updater.unbound_inscriptions,
     )?;
 
+    if !inscription_updater.orddefi_events.is_empty() {
+      let mut inscription_id_to_receipt = wtx.open_table(ORDDEFI_INSCRIPTION_ID_TO_RECEIPT)?;
+      let mut inscription_id_to_transferable =
+        wtx.open_table(ORDDEFI_INSCRIPTION_ID_TO_TRANSFERABLE)?;
+      let mut pair_to_pool = wtx.open_table(ORDDEFI_PAIR_TO_POOL)?;
+      let mut script_tick_to_balance = wtx.open_table(ORDDEFI_SCRIPT_TICK_TO_BALANCE)?;
+      let mut script_to_inscription_id =
+        wtx.open_multimap_table(ORDDEFI_SCRIPT_TO_INSCRIPTION_ID)?;
+      let mut tick_to_supply = wtx.open_table(ORDDEFI_TICK_TO_SUPPLY)?;
+
+      let mut orddefi_updater = OrdDeFiUpdater {
+        chain: self.index.options.chain(),
+        height: self.height,
+        index: self.index,
+        inscription_id_to_receipt: &mut inscription_id_to_receipt,
+        inscription_id_to_transferable: &mut inscription_id_to_transferable,
+        pair_to_pool: &mut pair_to_pool,
+        script_tick_to_balance: &mut script_tick_to_balance,
+        script_to_inscription_id: &mut script_to_inscription_id,
+        tick_to_supply: &mut tick_to_supply,
+      };
+
+      for event in mem::take(&mut inscription_updater.orddefi_events) {
+        orddefi_updater.index_event(event)?;
+      }
+    }
+
     if index.index_runes && self.height >= self.index.options.first_rune_height() {
       let mut outpoint_to_rune_balances = wtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
       let mut rune_id_to_rune_entry = wtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
@@ -622,6 +677,20 @@ impl<'index> Updater<'_> {
       }
     }
 
+    if self.index.index_addresses {
+      let mut outpoint_to_tx_out = wtx.open_table(OUTPOINT_TO_TX_OUT)?;
+      let mut script_pubkey_to_outpoints = wtx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINTS)?;
+
+      for (tx, txid) in &block.txdata {
+        Self::index_transaction_addresses(
+          tx,
+          *txid,
+          &mut outpoint_to_tx_out,
+          &mut script_pubkey_to_outpoints,
+        )?;
+      }
+    }
+
     height_to_block_header.insert(&self.height, &block.header.store())?;
 
     self.height += 1;
@@ -635,6 +704,46 @@ impl<'index> Updater<'_> {
     Ok(())
   }
 
+  /// Transactions are indexed in block order, so outputs spent later in
+  /// the same block are removed again. `OP_RETURN` outputs are unspendable
+  /// and never indexed.
+  fn index_transaction_addresses(
+    tx: &Transaction,
+    txid: Txid,
+    outpoint_to_tx_out: &mut Table<&OutPointValue, TxOutValue>,
+    script_pubkey_to_outpoints: &mut MultimapTable<&[u8], &OutPointValue>,
+  ) -> Result {
+    for input in &tx.input {
+      let outpoint = input.previous_output.store();
+
+      let Some(script_pubkey) = outpoint_to_tx_out
+        .remove(&outpoint)?
+        .map(|tx_out| tx_out.value().0.to_vec())
+      else {
+        continue;
+      };
+
+      script_pubkey_to_outpoints.remove(script_pubkey.as_slice(), &outpoint)?;
+    }
+
+    for (vout, output) in tx.output.iter().enumerate() {
+      if output.script_pubkey.is_op_return() {
+        continue;
+      }
+
+      let outpoint = OutPoint {
+        vout: vout.try_into().unwrap(),
+        txid,
+      }
+      .store();
+
+      outpoint_to_tx_out.insert(&outpoint, (output.script_pubkey.as_bytes(), output.value))?;
+      script_pubkey_to_outpoints.insert(output.script_pubkey.as_bytes(), &outpoint)?;
+    }
+
+    Ok(())
+  }
+
   fn index_transaction_sats(
     &mut self,
     tx: &Transaction,
@@ -26,6 +26,7 @@ enum Origin {
     cursed: bool,
     fee: u64,
     hidden: bool,
+    orddefi: Option<(OrdDeFiInstruction, OutPoint)>,
     parent: Option<InscriptionId>,
     pointer: Option<u64>,
     reinscription: bool,
@@ -45,10 +46,12 @@ pub(super) struct InscriptionUpdater<'a, 'db, 'tx> {
   pub(super) home_inscription_count: u64,
   pub(super) home_inscriptions: &'a mut Table<'db, 'tx, u32, InscriptionIdValue>,
   pub(super) id_to_sequence_number: &'a mut Table<'db, 'tx, InscriptionIdValue, u32>,
+  pub(super) index_orddefi: bool,
   pub(super) index_transactions: bool,
   pub(super) inscription_number_to_sequence_number: &'a mut Table<'db, 'tx, i32, u32>,
   pub(super) lost_sats: u64,
   pub(super) next_sequence_number: u32,
+  pub(super) orddefi_events: Vec<orddefi_updater::Event>,
   pub(super) outpoint_to_value: &'a mut Table<'db, 'tx, &'static OutPointValue, u64>,
   pub(super) reward: u64,
   pub(super) transaction_buffer: Vec<u8>,
@@ -205,6 +208,12 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
             cursed: curse.is_some(),
             fee: 0,
             hidden: inscription.payload.hidden(),
+            orddefi: if self.index_orddefi {
+              OrdDeFiInstruction::from_inscription(&inscription.payload)
+                .map(|instruction| (instruction, tx_in.previous_output))
+            } else {
+              None
+            },
             parent: inscription.payload.parent(),
             pointer: inscription.payload.pointer(),
             unbound,
@@ -332,6 +341,11 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
         _ => new_satpoint,
       };
 
+      if self.index_orddefi {
+        let owner = &tx.output[usize::try_from(new_satpoint.outpoint.vout).unwrap()].script_pubkey;
+        self.push_orddefi_event(&flotsam, Some(owner));
+      }
+
       self.update_inscription_location(input_sat_ranges, flotsam, new_satpoint)?;
     }
 
@@ -341,6 +355,9 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
           outpoint: OutPoint::null(),
           offset: self.lost_sats + flotsam.offset - output_value,
         };
+        if self.index_orddefi {
+          self.push_orddefi_event(&flotsam, None);
+        }
         self.update_inscription_location(input_sat_ranges, flotsam, new_satpoint)?;
       }
       self.lost_sats += self.reward - output_value;
@@ -355,6 +372,33 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
     }
   }
 
+  /// Record the new owner of an OrdDeFi instruction inscription, or of an
+  /// inscription which was moved and may be a transferable, for the
+  /// `OrdDeFiUpdater`. `owner` is `None` if the inscription was lost.
+  fn push_orddefi_event(&mut self, flotsam: &Flotsam, owner: Option<&Script>) {
+    let owner = owner.map(Script::to_owned);
+
+    match &flotsam.origin {
+      Origin::New {
+        orddefi: Some((instruction, commit)),
+        unbound: false,
+        ..
+      } => self.orddefi_events.push(orddefi_updater::Event::Inscribed {
+        commit: *commit,
+        inscription_id: flotsam.inscription_id,
+        instruction: instruction.clone(),
+        owner,
+      }),
+      Origin::New { .. } => {}
+      Origin::Old { .. } => self
+        .orddefi_events
+        .push(orddefi_updater::Event::Transferred {
+          inscription_id: flotsam.inscription_id,
+          owner,
+        }),
+    }
+  }
+
   fn calculate_sat(
     input_sat_ranges: Option<&VecDeque<(u64, u64)>>,
     input_offset: u64,
@@ -400,6 +444,7 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
         cursed,
         fee,
         hidden,
+        orddefi: _,
         parent,
         pointer: _,
         reinscription,
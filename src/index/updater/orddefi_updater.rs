@@ -0,0 +1,439 @@
+use {
+  super::*,
+  crate::orddefi::{pair, AbortReason, Balance, Pool, Quantity, Status},
+};
+
+#[derive(Debug)]
+pub(super) enum Event {
+  Inscribed {
+    commit: OutPoint,
+    inscription_id: InscriptionId,
+    instruction: OrdDeFiInstruction,
+    owner: Option<ScriptBuf>,
+  },
+  Transferred {
+    inscription_id: InscriptionId,
+    owner: Option<ScriptBuf>,
+  },
+}
+
+pub(super) struct OrdDeFiUpdater<'a, 'db, 'tx> {
+  pub(super) chain: Chain,
+  pub(super) height: u32,
+  pub(super) index: &'a Index,
+  pub(super) inscription_id_to_receipt:
+    &'a mut Table<'db, 'tx, InscriptionIdValue, OrdDeFiReceiptValue>,
+  pub(super) inscription_id_to_transferable:
+    &'a mut Table<'db, 'tx, InscriptionIdValue, OrdDeFiTransferableValue>,
+  pub(super) pair_to_pool: &'a mut Table<'db, 'tx, &'static str, PoolValue>,
+  pub(super) script_tick_to_balance:
+    &'a mut Table<'db, 'tx, (&'static [u8], &'static str), OrdDeFiBalanceValue>,
+  pub(super) script_to_inscription_id:
+    &'a mut MultimapTable<'db, 'tx, &'static [u8], InscriptionIdValue>,
+  pub(super) tick_to_supply: &'a mut Table<'db, 'tx, &'static str, u128>,
+}
+
+impl<'a, 'db, 'tx> OrdDeFiUpdater<'a, 'db, 'tx> {
+  pub(super) fn index_event(&mut self, event: Event) -> Result {
+    match event {
+      Event::Inscribed {
+        commit,
+        inscription_id,
+        instruction,
+        owner: Some(owner),
+      } => {
+        let status = self.execute(inscription_id, &instruction, commit, &owner)?;
+
+        log::debug!("OrdDeFi instruction {inscription_id} {status}");
+
+        self.inscription_id_to_receipt.insert(
+          &inscription_id.store(),
+          (
+            owner.as_bytes(),
+            self.height,
+            instruction.to_string().as_str(),
+            status.code(),
+          ),
+        )?;
+
+        self
+          .script_to_inscription_id
+          .insert(owner.as_bytes(), &inscription_id.store())?;
+      }
+      Event::Inscribed { owner: None, .. } => {}
+      Event::Transferred {
+        inscription_id,
+        owner,
+      } => {
+        let Some((sender, tick, amount)) = self
+          .inscription_id_to_transferable
+          .remove(&inscription_id.store())?
+          .map(|transferable| {
+            let (sender, tick, amount) = transferable.value();
+            (sender.to_vec(), tick.to_string(), Quantity(amount))
+          })
+        else {
+          return Ok(());
+        };
+
+        let mut balance = self.balance(&sender, &tick)?;
+        balance.transferable = balance
+          .transferable
+          .checked_sub(amount)
+          .ok_or_else(|| anyhow!("transferable {inscription_id} exceeds balance"))?;
+        self.set_balance(&sender, &tick, balance)?;
+
+        // transfers which are lost return to their sender
+        let recipient = owner.map(ScriptBuf::into_bytes).unwrap_or(sender);
+
+        self.credit(&recipient, &tick, amount)?;
+      }
+    }
+
+    Ok(())
+  }
+
+  fn execute(
+    &mut self,
+    inscription_id: InscriptionId,
+    instruction: &OrdDeFiInstruction,
+    commit: OutPoint,
+    owner: &Script,
+  ) -> Result<Status> {
+    if instruction.validate(self.chain).is_err() {
+      return Ok(Status::Aborted(AbortReason::InvalidInstruction));
+    }
+
+    if instruction.requires_authentication() && !self.authenticated(commit, owner)? {
+      return Ok(Status::Aborted(AbortReason::BadAuth));
+    }
+
+    let owner = owner.as_bytes();
+
+    let reason = match instruction {
+      OrdDeFiInstruction::Mint { tick, amt } => self.mint(owner, tick, amt)?,
+      OrdDeFiInstruction::Transfer { tick, amt, to } => {
+        self.transfer(inscription_id, owner, tick, amt, to.as_deref())?
+      }
+      OrdDeFiInstruction::Addlp {
+        ltick,
+        lamt,
+        rtick,
+        ramt,
+      } => self.add_liquidity(owner, ltick, lamt, rtick, ramt)?,
+      OrdDeFiInstruction::Rmlp { ltick, rtick, amt } => {
+        self.remove_liquidity(owner, ltick, rtick, amt)?
+      }
+      OrdDeFiInstruction::Swap {
+        ltick,
+        rtick,
+        spend,
+        amt,
+      } => self.swap(owner, ltick, rtick, spend, amt)?,
+    };
+
+    Ok(match reason {
+      Some(reason) => Status::Aborted(reason),
+      None => Status::Executed,
+    })
+  }
+
+  /// An instruction is authenticated if its commit transaction has an
+  /// `orddefi:auth` `OP_RETURN` output and spends only outputs of the script
+  /// the instruction was inscribed to. The commit and the transactions it
+  /// spends are confirmed, so failing to fetch them is an indexing error.
+  fn authenticated(&self, commit: OutPoint, owner: &Script) -> Result<bool> {
+    let commit = self
+      .index
+      .get_transaction(commit.txid)?
+      .ok_or_else(|| anyhow!("failed to fetch commit transaction {}", commit.txid))?;
+
+    let auth = script::Builder::new()
+      .push_opcode(opcodes::all::OP_RETURN)
+      .push_slice(<&script::PushBytes>::try_from(orddefi::AUTH_PAYLOAD.as_bytes()).unwrap())
+      .into_script();
+
+    if !commit
+      .output
+      .iter()
+      .any(|output| output.script_pubkey == auth)
+    {
+      return Ok(false);
+    }
+
+    for input in &commit.input {
+      let previous_output = input.previous_output;
+
+      let transaction = self
+        .index
+        .get_transaction(previous_output.txid)?
+        .ok_or_else(|| anyhow!("failed to fetch transaction {}", previous_output.txid))?;
+
+      let spent = usize::try_from(previous_output.vout)
+        .ok()
+        .and_then(|vout| transaction.output.get(vout));
+
+      if spent.map(|output| output.script_pubkey.as_script()) != Some(owner) {
+        return Ok(false);
+      }
+    }
+
+    Ok(true)
+  }
+
+  fn mint(&mut self, owner: &[u8], tick: &str, amt: &str) -> Result<Option<AbortReason>> {
+    let Some(terms) = self.chain.orddefi_mint_terms(tick) else {
+      return Ok(Some(AbortReason::UnknownTick));
+    };
+
+    let Ok(amount) = amt.parse::<Quantity>() else {
+      return Ok(Some(AbortReason::InvalidInstruction));
+    };
+
+    if amount > terms.limit {
+      return Ok(Some(AbortReason::MintLimit));
+    }
+
+    let supply = self
+      .tick_to_supply
+      .get(tick)?
+      .map(|supply| Quantity(supply.value()))
+      .unwrap_or_default();
+
+    let Some(supply) = supply
+      .checked_add(amount)
+      .filter(|supply| *supply <= terms.supply)
+    else {
+      return Ok(Some(AbortReason::SupplyExhausted));
+    };
+
+    self.tick_to_supply.insert(tick, supply.0)?;
+
+    self.credit(owner, tick, amount)?;
+
+    Ok(None)
+  }
+
+  fn transfer(
+    &mut self,
+    inscription_id: InscriptionId,
+    owner: &[u8],
+    tick: &str,
+    amt: &str,
+    to: Option<&str>,
+  ) -> Result<Option<AbortReason>> {
+    if !self.is_known_tick(tick)? {
+      return Ok(Some(AbortReason::UnknownTick));
+    }
+
+    let Ok(amount) = amt.parse::<Quantity>() else {
+      return Ok(Some(AbortReason::InvalidInstruction));
+    };
+
+    let mut balance = self.balance(owner, tick)?;
+
+    let Some(available) = balance.available.checked_sub(amount) else {
+      return Ok(Some(AbortReason::InsufficientBalance));
+    };
+
+    match to {
+      Some(to) => {
+        let Some(recipient) = to
+          .parse::<Address<NetworkUnchecked>>()
+          .ok()
+          .and_then(|address| address.require_network(self.chain.network()).ok())
+        else {
+          return Ok(Some(AbortReason::InvalidAddress));
+        };
+
+        self.debit(owner, tick, amount)?;
+        self.credit(recipient.script_pubkey().as_bytes(), tick, amount)?;
+      }
+      None => {
+        balance.available = available;
+        balance.transferable = balance
+          .transferable
+          .checked_add(amount)
+          .ok_or_else(|| anyhow!("transferable balance overflow"))?;
+        self.set_balance(owner, tick, balance)?;
+
+        self
+          .inscription_id_to_transferable
+          .insert(&inscription_id.store(), (owner, tick, amount.0))?;
+      }
+    }
+
+    Ok(None)
+  }
+
+  fn add_liquidity(
+    &mut self,
+    owner: &[u8],
+    ltick: &str,
+    lamt: &str,
+    rtick: &str,
+    ramt: &str,
+  ) -> Result<Option<AbortReason>> {
+    if !self.is_known_tick(ltick)? || !self.is_known_tick(rtick)? {
+      return Ok(Some(AbortReason::UnknownTick));
+    }
+
+    let (Ok(lamt), Ok(ramt)) = (lamt.parse::<Quantity>(), ramt.parse::<Quantity>()) else {
+      return Ok(Some(AbortReason::InvalidInstruction));
+    };
+
+    let ((left_tick, left), (right_tick, right)) = if ltick < rtick {
+      ((ltick, lamt), (rtick, ramt))
+    } else {
+      ((rtick, ramt), (ltick, lamt))
+    };
+
+    if self.balance(owner, left_tick)?.available < left
+      || self.balance(owner, right_tick)?.available < right
+    {
+      return Ok(Some(AbortReason::InsufficientBalance));
+    }
+
+    let pair = pair(ltick, rtick);
+
+    let mut pool = self.pool(&pair)?.unwrap_or_default();
+
+    let Some((left, right, shares)) = pool.add_liquidity(left, right) else {
+      return Ok(Some(AbortReason::InsufficientLiquidity));
+    };
+
+    self.debit(owner, left_tick, left)?;
+    self.debit(owner, right_tick, right)?;
+    self.credit(owner, &pair, shares)?;
+    self.pair_to_pool.insert(pair.as_str(), pool.store())?;
+
+    Ok(None)
+  }
+
+  fn remove_liquidity(
+    &mut self,
+    owner: &[u8],
+    ltick: &str,
+    rtick: &str,
+    amt: &str,
+  ) -> Result<Option<AbortReason>> {
+    let pair = pair(ltick, rtick);
+
+    let Some(mut pool) = self.pool(&pair)? else {
+      return Ok(Some(AbortReason::PoolNotFound));
+    };
+
+    let Ok(shares) = amt.parse::<Quantity>() else {
+      return Ok(Some(AbortReason::InvalidInstruction));
+    };
+
+    if self.balance(owner, &pair)?.available < shares {
+      return Ok(Some(AbortReason::InsufficientBalance));
+    }
+
+    let Some((left, right)) = pool.remove_liquidity(shares) else {
+      return Ok(Some(AbortReason::InsufficientLiquidity));
+    };
+
+    let (left_tick, right_tick) = if ltick < rtick {
+      (ltick, rtick)
+    } else {
+      (rtick, ltick)
+    };
+
+    self.debit(owner, &pair, shares)?;
+    self.credit(owner, left_tick, left)?;
+    self.credit(owner, right_tick, right)?;
+    self.pair_to_pool.insert(pair.as_str(), pool.store())?;
+
+    Ok(None)
+  }
+
+  fn swap(
+    &mut self,
+    owner: &[u8],
+    ltick: &str,
+    rtick: &str,
+    spend: &str,
+    amt: &str,
+  ) -> Result<Option<AbortReason>> {
+    let pair = pair(ltick, rtick);
+
+    let Some(mut pool) = self.pool(&pair)? else {
+      return Ok(Some(AbortReason::PoolNotFound));
+    };
+
+    let Ok(amount) = amt.parse::<Quantity>() else {
+      return Ok(Some(AbortReason::InvalidInstruction));
+    };
+
+    if self.balance(owner, spend)?.available < amount {
+      return Ok(Some(AbortReason::InsufficientBalance));
+    }
+
+    let receive = if spend == ltick { rtick } else { ltick };
+
+    let Some(output) = pool.swap(spend < receive, amount) else {
+      return Ok(Some(AbortReason::InsufficientLiquidity));
+    };
+
+    self.debit(owner, spend, amount)?;
+    self.credit(owner, receive, output)?;
+    self.pair_to_pool.insert(pair.as_str(), pool.store())?;
+
+    Ok(None)
+  }
+
+  fn is_known_tick(&self, tick: &str) -> Result<bool> {
+    Ok(self.tick_to_supply.get(tick)?.is_some())
+  }
+
+  fn pool(&self, pair: &str) -> Result<Option<Pool>> {
+    Ok(
+      self
+        .pair_to_pool
+        .get(pair)?
+        .map(|pool| Pool::load(pool.value())),
+    )
+  }
+
+  fn balance(&self, script: &[u8], tick: &str) -> Result<Balance> {
+    Ok(
+      self
+        .script_tick_to_balance
+        .get((script, tick))?
+        .map(|balance| Balance::load(balance.value()))
+        .unwrap_or_default(),
+    )
+  }
+
+  fn set_balance(&mut self, script: &[u8], tick: &str, balance: Balance) -> Result {
+    if balance == Balance::default() {
+      self.script_tick_to_balance.remove((script, tick))?;
+    } else {
+      self
+        .script_tick_to_balance
+        .insert((script, tick), balance.store())?;
+    }
+
+    Ok(())
+  }
+
+  fn credit(&mut self, script: &[u8], tick: &str, amount: Quantity) -> Result {
+    let mut balance = self.balance(script, tick)?;
+    balance.available = balance
+      .available
+      .checked_add(amount)
+      .ok_or_else(|| anyhow!("balance overflow"))?;
+    self.set_balance(script, tick, balance)
+  }
+
+  fn debit(&mut self, script: &[u8], tick: &str, amount: Quantity) -> Result {
+    let mut balance = self.balance(script, tick)?;
+    balance.available = balance
+      .available
+      .checked_sub(amount)
+      .ok_or_else(|| anyhow!("balance underflow"))?;
+    self.set_balance(script, tick, balance)
+  }
+}
@@ -8,7 +8,9 @@ use {
     },
     ScriptBuf,
   },
-  brotli::enc::{writer::CompressorWriter, BrotliEncoderParams},
+  brotli::enc::{
+    backward_references::BrotliEncoderMode, writer::CompressorWriter, BrotliEncoderParams,
+  },
   http::header::HeaderValue,
   io::{Cursor, Read, Write},
   std::str,
@@ -54,6 +56,30 @@ impl Inscription {
 
     let (content_type, compression_mode) = Media::content_type_for_path(path)?;
 
+    Self::from_body(
+      chain,
+      body,
+      content_type,
+      compression_mode,
+      parent,
+      pointer,
+      metaprotocol,
+      metadata,
+      compress,
+    )
+  }
+
+  pub(crate) fn from_body(
+    chain: Chain,
+    body: Vec<u8>,
+    content_type: &str,
+    compression_mode: BrotliEncoderMode,
+    parent: Option<InscriptionId>,
+    pointer: Option<u64>,
+    metaprotocol: Option<String>,
+    metadata: Option<Vec<u8>>,
+    compress: bool,
+  ) -> Result<Self, Error> {
     let (body, content_encoding) = if compress {
       let mut compressed = Vec::new();
 
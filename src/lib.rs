@@ -14,7 +14,7 @@ use {
   self::{
     arguments::Arguments,
     blocktime::Blocktime,
-    config::Config,
+    config::{Config, Profile},
     decimal::Decimal,
     decimal_sat::DecimalSat,
     degree::Degree,
@@ -26,7 +26,7 @@ use {
     outgoing::Outgoing,
     representation::Representation,
     runes::{Etching, Pile, SpacedRune},
-    subcommand::{Subcommand, SubcommandResult},
+    subcommand::{OutputFormat, Subcommand, SubcommandResult},
     tally::Tally,
   },
   anyhow::{anyhow, bail, ensure, Context, Error},
@@ -89,11 +89,14 @@ pub use self::{
   inscriptions::{Envelope, Inscription, InscriptionId},
   object::Object,
   options::Options,
+  orddefi::OrdDeFiInstruction,
   rarity::Rarity,
   runes::{Edict, Rune, RuneId, Runestone},
   sat::Sat,
   sat_point::SatPoint,
-  subcommand::wallet::transaction_builder::{Target, TransactionBuilder},
+  subcommand::wallet::transaction_builder::{
+    AuthOutput, AuthOutputPosition, Target, TransactionBuilder,
+  },
 };
 
 #[cfg(test)]
@@ -128,6 +131,7 @@ mod index;
 mod inscriptions;
 mod object;
 mod options;
+pub mod orddefi;
 mod outgoing;
 pub mod rarity;
 mod representation;
@@ -213,14 +217,25 @@ fn gracefully_shutdown_indexer() {
 }
 
 pub fn main() {
-  env_logger::init();
+  let arguments = Arguments::parse();
+
+  let format = arguments.options.format;
+
+  env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(
+    if arguments.options.verbose {
+      "info"
+    } else {
+      "warn"
+    },
+  ))
+  .init();
 
   ctrlc::set_handler(move || {
     if SHUTTING_DOWN.fetch_or(true, atomic::Ordering::Relaxed) {
       process::exit(1);
     }
 
-    println!("Shutting down gracefully. Press <CTRL-C> again to shutdown immediately.");
+    eprintln!("Shutting down gracefully. Press <CTRL-C> again to shutdown immediately.");
 
     LISTENERS
       .lock()
@@ -230,7 +245,7 @@ pub fn main() {
   })
   .expect("Error setting <CTRL-C> handler");
 
-  match Arguments::parse().run() {
+  match arguments.run() {
     Err(err) => {
       eprintln!("error: {err}");
       err
@@ -248,7 +263,7 @@ pub fn main() {
 
       process::exit(1);
     }
-    Ok(output) => output.print_json(),
+    Ok(output) => output.print(format),
   }
 
   gracefully_shutdown_indexer();
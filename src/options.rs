@@ -28,6 +28,14 @@ pub struct Options {
   pub(crate) cookie_file: Option<PathBuf>,
   #[arg(long, help = "Store index in <DATA_DIR>.", default_value_os_t = Options::default_data_dir())]
   pub(crate) data_dir: PathBuf,
+  #[arg(
+    long,
+    global = true,
+    value_enum,
+    default_value = "json",
+    help = "Print output as <FORMAT>. Diagnostics are always written to stderr."
+  )]
+  pub(crate) format: OutputFormat,
   #[arg(
     long,
     help = "Set index cache to <DB_CACHE_SIZE> bytes. By default takes 1/4 of available RAM."
@@ -40,8 +48,28 @@ pub struct Options {
   pub(crate) first_inscription_height: Option<u32>,
   #[arg(long, help = "Limit index to <HEIGHT_LIMIT> blocks.")]
   pub(crate) height_limit: Option<u32>,
+  #[arg(
+    long,
+    help = "Never use an estimated fee rate above <MAX_FEE_RATE> sats/vB. Overrides `max_fee_rate` in the config file."
+  )]
+  pub(crate) max_fee_rate: Option<FeeRate>,
+  #[arg(
+    long,
+    help = "Never use an estimated fee rate below <MIN_FEE_RATE> sats/vB. Overrides `min_fee_rate` in the config file."
+  )]
+  pub(crate) min_fee_rate: Option<FeeRate>,
   #[arg(long, help = "Use index at <INDEX>.")]
   pub(crate) index: Option<PathBuf>,
+  #[arg(
+    long,
+    help = "Track unspent outputs of every script pubkey, to list them by address."
+  )]
+  pub(crate) index_addresses: bool,
+  #[arg(
+    long,
+    help = "Track OrdDeFi balances, LP shares and pool reserves by executing OrdDeFi instructions."
+  )]
+  pub(crate) index_orddefi: bool,
   #[arg(
     long,
     help = "Track location of runes. RUNES ARE IN AN UNFINISHED PRE-ALPHA STATE AND SUBJECT TO CHANGE AT ANY TIME."
@@ -66,6 +94,12 @@ pub struct Options {
   pub(crate) signet: bool,
   #[arg(long, short, help = "Use testnet. Equivalent to `--chain testnet`.")]
   pub(crate) testnet: bool,
+  #[arg(
+    long,
+    global = true,
+    help = "Log diagnostics, such as raw transactions, to stderr. `RUST_LOG` takes precedence."
+  )]
+  pub(crate) verbose: bool,
 }
 
 impl Options {
@@ -159,6 +193,22 @@ impl Options {
     }
   }
 
+  pub(crate) fn fee_rate_limits(&self) -> Result<(Option<FeeRate>, Option<FeeRate>)> {
+    let config = self.load_config()?;
+
+    let min = self.min_fee_rate.or(config.min_fee_rate);
+    let max = self.max_fee_rate.or(config.max_fee_rate);
+
+    if let (Some(min), Some(max)) = (min, max) {
+      ensure!(
+        min.n() <= max.n(),
+        "minimum fee rate {min} is greater than maximum fee rate {max}"
+      );
+    }
+
+    Ok((min, max))
+  }
+
   fn derive_var(
     arg_value: Option<&str>,
     env_key: Option<&str>,
@@ -580,10 +630,35 @@ mod tests {
   #[test]
   fn wallet_flag_overrides_default_name() {
     let (_, wallet) = parse_wallet_args("ord wallet create");
-    assert_eq!(wallet.name, "ord");
+    assert_eq!(wallet.wallet_name(&Profile::default()), "ord");
 
     let (_, wallet) = parse_wallet_args("ord wallet --name foo create");
-    assert_eq!(wallet.name, "foo")
+    assert_eq!(wallet.wallet_name(&Profile::default()), "foo")
+  }
+
+  #[test]
+  fn format_and_verbose_may_follow_wallet_subcommands() {
+    let (options, _) = parse_wallet_args("ord wallet balance");
+    assert_eq!(options.format, OutputFormat::Json);
+    assert!(!options.verbose);
+
+    let (options, _) = parse_wallet_args("ord wallet --format yaml balance --verbose");
+    assert_eq!(options.format, OutputFormat::Yaml);
+    assert!(options.verbose);
+  }
+
+  #[test]
+  fn wallet_flag_overrides_profile_wallet() {
+    let profile = Profile {
+      wallet: Some("bar".into()),
+      ..Default::default()
+    };
+
+    let (_, wallet) = parse_wallet_args("ord wallet --profile mm1 create");
+    assert_eq!(wallet.wallet_name(&profile), "bar");
+
+    let (_, wallet) = parse_wallet_args("ord wallet --profile mm1 --name foo create");
+    assert_eq!(wallet.wallet_name(&profile), "foo")
   }
 
   #[test]
@@ -655,6 +730,63 @@ mod tests {
     );
   }
 
+  #[test]
+  fn fee_rate_limits_are_loaded_from_config_and_overridden_by_options() {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("ord.yaml");
+    fs::write(&path, "hidden: []\nmin_fee_rate: 2\nmax_fee_rate: 100").unwrap();
+
+    assert_eq!(
+      Arguments::try_parse_from(["ord", "--config", path.to_str().unwrap(), "index", "update"])
+        .unwrap()
+        .options
+        .fee_rate_limits()
+        .unwrap(),
+      (
+        Some(FeeRate::try_from(2.0).unwrap()),
+        Some(FeeRate::try_from(100.0).unwrap())
+      ),
+    );
+
+    assert_eq!(
+      Arguments::try_parse_from([
+        "ord",
+        "--config",
+        path.to_str().unwrap(),
+        "--min-fee-rate",
+        "5",
+        "index",
+        "update"
+      ])
+      .unwrap()
+      .options
+      .fee_rate_limits()
+      .unwrap(),
+      (
+        Some(FeeRate::try_from(5.0).unwrap()),
+        Some(FeeRate::try_from(100.0).unwrap())
+      ),
+    );
+
+    assert_eq!(
+      Arguments::try_parse_from([
+        "ord",
+        "--min-fee-rate",
+        "5",
+        "--max-fee-rate",
+        "1",
+        "index",
+        "update"
+      ])
+      .unwrap()
+      .options
+      .fee_rate_limits()
+      .unwrap_err()
+      .to_string(),
+      "minimum fee rate 5 is greater than maximum fee rate 1",
+    );
+  }
+
   #[test]
   fn config_is_loaded_from_config_dir_option_path() {
     let id = "8d363b28528b0cb86b5fd48615493fb175bdf132d2a3d20b4251bba3f130a5abi0"
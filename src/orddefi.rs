@@ -0,0 +1,71 @@
+use super::*;
+
+pub use self::{
+  balance::Balance,
+  instruction::OrdDeFiInstruction,
+  pool::Pool,
+  preview::{Outcome, Preview},
+  quantity::Quantity,
+  receipt::Receipt,
+  status::{AbortReason, Status},
+};
+
+pub const PROTOCOL_ID: &str = "orddefi";
+
+/// Payload of the `OP_RETURN` output which marks a commit transaction as
+/// authenticating its instruction.
+pub const AUTH_PAYLOAD: &str = "orddefi:auth";
+
+/// Terms under which a tick can be minted, see `Chain::orddefi_mint_terms`.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct MintTerms {
+  /// Most that a single `mint` instruction may claim
+  pub limit: Quantity,
+  /// Most that can be minted in total
+  pub supply: Quantity,
+}
+
+mod balance;
+mod instruction;
+mod pool;
+mod preview;
+mod quantity;
+mod receipt;
+mod status;
+
+/// Pools are identified by their two ticks in lexicographic order, joined by
+/// `-`. LP shares of a pool are tracked as a balance of this tick.
+pub fn pair(ltick: &str, rtick: &str) -> String {
+  if ltick <= rtick {
+    format!("{ltick}-{rtick}")
+  } else {
+    format!("{rtick}-{ltick}")
+  }
+}
+
+/// The two ticks of a pair created by `pair`. Ticks may themselves contain
+/// `-`, so the pair is split after the first tick.
+pub fn split_pair(pair: &str) -> Option<(&str, &str)> {
+  let (i, separator) = pair.char_indices().nth(OrdDeFiInstruction::TICK_LENGTH)?;
+
+  (separator == '-').then(|| (&pair[..i], &pair[i + 1..]))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn pair_is_sorted() {
+    assert_eq!(pair("odfi", "odgv"), "odfi-odgv");
+    assert_eq!(pair("odgv", "odfi"), "odfi-odgv");
+  }
+
+  #[test]
+  fn split_pair_returns_ticks() {
+    assert_eq!(split_pair("odfi-odgv"), Some(("odfi", "odgv")));
+    assert_eq!(split_pair("a-bc-def"), Some(("a-bc", "def")));
+    assert_eq!(split_pair("odfiodgv"), None);
+    assert_eq!(split_pair("odf"), None);
+  }
+}
@@ -0,0 +1,16 @@
+use super::*;
+
+/// A tick balance held by a script. Available coins can be spent by
+/// instructions, transferable coins are locked in transfer inscriptions
+/// until those are sent.
+#[derive(Debug, PartialEq, Copy, Clone, Default, Serialize, Deserialize)]
+pub struct Balance {
+  pub available: Quantity,
+  pub transferable: Quantity,
+}
+
+impl Balance {
+  pub fn total(self) -> Option<Quantity> {
+    self.available.checked_add(self.transferable)
+  }
+}
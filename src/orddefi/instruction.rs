@@ -0,0 +1,546 @@
+use {
+  super::*, brotli::enc::backward_references::BrotliEncoderMode::BROTLI_MODE_TEXT, std::io::Read,
+};
+
+/// An OrdDeFi instruction, serialized as the JSON body of a text inscription.
+/// Field order matches the canonical form used by the protocol, e.g.
+/// `{"p":"orddefi","op":"mint","tick":"odfi","amt":"1000"}`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum OrdDeFiInstruction {
+  Mint {
+    tick: String,
+    amt: String,
+  },
+  Transfer {
+    tick: String,
+    amt: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    to: Option<String>,
+  },
+  Addlp {
+    ltick: String,
+    lamt: String,
+    rtick: String,
+    ramt: String,
+  },
+  Rmlp {
+    ltick: String,
+    rtick: String,
+    amt: String,
+  },
+  Swap {
+    ltick: String,
+    rtick: String,
+    spend: String,
+    amt: String,
+  },
+}
+
+#[derive(Serialize, Deserialize)]
+struct Document<T> {
+  p: String,
+  #[serde(flatten)]
+  instruction: T,
+}
+
+impl OrdDeFiInstruction {
+  pub const CONTENT_TYPE: &'static str = "text/plain;charset=utf-8";
+  pub const MAX_BODY_SIZE: u64 = 1024;
+  pub const MAX_DECIMALS: usize = 8;
+  pub const OPS: [&'static str; 5] = ["mint", "transfer", "addlp", "rmlp", "swap"];
+  pub const TICK_LENGTH: usize = 4;
+
+  pub fn op(&self) -> &'static str {
+    match self {
+      Self::Mint { .. } => "mint",
+      Self::Transfer { .. } => "transfer",
+      Self::Addlp { .. } => "addlp",
+      Self::Rmlp { .. } => "rmlp",
+      Self::Swap { .. } => "swap",
+    }
+  }
+
+  /// The pool an `addlp`, `rmlp` or `swap` instruction acts on.
+  pub fn pair(&self) -> Option<String> {
+    match self {
+      Self::Addlp { ltick, rtick, .. }
+      | Self::Rmlp { ltick, rtick, .. }
+      | Self::Swap { ltick, rtick, .. } => Some(pair(ltick, rtick)),
+      Self::Mint { .. } | Self::Transfer { .. } => None,
+    }
+  }
+
+  /// Instructions which are only executed when the commit transaction is
+  /// funded by the destination address, which is how the protocol
+  /// authenticates the destination. Transfers without `to` only create a
+  /// transferable inscription and need no authentication.
+  pub fn requires_authentication(&self) -> bool {
+    match self {
+      Self::Transfer { to, .. } => to.is_some(),
+      Self::Mint { .. } | Self::Addlp { .. } | Self::Rmlp { .. } | Self::Swap { .. } => true,
+    }
+  }
+
+  /// Check that the instruction would be accepted by the protocol: ticks
+  /// have the right length, amounts are positive decimals with at most
+  /// `MAX_DECIMALS` places, pairs are made of two distinct ticks, and swaps
+  /// spend one side of their pair.
+  pub fn validate(&self, chain: Chain) -> Result {
+    match self {
+      Self::Mint { tick, amt } => {
+        Self::check_tick("tick", tick)?;
+        Self::check_amount("amt", amt)?;
+      }
+      Self::Transfer { tick, amt, to } => {
+        Self::check_tick("tick", tick)?;
+        Self::check_amount("amt", amt)?;
+        if let Some(to) = to {
+          to.parse::<Address<NetworkUnchecked>>()
+            .with_context(|| format!("invalid `to` address `{to}`"))?
+            .require_network(chain.network())
+            .with_context(|| format!("`to` address `{to}` is not valid on {chain}"))?;
+        }
+      }
+      Self::Addlp {
+        ltick,
+        lamt,
+        rtick,
+        ramt,
+      } => {
+        Self::check_pair(ltick, rtick)?;
+        Self::check_amount("lamt", lamt)?;
+        Self::check_amount("ramt", ramt)?;
+      }
+      Self::Rmlp { ltick, rtick, amt } => {
+        Self::check_pair(ltick, rtick)?;
+        Self::check_amount("amt", amt)?;
+      }
+      Self::Swap {
+        ltick,
+        rtick,
+        spend,
+        amt,
+      } => {
+        Self::check_pair(ltick, rtick)?;
+        ensure!(
+          spend == ltick || spend == rtick,
+          "`spend` tick `{spend}` is not part of pair `{ltick}-{rtick}`",
+        );
+        Self::check_amount("amt", amt)?;
+      }
+    }
+
+    Ok(())
+  }
+
+  fn check_tick(field: &str, tick: &str) -> Result {
+    let length = tick.chars().count();
+
+    ensure!(
+      length == Self::TICK_LENGTH,
+      "`{field}` `{tick}` must be {} characters long, not {length}",
+      Self::TICK_LENGTH,
+    );
+
+    Ok(())
+  }
+
+  fn check_pair(ltick: &str, rtick: &str) -> Result {
+    Self::check_tick("ltick", ltick)?;
+    Self::check_tick("rtick", rtick)?;
+
+    ensure!(
+      ltick != rtick,
+      "`ltick` and `rtick` must differ, both are `{ltick}`"
+    );
+
+    Ok(())
+  }
+
+  fn check_amount(field: &str, amount: &str) -> Result {
+    let (integer, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+
+    ensure!(
+      !integer.is_empty()
+        && integer.chars().all(|c| c.is_ascii_digit())
+        && fraction.chars().all(|c| c.is_ascii_digit())
+        && !amount.ends_with('.'),
+      "`{field}` `{amount}` is not a decimal number",
+    );
+
+    ensure!(
+      fraction.len() <= Self::MAX_DECIMALS,
+      "`{field}` `{amount}` has more than {} decimal places",
+      Self::MAX_DECIMALS,
+    );
+
+    ensure!(
+      amount.chars().any(|c| ('1'..='9').contains(&c)),
+      "`{field}` must be greater than zero",
+    );
+
+    Ok(())
+  }
+
+  /// Parse the instruction carried by `inscription`, if any. Brotli encoded
+  /// bodies are decompressed, up to `MAX_BODY_SIZE` bytes, so compressed
+  /// instructions are recognized too.
+  pub(crate) fn from_inscription(inscription: &Inscription) -> Option<Self> {
+    let body = inscription.body()?;
+
+    let body = match inscription.content_encoding() {
+      None => body.to_vec(),
+      Some(encoding) if encoding == "br" => {
+        let mut decompressed = Vec::new();
+        brotli::Decompressor::new(body, body.len())
+          .take(Self::MAX_BODY_SIZE)
+          .read_to_end(&mut decompressed)
+          .ok()?;
+        decompressed
+      }
+      Some(_) => return None,
+    };
+
+    std::str::from_utf8(&body).ok()?.parse().ok()
+  }
+
+  pub(crate) fn inscription(
+    &self,
+    chain: Chain,
+    parent: Option<InscriptionId>,
+    metaprotocol: Option<String>,
+    metadata: Option<Vec<u8>>,
+    compress: bool,
+  ) -> Result<Inscription> {
+    Inscription::from_body(
+      chain,
+      self.to_string().into_bytes(),
+      Self::CONTENT_TYPE,
+      BROTLI_MODE_TEXT,
+      parent,
+      None,
+      metaprotocol,
+      metadata,
+      compress,
+    )
+  }
+}
+
+impl Display for OrdDeFiInstruction {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let json = serde_json::to_string(&Document {
+      p: PROTOCOL_ID.into(),
+      instruction: self,
+    })
+    .map_err(|_| fmt::Error)?;
+
+    write!(f, "{json}")
+  }
+}
+
+impl FromStr for OrdDeFiInstruction {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let value = serde_json::from_str::<serde_json::Value>(s).context("instruction is not JSON")?;
+
+    let fields = value
+      .as_object()
+      .ok_or_else(|| anyhow!("instruction is not a JSON object"))?;
+
+    match fields.get("p").and_then(serde_json::Value::as_str) {
+      Some(PROTOCOL_ID) => {}
+      Some(p) => bail!("unknown protocol `{p}`, expected `{PROTOCOL_ID}`"),
+      None => bail!("instruction is missing protocol field `p`"),
+    }
+
+    let op = match fields.get("op").and_then(serde_json::Value::as_str) {
+      Some(op) if Self::OPS.contains(&op) => op,
+      Some(op) => bail!(
+        "unknown op `{op}`, expected one of {}",
+        Self::OPS.join(", ")
+      ),
+      None => bail!("instruction is missing op field `op`"),
+    };
+
+    let document = serde_json::from_value::<Document<Self>>(value.clone())
+      .map_err(|err| anyhow!("invalid `{op}` instruction: {err}"))?;
+
+    let known = serde_json::to_value(&document)?;
+
+    if let Some(field) = fields.keys().find(|field| known.get(field).is_none()) {
+      bail!("unknown field `{field}` in `{op}` instruction");
+    }
+
+    Ok(document.instruction)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn display_is_canonical() {
+    #[track_caller]
+    fn case(instruction: OrdDeFiInstruction, expected: &str) {
+      assert_eq!(instruction.to_string(), expected);
+      assert_eq!(expected.parse::<OrdDeFiInstruction>().unwrap(), instruction);
+    }
+
+    case(
+      OrdDeFiInstruction::Mint {
+        tick: "odfi".into(),
+        amt: "1000".into(),
+      },
+      r#"{"p":"orddefi","op":"mint","tick":"odfi","amt":"1000"}"#,
+    );
+
+    case(
+      OrdDeFiInstruction::Transfer {
+        tick: "odgv".into(),
+        amt: "1000".into(),
+        to: None,
+      },
+      r#"{"p":"orddefi","op":"transfer","tick":"odgv","amt":"1000"}"#,
+    );
+
+    case(
+      OrdDeFiInstruction::Transfer {
+        tick: "odfi".into(),
+        amt: "1000".into(),
+        to: Some("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4".into()),
+      },
+      r#"{"p":"orddefi","op":"transfer","tick":"odfi","amt":"1000","to":"bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"}"#,
+    );
+
+    case(
+      OrdDeFiInstruction::Addlp {
+        ltick: "odfi".into(),
+        lamt: "200".into(),
+        rtick: "odgv".into(),
+        ramt: "345.6".into(),
+      },
+      r#"{"p":"orddefi","op":"addlp","ltick":"odfi","lamt":"200","rtick":"odgv","ramt":"345.6"}"#,
+    );
+
+    case(
+      OrdDeFiInstruction::Rmlp {
+        ltick: "odfi".into(),
+        rtick: "odgv".into(),
+        amt: "200".into(),
+      },
+      r#"{"p":"orddefi","op":"rmlp","ltick":"odfi","rtick":"odgv","amt":"200"}"#,
+    );
+
+    case(
+      OrdDeFiInstruction::Swap {
+        ltick: "odfi".into(),
+        rtick: "odgv".into(),
+        spend: "odfi".into(),
+        amt: "100".into(),
+      },
+      r#"{"p":"orddefi","op":"swap","ltick":"odfi","rtick":"odgv","spend":"odfi","amt":"100"}"#,
+    );
+  }
+
+  #[test]
+  fn demo_instructions_round_trip() {
+    for entry in fs::read_dir("instruction_demo").unwrap() {
+      let path = entry.unwrap().path();
+      let content = fs::read_to_string(&path).unwrap();
+      let instruction = content.parse::<OrdDeFiInstruction>().unwrap();
+      assert_eq!(
+        instruction.to_string(),
+        content.trim_end(),
+        "{}",
+        path.display()
+      );
+    }
+  }
+
+  #[test]
+  fn wrong_protocol_is_rejected() {
+    assert_eq!(
+      r#"{"p":"brc-20","op":"mint","tick":"odfi","amt":"1000"}"#
+        .parse::<OrdDeFiInstruction>()
+        .unwrap_err()
+        .to_string(),
+      "unknown protocol `brc-20`, expected `orddefi`",
+    );
+  }
+
+  #[test]
+  fn malformed_instructions_are_rejected() {
+    #[track_caller]
+    fn case(s: &str, error: &str) {
+      assert_eq!(
+        s.parse::<OrdDeFiInstruction>().unwrap_err().to_string(),
+        error
+      );
+    }
+
+    case("mint odfi 1000", "instruction is not JSON");
+    case(r#"["orddefi"]"#, "instruction is not a JSON object");
+    case(
+      r#"{"op":"mint","tick":"odfi","amt":"1000"}"#,
+      "instruction is missing protocol field `p`",
+    );
+    case(
+      r#"{"p":"orddefi","op":"deploy","tick":"odfi","amt":"1000"}"#,
+      "unknown op `deploy`, expected one of mint, transfer, addlp, rmlp, swap",
+    );
+    case(
+      r#"{"p":"orddefi","op":"swap","ltick":"odfi","rtick":"odgv","amt":"100"}"#,
+      "invalid `swap` instruction: missing field `spend`",
+    );
+    case(
+      r#"{"p":"orddefi","op":"mint","tick":"odfi","amt":1000}"#,
+      "invalid `mint` instruction: invalid type: integer `1000`, expected a string",
+    );
+    case(
+      r#"{"p":"orddefi","op":"mint","tick":"odfi","amt":"1000","lim":"1000"}"#,
+      "unknown field `lim` in `mint` instruction",
+    );
+  }
+
+  #[test]
+  fn validate() {
+    #[track_caller]
+    fn case(s: &str, error: Option<&str>) {
+      assert_eq!(
+        s.parse::<OrdDeFiInstruction>()
+          .unwrap()
+          .validate(Chain::Mainnet)
+          .err()
+          .map(|err| err.to_string())
+          .as_deref(),
+        error,
+      );
+    }
+
+    for entry in fs::read_dir("instruction_demo").unwrap() {
+      let content = fs::read_to_string(entry.unwrap().path()).unwrap();
+      if !content.contains("change_this_to_your_destination_address") {
+        case(&content, None);
+      }
+    }
+
+    case(
+      r#"{"p":"orddefi","op":"mint","tick":"odfi","amt":"0.00000001"}"#,
+      None,
+    );
+    case(
+      r#"{"p":"orddefi","op":"mint","tick":"odf","amt":"1000"}"#,
+      Some("`tick` `odf` must be 4 characters long, not 3"),
+    );
+    case(
+      r#"{"p":"orddefi","op":"mint","tick":"odfi","amt":"1,000"}"#,
+      Some("`amt` `1,000` is not a decimal number"),
+    );
+    case(
+      r#"{"p":"orddefi","op":"mint","tick":"odfi","amt":"-1"}"#,
+      Some("`amt` `-1` is not a decimal number"),
+    );
+    case(
+      r#"{"p":"orddefi","op":"mint","tick":"odfi","amt":"1."}"#,
+      Some("`amt` `1.` is not a decimal number"),
+    );
+    case(
+      r#"{"p":"orddefi","op":"mint","tick":"odfi","amt":".5"}"#,
+      Some("`amt` `.5` is not a decimal number"),
+    );
+    case(
+      r#"{"p":"orddefi","op":"mint","tick":"odfi","amt":"0.000000001"}"#,
+      Some("`amt` `0.000000001` has more than 8 decimal places"),
+    );
+    case(
+      r#"{"p":"orddefi","op":"mint","tick":"odfi","amt":"0.0"}"#,
+      Some("`amt` must be greater than zero"),
+    );
+    case(
+      r#"{"p":"orddefi","op":"addlp","ltick":"odfi","lamt":"200","rtick":"odfi","ramt":"1"}"#,
+      Some("`ltick` and `rtick` must differ, both are `odfi`"),
+    );
+    case(
+      r#"{"p":"orddefi","op":"swap","ltick":"odfi","rtick":"odgv","spend":"abcd","amt":"100"}"#,
+      Some("`spend` tick `abcd` is not part of pair `odfi-odgv`"),
+    );
+    case(
+      r#"{"p":"orddefi","op":"transfer","tick":"odfi","amt":"1","to":"change_this"}"#,
+      Some("invalid `to` address `change_this`"),
+    );
+    case(
+      r#"{"p":"orddefi","op":"transfer","tick":"odfi","amt":"1","to":"tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx"}"#,
+      Some("`to` address `tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx` is not valid on mainnet"),
+    );
+  }
+
+  #[test]
+  fn requires_authentication() {
+    for (path, requires_authentication) in [
+      ("addlp_odfi_odgv.txt", true),
+      ("direct_transfer_odfi.txt", true),
+      ("mint_odfi.txt", true),
+      ("rmlp_odfi_odgv.txt", true),
+      ("swap_odfi_odgv.txt", true),
+      ("transfer_odfi.txt", false),
+    ] {
+      assert_eq!(
+        fs::read_to_string(Path::new("instruction_demo").join(path))
+          .unwrap()
+          .parse::<OrdDeFiInstruction>()
+          .unwrap()
+          .requires_authentication(),
+        requires_authentication,
+        "{path}",
+      );
+    }
+  }
+
+  #[test]
+  fn from_inscription() {
+    let instruction = OrdDeFiInstruction::Mint {
+      tick: "odfi".into(),
+      amt: "1000".into(),
+    };
+
+    for compress in [false, true] {
+      assert_eq!(
+        OrdDeFiInstruction::from_inscription(
+          &instruction
+            .inscription(Chain::Mainnet, None, None, None, compress)
+            .unwrap()
+        ),
+        Some(instruction.clone()),
+      );
+    }
+
+    assert_eq!(
+      OrdDeFiInstruction::from_inscription(&inscription("text/plain", "hello")),
+      None,
+    );
+  }
+
+  #[test]
+  fn inscription_body_is_instruction_json() {
+    let instruction = OrdDeFiInstruction::Mint {
+      tick: "odfi".into(),
+      amt: "1000".into(),
+    };
+
+    let inscription = instruction
+      .inscription(Chain::Mainnet, None, None, None, false)
+      .unwrap();
+
+    assert_eq!(
+      inscription.body().unwrap(),
+      instruction.to_string().as_bytes()
+    );
+    assert_eq!(
+      inscription.content_type(),
+      Some(OrdDeFiInstruction::CONTENT_TYPE)
+    );
+  }
+}
@@ -0,0 +1,225 @@
+use super::*;
+
+/// A constant product liquidity pool. `left` is the reserve of the pair's
+/// lexicographically smaller tick, `right` that of the larger one, and
+/// `shares` the outstanding LP shares.
+#[derive(Debug, PartialEq, Copy, Clone, Default, Serialize, Deserialize)]
+pub struct Pool {
+  pub left: Quantity,
+  pub right: Quantity,
+  pub shares: Quantity,
+}
+
+impl Pool {
+  /// Swaps pay a fee of 3 per mille of the amount spent, which stays in the
+  /// pool.
+  pub const FEE_PER_MILLE: u128 = 3;
+
+  /// Deposit up to `left` and `right`, returning the amounts actually taken
+  /// and the LP shares minted. The first deposit sets the price and mints the
+  /// geometric mean of the two amounts. Later deposits are taken at the
+  /// current price and the excess side is left with the depositor.
+  pub fn add_liquidity(
+    &mut self,
+    left: Quantity,
+    right: Quantity,
+  ) -> Option<(Quantity, Quantity, Quantity)> {
+    let (left, right, shares) = if self.shares == Quantity::ZERO {
+      (left, right, isqrt(left.0.checked_mul(right.0)?))
+    } else {
+      let (left, right) = match mul_div(left.0, self.right.0, self.left.0)? {
+        optimal if optimal <= right.0 => (left, Quantity(optimal)),
+        _ => (
+          Quantity(mul_div(right.0, self.left.0, self.right.0)?),
+          right,
+        ),
+      };
+
+      let shares = mul_div(left.0, self.shares.0, self.left.0)?.min(mul_div(
+        right.0,
+        self.shares.0,
+        self.right.0,
+      )?);
+
+      (left, right, shares)
+    };
+
+    if shares == 0 {
+      return None;
+    }
+
+    let shares = Quantity(shares);
+
+    self.left = self.left.checked_add(left)?;
+    self.right = self.right.checked_add(right)?;
+    self.shares = self.shares.checked_add(shares)?;
+
+    Some((left, right, shares))
+  }
+
+  /// Burn `shares`, returning their proportional part of both reserves.
+  pub fn remove_liquidity(&mut self, shares: Quantity) -> Option<(Quantity, Quantity)> {
+    if shares == Quantity::ZERO || shares > self.shares {
+      return None;
+    }
+
+    let left = Quantity(mul_div(shares.0, self.left.0, self.shares.0)?);
+    let right = Quantity(mul_div(shares.0, self.right.0, self.shares.0)?);
+
+    self.left = self.left.checked_sub(left)?;
+    self.right = self.right.checked_sub(right)?;
+    self.shares = self.shares.checked_sub(shares)?;
+
+    Some((left, right))
+  }
+
+  /// The amount received for spending `amount` of the left tick if
+  /// `spend_left`, or of the right tick otherwise.
+  pub fn swap_output(&self, spend_left: bool, amount: Quantity) -> Option<Quantity> {
+    let (reserve_in, reserve_out) = if spend_left {
+      (self.left.0, self.right.0)
+    } else {
+      (self.right.0, self.left.0)
+    };
+
+    let amount = amount.0.checked_mul(1000 - Self::FEE_PER_MILLE)?;
+
+    let output = mul_div(
+      amount,
+      reserve_out,
+      reserve_in.checked_mul(1000)?.checked_add(amount)?,
+    )?;
+
+    (output > 0).then_some(Quantity(output))
+  }
+
+  pub fn swap(&mut self, spend_left: bool, amount: Quantity) -> Option<Quantity> {
+    let output = self.swap_output(spend_left, amount)?;
+
+    if spend_left {
+      self.left = self.left.checked_add(amount)?;
+      self.right = self.right.checked_sub(output)?;
+    } else {
+      self.right = self.right.checked_add(amount)?;
+      self.left = self.left.checked_sub(output)?;
+    }
+
+    Some(output)
+  }
+}
+
+fn mul_div(a: u128, b: u128, c: u128) -> Option<u128> {
+  a.checked_mul(b)?.checked_div(c)
+}
+
+fn isqrt(n: u128) -> u128 {
+  if n < 2 {
+    return n;
+  }
+
+  let mut x = 1 << ((128 - n.leading_zeros() + 1) / 2);
+
+  loop {
+    let y = (x + n / x) / 2;
+
+    if y >= x {
+      return x;
+    }
+
+    x = y;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn q(s: &str) -> Quantity {
+    s.parse().unwrap()
+  }
+
+  #[test]
+  fn isqrt_is_floor_of_square_root() {
+    for (n, root) in [(0, 0), (1, 1), (3, 1), (4, 2), (99, 9), (100, 10)] {
+      assert_eq!(isqrt(n), root);
+    }
+    assert_eq!(isqrt(u128::MAX), u128::from(u64::MAX));
+  }
+
+  #[test]
+  fn first_deposit_mints_geometric_mean() {
+    let mut pool = Pool::default();
+
+    assert_eq!(
+      pool.add_liquidity(q("100"), q("400")),
+      Some((q("100"), q("400"), q("200")))
+    );
+
+    assert_eq!(
+      pool,
+      Pool {
+        left: q("100"),
+        right: q("400"),
+        shares: q("200"),
+      }
+    );
+  }
+
+  #[test]
+  fn later_deposits_refund_excess() {
+    let mut pool = Pool::default();
+    pool.add_liquidity(q("100"), q("400")).unwrap();
+
+    assert_eq!(
+      pool.add_liquidity(q("10"), q("100")),
+      Some((q("10"), q("40"), q("20")))
+    );
+
+    assert_eq!(
+      pool.add_liquidity(q("100"), q("40")),
+      Some((q("10"), q("40"), q("20")))
+    );
+
+    assert_eq!(
+      pool,
+      Pool {
+        left: q("120"),
+        right: q("480"),
+        shares: q("240"),
+      }
+    );
+  }
+
+  #[test]
+  fn remove_liquidity_returns_proportional_reserves() {
+    let mut pool = Pool::default();
+    pool.add_liquidity(q("100"), q("400")).unwrap();
+
+    assert_eq!(pool.remove_liquidity(q("50")), Some((q("25"), q("100"))));
+    assert_eq!(pool.remove_liquidity(q("151")), None);
+    assert_eq!(pool.remove_liquidity(q("150")), Some((q("75"), q("300"))));
+    assert_eq!(pool, Pool::default());
+  }
+
+  #[test]
+  fn swap_keeps_fee_in_pool() {
+    let mut pool = Pool::default();
+    pool.add_liquidity(q("1000"), q("1000")).unwrap();
+
+    let output = pool.swap(true, q("100")).unwrap();
+
+    assert_eq!(output, Quantity(9_066_108_938));
+    assert_eq!(pool.left, q("1100"));
+    assert_eq!(pool.right, Quantity(q("1000").0 - output.0));
+    assert!(pool.left.0 * pool.right.0 > q("1000").0 * q("1000").0);
+
+    let output = pool.swap(false, output).unwrap();
+
+    assert!(output < q("100"));
+  }
+
+  #[test]
+  fn swap_against_empty_pool_fails() {
+    assert_eq!(Pool::default().swap_output(true, q("1")), None);
+  }
+}
@@ -0,0 +1,433 @@
+use super::*;
+
+/// What an `addlp`, `rmlp` or `swap` instruction is expected to yield. Amounts
+/// are given per tick in the order the instruction names them.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum Outcome {
+  Addlp {
+    ltick: String,
+    lamt: Quantity,
+    lrefund: Quantity,
+    rtick: String,
+    ramt: Quantity,
+    rrefund: Quantity,
+    shares: Quantity,
+  },
+  Rmlp {
+    ltick: String,
+    lamt: Quantity,
+    rtick: String,
+    ramt: Quantity,
+  },
+  Swap {
+    spend: String,
+    amt: Quantity,
+    receive: String,
+    output: Quantity,
+    price_impact: f64,
+  },
+}
+
+impl Outcome {
+  /// Execute `instruction` against `pool` the way the indexer would, leaving
+  /// `pool` updated. Balances are not checked.
+  fn execute(
+    instruction: &OrdDeFiInstruction,
+    pool: &mut Option<Pool>,
+  ) -> Result<Self, AbortReason> {
+    fn parse(amount: &str) -> Result<Quantity, AbortReason> {
+      amount.parse().map_err(|_| AbortReason::InvalidInstruction)
+    }
+
+    match instruction {
+      OrdDeFiInstruction::Addlp {
+        ltick,
+        lamt,
+        rtick,
+        ramt,
+      } => {
+        let (lamt, ramt) = (parse(lamt)?, parse(ramt)?);
+
+        let flip = ltick > rtick;
+
+        let mut updated = pool.unwrap_or_default();
+
+        let (left, right, shares) = if flip {
+          updated.add_liquidity(ramt, lamt)
+        } else {
+          updated.add_liquidity(lamt, ramt)
+        }
+        .ok_or(AbortReason::InsufficientLiquidity)?;
+
+        *pool = Some(updated);
+
+        let (lused, rused) = if flip { (right, left) } else { (left, right) };
+
+        Ok(Self::Addlp {
+          ltick: ltick.clone(),
+          lamt: lused,
+          lrefund: Quantity(lamt.0 - lused.0),
+          rtick: rtick.clone(),
+          ramt: rused,
+          rrefund: Quantity(ramt.0 - rused.0),
+          shares,
+        })
+      }
+      OrdDeFiInstruction::Rmlp { ltick, rtick, amt } => {
+        let shares = parse(amt)?;
+
+        let (left, right) = pool
+          .as_mut()
+          .ok_or(AbortReason::PoolNotFound)?
+          .remove_liquidity(shares)
+          .ok_or(AbortReason::InsufficientLiquidity)?;
+
+        let (lamt, ramt) = if ltick > rtick {
+          (right, left)
+        } else {
+          (left, right)
+        };
+
+        Ok(Self::Rmlp {
+          ltick: ltick.clone(),
+          lamt,
+          rtick: rtick.clone(),
+          ramt,
+        })
+      }
+      OrdDeFiInstruction::Swap {
+        ltick,
+        rtick,
+        spend,
+        amt,
+      } => {
+        let amount = parse(amt)?;
+
+        let pool = pool.as_mut().ok_or(AbortReason::PoolNotFound)?;
+
+        let receive = if spend == ltick { rtick } else { ltick };
+
+        let spend_left = spend < receive;
+
+        let (reserve_in, reserve_out) = if spend_left {
+          (pool.left, pool.right)
+        } else {
+          (pool.right, pool.left)
+        };
+
+        let output = pool
+          .swap(spend_left, amount)
+          .ok_or(AbortReason::InsufficientLiquidity)?;
+
+        Ok(Self::Swap {
+          spend: spend.clone(),
+          amt: amount,
+          receive: receive.clone(),
+          output,
+          price_impact: 1.0
+            - (output.0 as f64 * reserve_in.0 as f64) / (amount.0 as f64 * reserve_out.0 as f64),
+        })
+      }
+      OrdDeFiInstruction::Mint { .. } | OrdDeFiInstruction::Transfer { .. } => {
+        Err(AbortReason::InvalidInstruction)
+      }
+    }
+  }
+
+  /// The amounts the instruction's owner receives.
+  fn received(&self) -> Vec<Quantity> {
+    match self {
+      Self::Addlp { shares, .. } => vec![*shares],
+      Self::Rmlp { lamt, ramt, .. } => vec![*lamt, *ramt],
+      Self::Swap { output, .. } => vec![*output],
+    }
+  }
+}
+
+/// The expected outcome of an instruction against the indexed pool, and how
+/// much worse it gets if the pool instructions waiting in the mempool are
+/// executed first.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Preview {
+  #[serde(flatten)]
+  pub outcome: Outcome,
+  pub pending: usize,
+  pub slippage: f64,
+}
+
+impl Preview {
+  /// Preview `instruction` against `pool`, the indexed state of its pair.
+  /// Instructions in `pending` which act on the same pair are assumed to
+  /// execute before it. Returns `None` for instructions which don't act on a
+  /// pool.
+  pub fn new(
+    instruction: &OrdDeFiInstruction,
+    pool: Option<Pool>,
+    pending: &[OrdDeFiInstruction],
+  ) -> Result<Option<Self>> {
+    let Some(pair) = instruction.pair() else {
+      return Ok(None);
+    };
+
+    let outcome = Outcome::execute(instruction, &mut pool.clone()).map_err(|reason| {
+      anyhow!(
+        "`{}` instruction on pool `{pair}` would be aborted: {reason}",
+        instruction.op()
+      )
+    })?;
+
+    let mut adjusted = pool;
+    let mut applied = 0;
+
+    for pending in pending {
+      if pending.pair().as_ref() == Some(&pair) && Outcome::execute(pending, &mut adjusted).is_ok()
+      {
+        applied += 1;
+      }
+    }
+
+    let slippage = match Outcome::execute(instruction, &mut adjusted) {
+      Ok(adjusted) => outcome
+        .received()
+        .into_iter()
+        .zip(adjusted.received())
+        .filter(|(expected, _)| expected.0 > 0)
+        .map(|(expected, adjusted)| {
+          expected.0.saturating_sub(adjusted.0) as f64 / expected.0 as f64
+        })
+        .fold(0.0, f64::max),
+      Err(_) => 1.0,
+    };
+
+    Ok(Some(Self {
+      outcome,
+      pending: applied,
+      slippage,
+    }))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn q(s: &str) -> Quantity {
+    s.parse().unwrap()
+  }
+
+  fn instruction(s: &str) -> OrdDeFiInstruction {
+    s.parse().unwrap()
+  }
+
+  fn pool(left: &str, right: &str) -> Option<Pool> {
+    let mut pool = Pool::default();
+    pool.add_liquidity(q(left), q(right)).unwrap();
+    Some(pool)
+  }
+
+  #[test]
+  fn mint_and_transfer_have_no_preview() {
+    assert_eq!(
+      Preview::new(
+        &instruction(r#"{"p":"orddefi","op":"mint","tick":"odfi","amt":"1000"}"#),
+        None,
+        &[]
+      )
+      .unwrap(),
+      None
+    );
+  }
+
+  #[test]
+  fn swap() {
+    let preview = Preview::new(
+      &instruction(
+        r#"{"p":"orddefi","op":"swap","ltick":"odfi","rtick":"odgv","spend":"odfi","amt":"100"}"#,
+      ),
+      pool("1000", "1000"),
+      &[],
+    )
+    .unwrap()
+    .unwrap();
+
+    let Outcome::Swap {
+      spend,
+      amt,
+      receive,
+      output,
+      price_impact,
+    } = preview.outcome
+    else {
+      panic!("unexpected outcome {:?}", preview.outcome);
+    };
+
+    assert_eq!(spend, "odfi");
+    assert_eq!(amt, q("100"));
+    assert_eq!(receive, "odgv");
+    assert_eq!(output, Quantity(9_066_108_938));
+    assert!((price_impact - (1.0 - 90.66108938 / 100.0)).abs() < 1e-9);
+    assert_eq!(preview.pending, 0);
+    assert_eq!(preview.slippage, 0.0);
+  }
+
+  #[test]
+  fn swap_spending_right_tick_of_reversed_pair() {
+    let preview = Preview::new(
+      &instruction(
+        r#"{"p":"orddefi","op":"swap","ltick":"odgv","rtick":"odfi","spend":"odgv","amt":"100"}"#,
+      ),
+      pool("1000", "1000"),
+      &[],
+    )
+    .unwrap()
+    .unwrap();
+
+    assert!(matches!(
+      preview.outcome,
+      Outcome::Swap { ref receive, output, .. } if receive == "odfi" && output == Quantity(9_066_108_938)
+    ));
+  }
+
+  #[test]
+  fn addlp_reports_refund_in_instruction_order() {
+    let preview = Preview::new(
+      &instruction(
+        r#"{"p":"orddefi","op":"addlp","ltick":"odgv","lamt":"100","rtick":"odfi","ramt":"100"}"#,
+      ),
+      pool("100", "400"),
+      &[],
+    )
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(
+      preview.outcome,
+      Outcome::Addlp {
+        ltick: "odgv".into(),
+        lamt: q("100"),
+        lrefund: q("0"),
+        rtick: "odfi".into(),
+        ramt: q("25"),
+        rrefund: q("75"),
+        shares: q("50"),
+      }
+    );
+  }
+
+  #[test]
+  fn first_addlp_creates_pool() {
+    let preview = Preview::new(
+      &instruction(
+        r#"{"p":"orddefi","op":"addlp","ltick":"odfi","lamt":"100","rtick":"odgv","ramt":"400"}"#,
+      ),
+      None,
+      &[],
+    )
+    .unwrap()
+    .unwrap();
+
+    assert!(matches!(preview.outcome, Outcome::Addlp { shares, .. } if shares == q("200")));
+  }
+
+  #[test]
+  fn rmlp() {
+    let preview = Preview::new(
+      &instruction(r#"{"p":"orddefi","op":"rmlp","ltick":"odgv","rtick":"odfi","amt":"50"}"#),
+      pool("100", "400"),
+      &[],
+    )
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(
+      preview.outcome,
+      Outcome::Rmlp {
+        ltick: "odgv".into(),
+        lamt: q("100"),
+        rtick: "odfi".into(),
+        ramt: q("25"),
+      }
+    );
+  }
+
+  #[test]
+  fn aborted_instructions_are_errors() {
+    assert_eq!(
+      Preview::new(
+        &instruction(
+          r#"{"p":"orddefi","op":"swap","ltick":"odfi","rtick":"odgv","spend":"odfi","amt":"100"}"#,
+        ),
+        None,
+        &[],
+      )
+      .unwrap_err()
+      .to_string(),
+      "`swap` instruction on pool `odfi-odgv` would be aborted: pool not found",
+    );
+  }
+
+  #[test]
+  fn pending_instructions_cause_slippage() {
+    let swap = instruction(
+      r#"{"p":"orddefi","op":"swap","ltick":"odfi","rtick":"odgv","spend":"odfi","amt":"100"}"#,
+    );
+
+    let other_pool = instruction(
+      r#"{"p":"orddefi","op":"swap","ltick":"odfi","rtick":"abcd","spend":"odfi","amt":"100"}"#,
+    );
+
+    let preview = Preview::new(&swap, pool("1000", "1000"), &[swap.clone(), other_pool])
+      .unwrap()
+      .unwrap();
+
+    let mut adjusted = pool("1000", "1000").unwrap();
+    adjusted.swap(true, q("100")).unwrap();
+    let output = adjusted.swap(true, q("100")).unwrap();
+
+    assert_eq!(preview.pending, 1);
+    assert!(
+      (preview.slippage - (1.0 - output.0 as f64 / 9_066_108_938.0)).abs() < 1e-9,
+      "{}",
+      preview.slippage
+    );
+    assert!(preview.slippage > 0.15);
+  }
+
+  #[test]
+  fn pending_rmlp_of_all_shares_is_full_slippage() {
+    let preview = Preview::new(
+      &instruction(
+        r#"{"p":"orddefi","op":"swap","ltick":"odfi","rtick":"odgv","spend":"odfi","amt":"100"}"#,
+      ),
+      pool("100", "400"),
+      &[instruction(
+        r#"{"p":"orddefi","op":"rmlp","ltick":"odfi","rtick":"odgv","amt":"200"}"#,
+      )],
+    )
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(preview.pending, 1);
+    assert_eq!(preview.slippage, 1.0);
+  }
+
+  #[test]
+  fn serde() {
+    let preview = Preview {
+      outcome: Outcome::Rmlp {
+        ltick: "odfi".into(),
+        lamt: q("1"),
+        rtick: "odgv".into(),
+        ramt: q("2.5"),
+      },
+      pending: 0,
+      slippage: 0.0,
+    };
+
+    let json = r#"{"op":"rmlp","ltick":"odfi","lamt":"1","rtick":"odgv","ramt":"2.5","pending":0,"slippage":0.0}"#;
+
+    assert_eq!(serde_json::to_string(&preview).unwrap(), json);
+    assert_eq!(serde_json::from_str::<Preview>(json).unwrap(), preview);
+  }
+}
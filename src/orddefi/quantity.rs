@@ -0,0 +1,152 @@
+use super::*;
+
+/// An amount of an OrdDeFi tick, stored as an integer number of the smallest
+/// unit, 10^-8 of a coin, and written as a decimal string.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Default)]
+pub struct Quantity(pub u128);
+
+impl Quantity {
+  pub const ONE: u128 = 100_000_000;
+  pub const ZERO: Self = Self(0);
+
+  pub const fn from_integer(n: u128) -> Self {
+    Self(n * Self::ONE)
+  }
+
+  pub fn checked_add(self, other: Self) -> Option<Self> {
+    self.0.checked_add(other.0).map(Self)
+  }
+
+  pub fn checked_sub(self, other: Self) -> Option<Self> {
+    self.0.checked_sub(other.0).map(Self)
+  }
+}
+
+impl FromStr for Quantity {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let (integer, fraction) = s.split_once('.').unwrap_or((s, ""));
+
+    ensure!(
+      !integer.is_empty()
+        && integer.chars().all(|c| c.is_ascii_digit())
+        && fraction.chars().all(|c| c.is_ascii_digit())
+        && !s.ends_with('.'),
+      "`{s}` is not a decimal number",
+    );
+
+    ensure!(
+      fraction.len() <= OrdDeFiInstruction::MAX_DECIMALS,
+      "`{s}` has more than {} decimal places",
+      OrdDeFiInstruction::MAX_DECIMALS,
+    );
+
+    let fraction = format!(
+      "{fraction:0<width$}",
+      width = OrdDeFiInstruction::MAX_DECIMALS
+    );
+
+    integer
+      .parse::<u128>()
+      .ok()
+      .and_then(|integer| integer.checked_mul(Self::ONE))
+      .and_then(|integer| integer.checked_add(fraction.parse::<u128>().unwrap()))
+      .map(Self)
+      .ok_or_else(|| anyhow!("`{s}` is out of range"))
+  }
+}
+
+impl Display for Quantity {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", self.0 / Self::ONE)?;
+
+    let fraction = self.0 % Self::ONE;
+
+    if fraction > 0 {
+      let fraction = format!(
+        "{fraction:0>width$}",
+        width = OrdDeFiInstruction::MAX_DECIMALS
+      );
+      write!(f, ".{}", fraction.trim_end_matches('0'))?;
+    }
+
+    Ok(())
+  }
+}
+
+impl Serialize for Quantity {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    serializer.collect_str(self)
+  }
+}
+
+impl<'de> Deserialize<'de> for Quantity {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    Ok(DeserializeFromStr::deserialize(deserializer)?.0)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn from_str() {
+    #[track_caller]
+    fn case(s: &str, n: u128) {
+      assert_eq!(s.parse::<Quantity>().unwrap(), Quantity(n));
+    }
+
+    case("0", 0);
+    case("1", 100_000_000);
+    case("1000", 100_000_000_000);
+    case("345.6", 34_560_000_000);
+    case("0.00000001", 1);
+    case("1.10000000", 110_000_000);
+  }
+
+  #[test]
+  fn from_str_errors() {
+    #[track_caller]
+    fn case(s: &str, error: &str) {
+      assert_eq!(s.parse::<Quantity>().unwrap_err().to_string(), error);
+    }
+
+    case("", "`` is not a decimal number");
+    case(".5", "`.5` is not a decimal number");
+    case("1.", "`1.` is not a decimal number");
+    case("-1", "`-1` is not a decimal number");
+    case("1e3", "`1e3` is not a decimal number");
+    case(
+      "0.000000001",
+      "`0.000000001` has more than 8 decimal places",
+    );
+    case(
+      "340282366920938463463374607431768211455",
+      "`340282366920938463463374607431768211455` is out of range",
+    );
+  }
+
+  #[test]
+  fn display() {
+    assert_eq!(Quantity(0).to_string(), "0");
+    assert_eq!(Quantity(1).to_string(), "0.00000001");
+    assert_eq!(Quantity(34_560_000_000).to_string(), "345.6");
+    assert_eq!(Quantity::from_integer(21_000_000).to_string(), "21000000");
+  }
+
+  #[test]
+  fn serde() {
+    let quantity = Quantity(34_560_000_000);
+    let json = "\"345.6\"";
+    assert_eq!(serde_json::to_string(&quantity).unwrap(), json);
+    assert_eq!(serde_json::from_str::<Quantity>(json).unwrap(), quantity);
+  }
+}
@@ -0,0 +1,11 @@
+use super::*;
+
+/// An indexed instruction: the script it was inscribed to, the height it was
+/// executed at, and its outcome.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Receipt {
+  pub destination: ScriptBuf,
+  pub height: u32,
+  pub instruction: OrdDeFiInstruction,
+  pub status: Status,
+}
@@ -0,0 +1,121 @@
+use super::*;
+
+/// Why an indexed instruction had no effect.
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AbortReason {
+  BadAuth,
+  InsufficientBalance,
+  InsufficientLiquidity,
+  InvalidAddress,
+  InvalidInstruction,
+  MintLimit,
+  PoolNotFound,
+  SupplyExhausted,
+  UnknownTick,
+}
+
+impl AbortReason {
+  const ALL: [Self; 9] = [
+    Self::BadAuth,
+    Self::InsufficientBalance,
+    Self::InsufficientLiquidity,
+    Self::InvalidAddress,
+    Self::InvalidInstruction,
+    Self::MintLimit,
+    Self::PoolNotFound,
+    Self::SupplyExhausted,
+    Self::UnknownTick,
+  ];
+}
+
+impl Display for AbortReason {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        Self::BadAuth => "bad auth",
+        Self::InsufficientBalance => "insufficient balance",
+        Self::InsufficientLiquidity => "insufficient liquidity",
+        Self::InvalidAddress => "invalid address",
+        Self::InvalidInstruction => "invalid instruction",
+        Self::MintLimit => "mint limit exceeded",
+        Self::PoolNotFound => "pool not found",
+        Self::SupplyExhausted => "supply exhausted",
+        Self::UnknownTick => "unknown tick",
+      }
+    )
+  }
+}
+
+/// The outcome of an indexed instruction.
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "status", content = "reason")]
+pub enum Status {
+  Executed,
+  Aborted(AbortReason),
+}
+
+impl Status {
+  pub(crate) fn code(self) -> u8 {
+    match self {
+      Self::Executed => 0,
+      Self::Aborted(reason) => {
+        u8::try_from(AbortReason::ALL.iter().position(|r| *r == reason).unwrap()).unwrap() + 1
+      }
+    }
+  }
+
+  pub(crate) fn from_code(code: u8) -> Option<Self> {
+    match code.checked_sub(1) {
+      None => Some(Self::Executed),
+      Some(i) => AbortReason::ALL
+        .get(usize::from(i))
+        .copied()
+        .map(Self::Aborted),
+    }
+  }
+}
+
+impl Display for Status {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::Executed => write!(f, "executed"),
+      Self::Aborted(reason) => write!(f, "aborted: {reason}"),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn code_round_trips() {
+    assert_eq!(Status::Executed.code(), 0);
+    assert_eq!(Status::from_code(0), Some(Status::Executed));
+
+    for reason in AbortReason::ALL {
+      let status = Status::Aborted(reason);
+      assert_eq!(Status::from_code(status.code()), Some(status));
+    }
+
+    assert_eq!(
+      Status::from_code(u8::try_from(AbortReason::ALL.len()).unwrap() + 1),
+      None
+    );
+  }
+
+  #[test]
+  fn serde() {
+    assert_eq!(
+      serde_json::to_string(&Status::Executed).unwrap(),
+      r#"{"status":"executed"}"#
+    );
+    assert_eq!(
+      serde_json::to_string(&Status::Aborted(AbortReason::BadAuth)).unwrap(),
+      r#"{"status":"aborted","reason":"bad_auth"}"#
+    );
+  }
+}
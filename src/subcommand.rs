@@ -1,4 +1,4 @@
-use super::*;
+use {super::*, clap::ValueEnum};
 
 pub mod balances;
 pub mod decode;
@@ -6,6 +6,7 @@ pub mod epochs;
 pub mod find;
 pub mod index;
 pub mod list;
+pub mod orddefi;
 pub mod parse;
 mod preview;
 pub mod runes;
@@ -30,6 +31,8 @@ pub(crate) enum Subcommand {
   Index(index::IndexSubcommand),
   #[command(about = "List the satoshis in an output")]
   List(list::List),
+  #[command(name = "orddefi", subcommand, about = "OrdDeFi commands")]
+  OrdDeFi(orddefi::OrdDeFiSubcommand),
   #[command(about = "Parse a satoshi from ordinal notation")]
   Parse(parse::Parse),
   #[command(about = "Run an explorer server populated with inscriptions")]
@@ -59,6 +62,7 @@ impl Subcommand {
       Self::Find(find) => find.run(options),
       Self::Index(index) => index.run(options),
       Self::List(list) => list.run(options),
+      Self::OrdDeFi(orddefi) => orddefi.run(options),
       Self::Parse(parse) => parse.run(),
       Self::Preview(preview) => preview.run(),
       Self::Runes => runes::run(options),
@@ -80,17 +84,32 @@ impl Subcommand {
 #[derive(Serialize, Deserialize)]
 pub struct Empty {}
 
+/// How subcommand output is written to stdout. Diagnostics go to stderr.
+#[derive(Default, ValueEnum, Copy, Clone, Debug, PartialEq)]
+pub(crate) enum OutputFormat {
+  #[default]
+  Json,
+  Yaml,
+}
+
 pub(crate) trait Output: Send {
-  fn print_json(&self);
+  fn print(&self, format: OutputFormat);
 }
 
 impl<T> Output for T
 where
   T: Serialize + Send,
 {
-  fn print_json(&self) {
-    serde_json::to_writer_pretty(io::stdout(), self).ok();
-    println!();
+  fn print(&self, format: OutputFormat) {
+    match format {
+      OutputFormat::Json => {
+        serde_json::to_writer_pretty(io::stdout(), self).ok();
+        println!();
+      }
+      OutputFormat::Yaml => {
+        serde_yaml::to_writer(io::stdout(), self).ok();
+      }
+    }
   }
 }
 
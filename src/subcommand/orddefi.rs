@@ -0,0 +1,41 @@
+use {
+  super::*,
+  crate::orddefi::{Quantity, Status},
+};
+
+pub mod balance;
+pub mod history;
+pub mod pools;
+
+#[derive(Debug, Parser)]
+pub(crate) enum OrdDeFiSubcommand {
+  #[command(about = "List OrdDeFi balances of an address")]
+  Balance(balance::Balance),
+  #[command(about = "List OrdDeFi instructions inscribed to an address")]
+  History(history::History),
+  #[command(about = "List OrdDeFi liquidity pools")]
+  Pools,
+}
+
+impl OrdDeFiSubcommand {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    match self {
+      Self::Balance(balance) => balance.run(options),
+      Self::History(history) => history.run(options),
+      Self::Pools => pools::run(options),
+    }
+  }
+}
+
+fn open_index(options: &Options, command: &str) -> Result<Index> {
+  let index = Index::open(options)?;
+
+  ensure!(
+    index.has_orddefi_index(),
+    "`ord orddefi {command}` requires index created with `--index-orddefi` flag",
+  );
+
+  index.update()?;
+
+  Ok(index)
+}
@@ -0,0 +1,29 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Balance {
+  #[arg(help = "List balances of <ADDRESS>.")]
+  address: Address<NetworkUnchecked>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub address: Address<NetworkUnchecked>,
+  pub balances: BTreeMap<String, crate::orddefi::Balance>,
+}
+
+impl Balance {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let address = self
+      .address
+      .clone()
+      .require_network(options.chain().network())?;
+
+    let index = open_index(&options, "balance")?;
+
+    Ok(Box::new(Output {
+      balances: index.get_orddefi_balances(&address.script_pubkey())?,
+      address: self.address,
+    }))
+  }
+}
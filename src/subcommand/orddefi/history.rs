@@ -0,0 +1,49 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct History {
+  #[arg(help = "List instructions inscribed to <ADDRESS>.")]
+  address: Address<NetworkUnchecked>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub address: Address<NetworkUnchecked>,
+  pub instructions: Vec<InstructionInfo>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct InstructionInfo {
+  pub height: u32,
+  pub inscription: InscriptionId,
+  pub instruction: OrdDeFiInstruction,
+  pub reveal: Txid,
+  #[serde(flatten)]
+  pub status: Status,
+}
+
+impl History {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let address = self
+      .address
+      .clone()
+      .require_network(options.chain().network())?;
+
+    let index = open_index(&options, "history")?;
+
+    Ok(Box::new(Output {
+      instructions: index
+        .get_orddefi_history(&address.script_pubkey())?
+        .into_iter()
+        .map(|(inscription, receipt)| InstructionInfo {
+          height: receipt.height,
+          inscription,
+          instruction: receipt.instruction,
+          reveal: inscription.txid,
+          status: receipt.status,
+        })
+        .collect(),
+      address: self.address,
+    }))
+  }
+}
@@ -0,0 +1,38 @@
+use super::*;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub pools: BTreeMap<String, PoolInfo>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct PoolInfo {
+  pub ltick: String,
+  pub lreserve: Quantity,
+  pub rtick: String,
+  pub rreserve: Quantity,
+  pub shares: Quantity,
+}
+
+pub(crate) fn run(options: Options) -> SubcommandResult {
+  let index = open_index(&options, "pools")?;
+
+  let mut pools = BTreeMap::new();
+
+  for (pair, pool) in index.get_orddefi_pools()? {
+    let (ltick, rtick) = crate::orddefi::split_pair(&pair)
+      .ok_or_else(|| anyhow!("invalid OrdDeFi pair `{pair}` in index"))?;
+
+    let info = PoolInfo {
+      ltick: ltick.into(),
+      lreserve: pool.left,
+      rtick: rtick.into(),
+      rreserve: pool.right,
+      shares: pool.shares,
+    };
+
+    pools.insert(pair, info);
+  }
+
+  Ok(Box::new(Output { pools }))
+}
@@ -103,8 +103,11 @@ impl Preview {
         Arguments {
           options: options.clone(),
           subcommand: Subcommand::Wallet(super::wallet::Wallet {
-            name: "ord".into(),
+            name: Some("ord".into()),
+            profile: None,
             subcommand: super::wallet::Subcommand::Inscribe(super::wallet::inscribe::Inscribe {
+              auth_payload: AuthOutput::DEFAULT_PAYLOAD.into(),
+              auth_position: AuthOutputPosition::Last,
               batch: None,
               cbor_metadata: None,
               commit_fee_rate: None,
@@ -113,17 +116,23 @@ impl Preview {
               destination: None,
               change: None,
               dry_run: false,
-              fee_rate: FeeRate::try_from(1.0).unwrap(),
+              fee_rate: Some(FeeRate::try_from(1.0).unwrap().into()),
               file: Some(file),
+              instruction: None,
               json_metadata: None,
+              max_slippage: None,
               metaprotocol: None,
+              no_auth_check: true,
               no_backup: true,
               no_limit: false,
               parent: None,
               postage: Some(TARGET_POSTAGE),
+              psbt_out: None,
               reinscribe: false,
               satpoint: None,
               sat: None,
+              skip_validation: true,
+              subcommand: None,
             }),
           }),
         }
@@ -138,8 +147,11 @@ impl Preview {
         Arguments {
           options: options.clone(),
           subcommand: Subcommand::Wallet(super::wallet::Wallet {
-            name: "ord".into(),
+            name: Some("ord".into()),
+            profile: None,
             subcommand: super::wallet::Subcommand::Inscribe(super::wallet::inscribe::Inscribe {
+              auth_payload: AuthOutput::DEFAULT_PAYLOAD.into(),
+              auth_position: AuthOutputPosition::Last,
               batch: Some(batch),
               cbor_metadata: None,
               commit_fee_rate: None,
@@ -148,17 +160,23 @@ impl Preview {
               destination: None,
               change: None,
               dry_run: false,
-              fee_rate: FeeRate::try_from(1.0).unwrap(),
+              fee_rate: Some(FeeRate::try_from(1.0).unwrap().into()),
               file: None,
+              instruction: None,
               json_metadata: None,
+              max_slippage: None,
               metaprotocol: None,
+              no_auth_check: true,
               no_backup: true,
               no_limit: false,
               parent: None,
               postage: Some(TARGET_POSTAGE),
+              psbt_out: None,
               reinscribe: false,
               satpoint: None,
               sat: None,
+              skip_validation: true,
+              subcommand: None,
             }),
           }),
         }
@@ -9,13 +9,15 @@ use {
   crate::{
     server_config::ServerConfig,
     templates::{
-      BlockHtml, BlockJson, BlocksHtml, ChildrenHtml, ChildrenJson, ClockSvg, CollectionsHtml,
-      HomeHtml, InputHtml, InscriptionHtml, InscriptionJson, InscriptionsBlockHtml,
-      InscriptionsHtml, InscriptionsJson, OutputHtml, OutputJson, PageContent, PageHtml,
-      PreviewAudioHtml, PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml, PreviewMarkdownHtml,
-      PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml, PreviewVideoHtml,
-      RangeHtml, RareTxt, RuneHtml, RunesHtml, SatHtml, SatInscriptionJson, SatInscriptionsJson,
-      SatJson, StatusHtml, TransactionHtml,
+      AddressHtml, AddressJson, BlockHtml, BlockJson, BlocksHtml, ChildrenHtml, ChildrenJson,
+      ClockSvg, CollectionsHtml, HomeHtml, InputHtml, InscriptionHtml, InscriptionJson,
+      InscriptionsBlockHtml, InscriptionsHtml, InscriptionsJson, OrdDeFiBalanceHtml,
+      OrdDeFiBalanceJson, OrdDeFiInstructionHtml, OrdDeFiInstructionJson, OrdDeFiPoolHtml,
+      OrdDeFiPoolJson, OrdDeFiTicksHtml, OrdDeFiTicksJson, OutputHtml, OutputJson, PageContent,
+      PageHtml, PreviewAudioHtml, PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml,
+      PreviewMarkdownHtml, PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml,
+      PreviewVideoHtml, RangeHtml, RareTxt, RuneHtml, RunesHtml, SatHtml, SatInscriptionJson,
+      SatInscriptionsJson, SatJson, StatusHtml, TransactionHtml,
     },
   },
   axum::{
@@ -202,6 +204,7 @@ impl Server {
 
       let router = Router::new()
         .route("/", get(Self::home))
+        .route("/address/:address", get(Self::address))
         .route("/block/:query", get(Self::block))
         .route("/blockcount", get(Self::block_count))
         .route("/blockhash", get(Self::block_hash))
@@ -235,6 +238,14 @@ impl Server {
           get(Self::inscriptions_in_block_paginated),
         )
         .route("/install.sh", get(Self::install_script))
+        .route("/orddefi/balance/:address", get(Self::orddefi_balance))
+        .route(
+          "/orddefi/instruction/:inscription_id",
+          get(Self::orddefi_instruction),
+        )
+        .route("/orddefi/pool/:ltick/:rtick", get(Self::orddefi_pool))
+        .route("/orddefi/ticks", get(Self::orddefi_ticks))
+        .route("/orddefi/ticks/:page", get(Self::orddefi_ticks_paginated))
         .route("/ordinal/:sat", get(Self::ordinal))
         .route("/output/:output", get(Self::output))
         .route("/preview/:inscription_id", get(Self::preview))
@@ -527,6 +538,192 @@ impl Server {
     })
   }
 
+  fn require_orddefi_index(index: &Index) -> ServerResult<()> {
+    if !index.has_orddefi_index() {
+      return Err(ServerError::NotFound(
+        "this server has no OrdDeFi index".to_string(),
+      ));
+    }
+
+    Ok(())
+  }
+
+  async fn orddefi_balance(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(address)): Path<DeserializeFromStr<Address<NetworkUnchecked>>>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult<Response> {
+    Self::require_orddefi_index(&index)?;
+
+    let checked = address
+      .clone()
+      .require_network(server_config.chain.network())
+      .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+    let balances = index.get_orddefi_balances(&checked.script_pubkey())?;
+
+    Ok(if accept_json {
+      Json(OrdDeFiBalanceJson { address, balances }).into_response()
+    } else {
+      OrdDeFiBalanceHtml {
+        address: checked,
+        balances,
+      }
+      .page(server_config)
+      .into_response()
+    })
+  }
+
+  async fn orddefi_instruction(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(inscription_id): Path<InscriptionId>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult<Response> {
+    Self::require_orddefi_index(&index)?;
+
+    let receipt = index
+      .get_orddefi_receipt(inscription_id)?
+      .ok_or_not_found(|| format!("OrdDeFi instruction {inscription_id}"))?;
+
+    let html = OrdDeFiInstructionHtml {
+      chain: server_config.chain,
+      inscription_id,
+      receipt,
+    };
+
+    Ok(if accept_json {
+      Json(OrdDeFiInstructionJson::from(html)).into_response()
+    } else {
+      html.page(server_config).into_response()
+    })
+  }
+
+  async fn orddefi_pool(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path((ltick, rtick)): Path<(String, String)>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult<Response> {
+    Self::require_orddefi_index(&index)?;
+
+    let pool = index
+      .get_orddefi_pool(&ltick, &rtick)?
+      .ok_or_not_found(|| format!("OrdDeFi pool {}", crate::orddefi::pair(&ltick, &rtick)))?;
+
+    let html = OrdDeFiPoolHtml::new(&ltick, &rtick, pool);
+
+    Ok(if accept_json {
+      Json(OrdDeFiPoolJson::from(html)).into_response()
+    } else {
+      html.page(server_config).into_response()
+    })
+  }
+
+  async fn orddefi_ticks(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    accept_json: AcceptJson,
+  ) -> ServerResult<Response> {
+    Self::orddefi_ticks_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path(0),
+      accept_json,
+    )
+    .await
+  }
+
+  async fn orddefi_ticks_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(page_index): Path<usize>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult<Response> {
+    Self::require_orddefi_index(&index)?;
+
+    let (ticks, more) = index.get_orddefi_ticks_paginated(100, page_index)?;
+
+    let prev = page_index.checked_sub(1);
+
+    let next = more.then_some(page_index + 1);
+
+    Ok(if accept_json {
+      Json(OrdDeFiTicksJson {
+        ticks: ticks.into_iter().collect(),
+        more,
+        page_index,
+      })
+      .into_response()
+    } else {
+      OrdDeFiTicksHtml { ticks, prev, next }
+        .page(server_config)
+        .into_response()
+    })
+  }
+
+  async fn address(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(address)): Path<DeserializeFromStr<Address<NetworkUnchecked>>>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult<Response> {
+    if !index.has_address_index() {
+      return Err(ServerError::NotFound(
+        "this server has no address index".to_string(),
+      ));
+    }
+
+    let checked = address
+      .clone()
+      .require_network(server_config.chain.network())
+      .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+    let outputs = index.get_address_outputs(&checked.script_pubkey())?;
+
+    let inscriptions = index.get_inscriptions(&outputs)?.into_values().collect();
+
+    let mut runes = BTreeMap::<SpacedRune, Pile>::new();
+
+    for outpoint in outputs.keys() {
+      for (spaced_rune, pile) in index.get_rune_balances_for_outpoint(*outpoint)? {
+        runes
+          .entry(spaced_rune)
+          .and_modify(|total| total.amount += pile.amount)
+          .or_insert(pile);
+      }
+    }
+
+    let html = AddressHtml {
+      address: checked,
+      inscriptions,
+      outputs,
+      runes,
+    };
+
+    Ok(if accept_json {
+      Json(AddressJson {
+        sat_balance: html.sat_balance(),
+        address,
+        inscriptions: html.inscriptions,
+        outputs: html
+          .outputs
+          .into_iter()
+          .map(|(outpoint, value)| (outpoint, value.to_sat()))
+          .collect(),
+        runes: html
+          .runes
+          .into_iter()
+          .map(|(spaced_rune, pile)| (spaced_rune.rune, pile.amount))
+          .collect(),
+      })
+      .into_response()
+    } else {
+      html.page(server_config).into_response()
+    })
+  }
+
   async fn ordinal(Path(sat): Path<String>) -> Redirect {
     Redirect::to(&format!("/sat/{sat}"))
   }
@@ -1591,6 +1788,28 @@ mod tests {
       )
     }
 
+    fn new_with_regtest_with_index_addresses() -> Self {
+      Self::new_server(
+        test_bitcoincore_rpc::builder()
+          .network(bitcoin::Network::Regtest)
+          .build(),
+        None,
+        &["--chain", "regtest", "--index-addresses"],
+        &["--enable-json-api"],
+      )
+    }
+
+    fn new_with_regtest_with_index_orddefi() -> Self {
+      Self::new_server(
+        test_bitcoincore_rpc::builder()
+          .network(bitcoin::Network::Regtest)
+          .build(),
+        None,
+        &["--chain", "regtest", "--index-orddefi"],
+        &["--enable-json-api"],
+      )
+    }
+
     fn new_with_bitcoin_rpc_server_and_config(
       bitcoin_rpc_server: test_bitcoincore_rpc::Handle,
       config: String,
@@ -1763,6 +1982,39 @@ mod tests {
       blocks
     }
 
+    fn inscribe_orddefi(&self, instruction: &str) -> InscriptionId {
+      let height = usize::try_from(self.index.block_count().unwrap()).unwrap();
+
+      self.mine_blocks(1);
+
+      self.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(height, 0, 0, Witness::new())],
+        op_return: Some(
+          script::Builder::new()
+            .push_opcode(opcodes::all::OP_RETURN)
+            .push_slice(b"orddefi:auth")
+            .into_script(),
+        ),
+        ..Default::default()
+      });
+
+      self.mine_blocks(1);
+
+      let txid = self.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          height + 1,
+          1,
+          0,
+          inscription(OrdDeFiInstruction::CONTENT_TYPE, instruction).to_witness(),
+        )],
+        ..Default::default()
+      });
+
+      self.mine_blocks(1);
+
+      InscriptionId { txid, index: 0 }
+    }
+
     fn mine_blocks_with_subsidy(&self, n: u64, subsidy: u64) -> Vec<Block> {
       let blocks = self.bitcoin_rpc_server.mine_blocks_with_subsidy(n, subsidy);
       self.index.update().unwrap();
@@ -5007,4 +5259,210 @@ next
 
     server.assert_response(format!("/preview/{id}"), StatusCode::OK, "foo");
   }
+
+  #[test]
+  fn orddefi_endpoints_require_orddefi_index() {
+    let server = TestServer::new_with_regtest();
+
+    server.assert_response(
+      "/orddefi/ticks",
+      StatusCode::NOT_FOUND,
+      "this server has no OrdDeFi index",
+    );
+  }
+
+  #[test]
+  fn orddefi_state_is_served() {
+    let server = TestServer::new_with_regtest_with_index_orddefi();
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      "/orddefi/ticks",
+      StatusCode::OK,
+      ".*<title>OrdDeFi Ticks</title>.*<th>supply</th>\n  </tr>\n</table>.*",
+    );
+
+    for tick in ["odfi", "odgv"] {
+      server.inscribe_orddefi(&format!(
+        r#"{{"p":"orddefi","op":"mint","tick":"{tick}","amt":"1000"}}"#
+      ));
+    }
+
+    server.inscribe_orddefi(
+      r#"{"p":"orddefi","op":"addlp","ltick":"odfi","lamt":"100","rtick":"odgv","ramt":"400"}"#,
+    );
+
+    let address = "bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw";
+
+    let transfer = server.inscribe_orddefi(&format!(
+      r#"{{"p":"orddefi","op":"transfer","tick":"odfi","amt":"10.5","to":"{address}"}}"#
+    ));
+
+    pretty_assert_eq!(
+      server.get_json::<OrdDeFiTicksJson>("/orddefi/ticks"),
+      OrdDeFiTicksJson {
+        ticks: [
+          ("odfi".into(), "1000".parse().unwrap()),
+          ("odgv".into(), "1000".parse().unwrap()),
+        ]
+        .into_iter()
+        .collect(),
+        more: false,
+        page_index: 0,
+      }
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<OrdDeFiTicksJson>("/orddefi/ticks/1"),
+      OrdDeFiTicksJson {
+        ticks: BTreeMap::new(),
+        more: false,
+        page_index: 1,
+      }
+    );
+
+    server.assert_response_regex(
+      "/orddefi/ticks",
+      StatusCode::OK,
+      ".*<td>odfi</td>\n    <td>1000</td>.*<td>odgv</td>\n    <td>1000</td>.*",
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<OrdDeFiPoolJson>("/orddefi/pool/odgv/odfi"),
+      OrdDeFiPoolJson {
+        ltick: "odfi".into(),
+        lreserve: "100".parse().unwrap(),
+        rtick: "odgv".into(),
+        rreserve: "400".parse().unwrap(),
+        shares: "200".parse().unwrap(),
+      }
+    );
+
+    server.assert_response_regex(
+      "/orddefi/pool/odfi/odgv",
+      StatusCode::OK,
+      ".*<h1>OrdDeFi Pool odfi-odgv</h1>.*<dt>odgv reserve</dt>\n  <dd>400</dd>.*",
+    );
+
+    server.assert_response(
+      "/orddefi/pool/odfi/abcd",
+      StatusCode::NOT_FOUND,
+      "OrdDeFi pool abcd-odfi not found",
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<OrdDeFiBalanceJson>(format!("/orddefi/balance/{address}")),
+      OrdDeFiBalanceJson {
+        address: address.parse().unwrap(),
+        balances: [(
+          "odfi".into(),
+          crate::orddefi::Balance {
+            available: "10.5".parse().unwrap(),
+            transferable: crate::orddefi::Quantity::ZERO,
+          }
+        )]
+        .into_iter()
+        .collect(),
+      }
+    );
+
+    server.assert_response_regex(
+      format!("/orddefi/balance/{address}"),
+      StatusCode::OK,
+      ".*<td>odfi</td>\n    <td>10.5</td>\n    <td>0</td>.*",
+    );
+
+    server.assert_response_regex(
+      "/orddefi/balance/bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+      StatusCode::BAD_REQUEST,
+      ".*network.*",
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<OrdDeFiInstructionJson>(format!("/orddefi/instruction/{transfer}")),
+      OrdDeFiInstructionJson {
+        destination: None,
+        height: 13,
+        inscription_id: transfer,
+        instruction: format!(
+          r#"{{"p":"orddefi","op":"transfer","tick":"odfi","amt":"10.5","to":"{address}"}}"#
+        )
+        .parse()
+        .unwrap(),
+        status: crate::orddefi::Status::Executed,
+      }
+    );
+
+    server.assert_response_regex(
+      format!("/orddefi/instruction/{transfer}"),
+      StatusCode::OK,
+      ".*<dt>status</dt>\n  <dd>executed</dd>.*",
+    );
+
+    server.assert_response(
+      format!("/orddefi/instruction/{}", inscription_id(1)),
+      StatusCode::NOT_FOUND,
+      &format!("OrdDeFi instruction {} not found", inscription_id(1)),
+    );
+  }
+
+  #[test]
+  fn address_requires_address_index() {
+    let server = TestServer::new_with_regtest();
+
+    server.assert_response(
+      "/address/bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw",
+      StatusCode::NOT_FOUND,
+      "this server has no address index",
+    );
+  }
+
+  #[test]
+  fn address_lists_unspent_outputs() {
+    let server = TestServer::new_with_regtest_with_index_addresses();
+
+    server.mine_blocks(1);
+
+    let address = "bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw";
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      op_return: Some(
+        address
+          .parse::<Address<NetworkUnchecked>>()
+          .unwrap()
+          .assume_checked()
+          .script_pubkey(),
+      ),
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let outpoint = OutPoint { txid, vout: 1 };
+
+    pretty_assert_eq!(
+      server.get_json::<AddressJson>(format!("/address/{address}")),
+      AddressJson {
+        address: address.parse().unwrap(),
+        inscriptions: Vec::new(),
+        outputs: [(outpoint, 0)].into_iter().collect(),
+        runes: BTreeMap::new(),
+        sat_balance: 0,
+      }
+    );
+
+    server.assert_response_regex(
+      format!("/address/{address}"),
+      StatusCode::OK,
+      format!(".*<title>Address {address}</title>.*<h2>1 Output</h2>.*<a href=/output/{outpoint}>{outpoint}</a> 0.*"),
+    );
+
+    server.assert_response_regex(
+      "/address/bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+      StatusCode::BAD_REQUEST,
+      ".*network.*",
+    );
+  }
 }
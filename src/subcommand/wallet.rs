@@ -9,7 +9,7 @@ use {
     Network,
   },
   bitcoincore_rpc::bitcoincore_rpc_json::{ImportDescriptors, Timestamp},
-  fee_rate::FeeRate,
+  fee_rate::{FeeRate, FeeRateArgument},
   miniscript::descriptor::{Descriptor, DescriptorSecretKey, DescriptorXKey, Wildcard},
   transaction_builder::TransactionBuilder,
 };
@@ -17,6 +17,7 @@ use crate::subcommand::wallet::commit_gen_addr::CommitGenAddr;
 use crate::subcommand::wallet::commit_gen_prv::CommitGenPrv;
 
 pub mod balance;
+pub mod bump;
 pub mod commit_gen_addr;
 pub mod commit_gen_prv;
 pub mod cardinals;
@@ -24,18 +25,29 @@ pub mod create;
 pub mod etch;
 pub mod inscribe;
 pub mod inscriptions;
+pub mod instruction;
+pub mod instructions;
 pub mod outputs;
 pub mod receive;
+pub mod recover_commits;
 mod restore;
+pub mod reveal_from_commit;
 pub mod sats;
 pub mod send;
 pub mod transaction_builder;
 pub mod transactions;
+pub mod transfer;
+pub mod watch;
 
 #[derive(Debug, Parser)]
 pub(crate) struct Wallet {
-  #[arg(long, default_value = "ord", help = "Use wallet named <WALLET>.")]
-  pub(crate) name: String,
+  #[arg(long, help = "Use wallet named <WALLET>. Defaults to `ord`.")]
+  pub(crate) name: Option<String>,
+  #[arg(
+    long,
+    help = "Use wallet and inscribe defaults from <PROFILE> in the config file. Explicit flags override them."
+  )]
+  pub(crate) profile: Option<String>,
   #[command(subcommand)]
   pub(crate) subcommand: Subcommand,
 }
@@ -44,6 +56,8 @@ pub(crate) struct Wallet {
 pub(crate) enum Subcommand {
   #[command(about = "Get wallet balance")]
   Balance,
+  #[command(about = "Bump fee of a commit or reveal transaction")]
+  Bump(bump::Bump),
   #[command(about = "Commit generate address")]
   CommitGenAddr(commit_gen_addr::CommitGenAddr),
   #[command(about = "Commit generate xprv")]
@@ -56,16 +70,28 @@ pub(crate) enum Subcommand {
   Inscribe(inscribe::Inscribe),
   #[command(about = "List wallet inscriptions")]
   Inscriptions,
+  #[command(about = "Inscribe an OrdDeFi instruction")]
+  Instruction(instruction::Instruction),
+  #[command(about = "List OrdDeFi instructions inscribed by this wallet and their status")]
+  Instructions,
   #[command(about = "Generate receive address")]
   Receive,
+  #[command(about = "Sweep unspent commit outputs using their recovery keys")]
+  RecoverCommits(recover_commits::RecoverCommits),
   #[command(about = "Restore wallet")]
   Restore(restore::Restore),
+  #[command(about = "Reveal inscription from an externally funded commit address")]
+  RevealFromCommit(reveal_from_commit::RevealFromCommit),
   #[command(about = "List wallet satoshis")]
   Sats(sats::Sats),
   #[command(about = "Send sat or inscription")]
   Send(send::Send),
   #[command(about = "See wallet transactions")]
   Transactions(transactions::Transactions),
+  #[command(about = "Inscribe an OrdDeFi transfer and send it to its recipient")]
+  Transfer(transfer::Transfer),
+  #[command(subcommand, about = "Manage watch-only origin addresses")]
+  Watch(watch::Watch),
   #[command(about = "List all unspent outputs in wallet")]
   Outputs,
   #[command(about = "List unspent cardinal outputs in wallet")]
@@ -74,34 +100,57 @@ pub(crate) enum Subcommand {
 
 impl Wallet {
   pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let profile = match &self.profile {
+      Some(profile) => options.load_config()?.profile(profile, options.chain())?,
+      None => Profile::default(),
+    };
+
+    let name = self.wallet_name(&profile);
+
     match self.subcommand {
-      Subcommand::Balance => balance::run(self.name, options),
+      Subcommand::Balance => balance::run(name, options),
+      Subcommand::Bump(bump) => bump.run(name, options),
       Subcommand::CommitGenAddr(commitGenAddr) => commitGenAddr.run(options),
       Subcommand::CommitGenPrv(..) => CommitGenPrv::run(),
-      Subcommand::Create(create) => create.run(self.name, options),
-      Subcommand::Etch(etch) => etch.run(self.name, options),
-      Subcommand::Inscribe(inscribe) => inscribe.run(self.name, options),
-      Subcommand::Inscriptions => inscriptions::run(self.name, options),
-      Subcommand::Receive => receive::run(self.name, options),
-      Subcommand::Restore(restore) => restore.run(self.name, options),
-      Subcommand::Sats(sats) => sats.run(self.name, options),
-      Subcommand::Send(send) => send.run(self.name, options),
-      Subcommand::Transactions(transactions) => transactions.run(self.name, options),
-      Subcommand::Outputs => outputs::run(self.name, options),
-      Subcommand::Cardinals => cardinals::run(self.name, options),
+      Subcommand::Create(create) => create.run(name, options),
+      Subcommand::Etch(etch) => etch.run(name, options),
+      Subcommand::Inscribe(inscribe) => inscribe.with_profile(&profile).run(name, options),
+      Subcommand::Inscriptions => inscriptions::run(name, options),
+      Subcommand::Instruction(instruction) => instruction.with_profile(&profile).run(name, options),
+      Subcommand::Instructions => instructions::run(name, options),
+      Subcommand::Receive => receive::run(name, options),
+      Subcommand::RecoverCommits(recover_commits) => recover_commits.run(name, options),
+      Subcommand::Restore(restore) => restore.run(name, options),
+      Subcommand::RevealFromCommit(reveal_from_commit) => reveal_from_commit.run(options),
+      Subcommand::Sats(sats) => sats.run(name, options),
+      Subcommand::Send(send) => send.run(name, options),
+      Subcommand::Transactions(transactions) => transactions.run(name, options),
+      Subcommand::Transfer(transfer) => transfer.with_profile(&profile).run(name, options),
+      Subcommand::Watch(watch) => watch.run(name, options),
+      Subcommand::Outputs => outputs::run(name, options),
+      Subcommand::Cardinals => cardinals::run(name, options),
     }
   }
+
+  pub(crate) fn wallet_name(&self, profile: &Profile) -> String {
+    self
+      .name
+      .clone()
+      .or_else(|| profile.wallet.clone())
+      .unwrap_or_else(|| "ord".into())
+  }
 }
 
 pub(crate) fn get_unspent_outputs(
   client: &Client,
   index: &Index,
 ) -> Result<BTreeMap<OutPoint, Amount>> {
-  return get_unspent_outputs_with_address(client, index, &None, 0)
+  return get_unspent_outputs_with_address(client, None, index, &None, 0);
 }
 
 pub(crate) fn get_unspent_outputs_with_address(
   client: &Client,
+  watch_client: Option<&Client>,
   index: &Index,
   origin_address: &Option<Address>,
   min_value: u64
@@ -116,32 +165,71 @@ pub(crate) fn get_unspent_outputs_with_address(
   }
 
   let mut utxos = BTreeMap::new();
-  utxos.extend(
-    client
-      .list_unspent(None, None, addr_slice_opt, None, None)?
-      .into_iter()
-      .filter(|utxo| utxo.amount.to_sat() > min_value)
-      .map(|utxo| {
-        let outpoint = OutPoint::new(utxo.txid, utxo.vout);
-        let amount = utxo.amount;
+  for client in [Some(client), watch_client].into_iter().flatten() {
+    utxos.extend(
+      client
+        .list_unspent(None, None, addr_slice_opt, None, None)?
+        .into_iter()
+        .filter(|utxo| utxo.amount.to_sat() > min_value)
+        .map(|utxo| {
+          let outpoint = OutPoint::new(utxo.txid, utxo.vout);
+          let amount = utxo.amount;
+
+          (outpoint, amount)
+        }),
+    );
+  }
 
-        (outpoint, amount)
-      }),
-  );
+  // watch-only origins may not be in the Bitcoin Core wallet at all, so
+  // their outputs are taken from the address index if there is one, skipping
+  // outputs already spent by mempool transactions
+  if let (Some(address), true) = (origin_address, index.has_address_index()) {
+    for (outpoint, amount) in index.get_address_outputs(&address.script_pubkey())? {
+      if amount.to_sat() <= min_value || utxos.contains_key(&outpoint) {
+        continue;
+      }
+
+      if client
+        .get_tx_out(&outpoint.txid, outpoint.vout, Some(true))?
+        .is_some()
+      {
+        utxos.insert(outpoint, amount);
+      }
+    }
+  }
 
   let locked_utxos: BTreeSet<OutPoint> = get_locked_outputs(client)?;
 
   for outpoint in locked_utxos {
-    utxos.insert(
-      outpoint,
-      Amount::from_sat(
-        client.get_raw_transaction(&outpoint.txid, None)?.output
-          [TryInto::<usize>::try_into(outpoint.vout).unwrap()]
-        .value,
-      ),
-    );
+    let tx_out = client.get_raw_transaction(&outpoint.txid, None)?.output
+      [TryInto::<usize>::try_into(outpoint.vout).unwrap()]
+    .clone();
+
+    // locked outputs owned by other addresses must not fund origin transactions
+    if let Some(address) = origin_address {
+      if tx_out.script_pubkey != address.script_pubkey() {
+        continue;
+      }
+    }
+
+    utxos.insert(outpoint, Amount::from_sat(tx_out.value));
   }
 
+  Ok(utxos)
+}
+
+/// Like `get_unspent_outputs_with_address`, but fails if any of the outputs
+/// is not in the index yet.
+pub(crate) fn get_indexed_unspent_outputs_with_address(
+  client: &Client,
+  watch_client: Option<&Client>,
+  index: &Index,
+  origin_address: &Option<Address>,
+  min_value: u64,
+) -> Result<BTreeMap<OutPoint, Amount>> {
+  let utxos =
+    get_unspent_outputs_with_address(client, watch_client, index, origin_address, min_value)?;
+
   index.check_sync(&utxos)?;
 
   Ok(utxos)
@@ -186,6 +274,30 @@ pub(crate) fn get_change_address(client: &Client, chain: Chain) -> Result<Addres
   )
 }
 
+/// Dry runs must not advance the wallet's keypool, so they use an unspendable
+/// P2TR address instead, whose output key is the BIP 341 NUMS point. Its
+/// outputs are as large as those of wallet change addresses, so sizes and
+/// fees are estimated correctly.
+pub(crate) fn get_change_address_or_placeholder(
+  client: &Client,
+  chain: Chain,
+  dry_run: bool,
+) -> Result<Address> {
+  if !dry_run {
+    return get_change_address(client, chain);
+  }
+
+  let nums = bitcoin::secp256k1::XOnlyPublicKey::from_slice(&[
+    0x50, 0x92, 0x9b, 0x74, 0xc1, 0xa0, 0x49, 0x54, 0xb7, 0x8b, 0x4b, 0x60, 0x35, 0xe9, 0x7a, 0x5e,
+    0x07, 0x8a, 0x5a, 0x0f, 0x28, 0xec, 0x96, 0xd5, 0x47, 0xbf, 0xee, 0x9a, 0xce, 0x80, 0x3a, 0xc0,
+  ])?;
+
+  Ok(Address::p2tr_tweaked(
+    bitcoin::key::TweakedPublicKey::dangerous_assume_tweaked(nums),
+    chain.network(),
+  ))
+}
+
 pub(crate) fn initialize(wallet: String, options: &Options, seed: [u8; 64]) -> Result {
   check_version(options.bitcoin_rpc_client(None)?)?.create_wallet(
     &wallet,
@@ -0,0 +1,1205 @@
+use {
+  super::*,
+  bitcoin::{
+    key::{KeyPair, PrivateKey, TapTweak, TweakedPublicKey},
+    secp256k1::{Message, Scalar, SecretKey},
+    sighash::{Prevouts, SighashCache, TapSighashType},
+    taproot::{ControlBlock, LeafVersion, Signature, TapLeafHash, TapTweakHash, TaprootBuilder},
+  },
+  bitcoincore_rpc::bitcoincore_rpc_json::SignRawTransactionInput,
+};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Bump {
+  #[arg(help = "Bump commit or reveal transaction <TXID>.")]
+  txid: Txid,
+  #[arg(
+    long,
+    help = "Bump reveal transaction with a child transaction spending its first output instead of replacing it with one paying less postage. The output must be owned by the wallet and is sent back to the same address."
+  )]
+  cpfp: bool,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
+  fee_rate: FeeRate,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Output {
+  pub commit: Option<Txid>,
+  pub cpfp: Option<Txid>,
+  pub reveal: Option<Txid>,
+}
+
+impl Bump {
+  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+    let client = bitcoin_rpc_client_for_wallet_command(wallet, &options)?;
+
+    let recovery_keys = Self::recovery_keys(&client)?;
+
+    let transaction = client.get_raw_transaction(&self.txid, None)?;
+
+    if let Some(commit_input) = Self::commit_input(&client, &transaction, &recovery_keys)? {
+      let prevouts = transaction
+        .input
+        .iter()
+        .map(|txin| Self::get_prevout(&client, txin.previous_output))
+        .collect::<Result<Vec<TxOut>>>()?;
+
+      if !self.cpfp {
+        let reveal_tx = Self::bump_reveal_transaction(
+          &transaction,
+          commit_input,
+          &prevouts,
+          client.get_mempool_entry(&self.txid)?.fees.descendant,
+          client.get_network_info()?.incremental_fee,
+          self.fee_rate,
+        )?;
+
+        let commit_outpoint = transaction.input[commit_input].previous_output;
+
+        let reveal_tx = Self::sign_reveal_transaction(
+          &reveal_tx,
+          commit_input,
+          commit_outpoint,
+          &prevouts,
+          &recovery_keys[&prevouts[commit_input].script_pubkey],
+        )?;
+
+        // the parent input's signature commits to the reduced postage as well,
+        // so the wallet signs it again
+        let reveal_tx = if commit_input > 0 {
+          client
+            .sign_raw_transaction_with_wallet(
+              &reveal_tx,
+              Some(&[SignRawTransactionInput {
+                txid: commit_outpoint.txid,
+                vout: commit_outpoint.vout,
+                script_pub_key: prevouts[commit_input].script_pubkey.clone(),
+                redeem_script: None,
+                amount: Some(Amount::from_sat(prevouts[commit_input].value)),
+              }]),
+              None,
+            )?
+            .hex
+        } else {
+          consensus::encode::serialize(&reveal_tx)
+        };
+
+        return Ok(Box::new(Output {
+          commit: None,
+          cpfp: None,
+          reveal: Some(client.send_raw_transaction(&reveal_tx)?),
+        }));
+      }
+
+      let input_value = prevouts.iter().map(|prevout| prevout.value).sum();
+
+      let child = Self::build_cpfp_transaction(&transaction, input_value, self.fee_rate)?;
+
+      let signed_child = client.sign_raw_transaction_with_wallet(
+        &child,
+        Some(&[SignRawTransactionInput {
+          txid: transaction.txid(),
+          vout: 0,
+          script_pub_key: transaction.output[0].script_pubkey.clone(),
+          redeem_script: None,
+          amount: Some(Amount::from_sat(transaction.output[0].value)),
+        }]),
+        None,
+      )?;
+
+      ensure!(
+        signed_child.complete,
+        "failed to sign child transaction, first output of reveal transaction {} must be owned by the wallet",
+        self.txid
+      );
+
+      return Ok(Box::new(Output {
+        commit: None,
+        cpfp: Some(client.send_raw_transaction(&signed_child.hex)?),
+        reveal: None,
+      }));
+    }
+
+    ensure!(
+      !self.cpfp,
+      "--cpfp can only be used to bump reveal transactions"
+    );
+
+    let commit_vout = transaction
+      .output
+      .iter()
+      .position(|output| recovery_keys.contains_key(&output.script_pubkey))
+      .ok_or_else(|| {
+        anyhow!(
+          "transaction {} is neither a commit nor a reveal transaction with a recovery key in the wallet",
+          self.txid
+        )
+      })?;
+
+    let commit_output = transaction.output[commit_vout].clone();
+
+    let commit_outpoint = OutPoint {
+      txid: self.txid,
+      vout: commit_vout.try_into().unwrap(),
+    };
+
+    let reveal_tx = client.get_raw_transaction(
+      &Self::get_spending_transaction(&client, commit_outpoint)?,
+      None,
+    )?;
+
+    let commit_input = reveal_tx
+      .input
+      .iter()
+      .position(|txin| txin.previous_output == commit_outpoint)
+      .ok_or_else(|| {
+        anyhow!("reveal transaction does not spend commit output {commit_outpoint}")
+      })?;
+
+    let mut input_value = 0;
+    let mut input_scripts = BTreeMap::new();
+    for txin in &transaction.input {
+      let prevout = Self::get_prevout(&client, txin.previous_output)?;
+      input_value += prevout.value;
+      input_scripts.insert(txin.previous_output, prevout.script_pubkey);
+    }
+
+    // replacing the commit evicts the reveal and any other descendants too
+    let replaced_fee = client.get_mempool_entry(&self.txid)?.fees.descendant;
+
+    let commit_tx = Self::bump_commit_transaction(
+      &transaction,
+      commit_vout,
+      input_value,
+      &input_scripts,
+      replaced_fee,
+      client.get_network_info()?.incremental_fee,
+      self.fee_rate,
+    )?;
+
+    let signed_commit_tx = client.sign_raw_transaction_with_wallet(&commit_tx, None, None)?;
+
+    ensure!(
+      signed_commit_tx.complete,
+      "failed to sign commit transaction: {:?}",
+      signed_commit_tx.errors
+    );
+
+    let reveal_prevouts = reveal_tx
+      .input
+      .iter()
+      .enumerate()
+      .map(|(input, txin)| {
+        if input == commit_input {
+          Ok(commit_output.clone())
+        } else {
+          Self::get_prevout(&client, txin.previous_output)
+        }
+      })
+      .collect::<Result<Vec<TxOut>>>()?;
+
+    // script sigs of P2SH-P2WPKH and P2PKH inputs change the commit's txid
+    let new_commit_outpoint = OutPoint {
+      txid: consensus::encode::deserialize::<Transaction>(&signed_commit_tx.hex)?.txid(),
+      vout: commit_outpoint.vout,
+    };
+
+    let reveal_tx = Self::sign_reveal_transaction(
+      &reveal_tx,
+      commit_input,
+      new_commit_outpoint,
+      &reveal_prevouts,
+      &recovery_keys[&commit_output.script_pubkey],
+    )?;
+
+    // the parent input's signature commits to the commit outpoint as well, so
+    // the wallet signs it again
+    let reveal_tx = if reveal_tx.input.len() > 1 {
+      client
+        .sign_raw_transaction_with_wallet(
+          &reveal_tx,
+          Some(&[SignRawTransactionInput {
+            txid: new_commit_outpoint.txid,
+            vout: new_commit_outpoint.vout,
+            script_pub_key: commit_output.script_pubkey.clone(),
+            redeem_script: None,
+            amount: Some(Amount::from_sat(commit_output.value)),
+          }]),
+          None,
+        )?
+        .hex
+    } else {
+      consensus::encode::serialize(&reveal_tx)
+    };
+
+    let commit = client.send_raw_transaction(&signed_commit_tx.hex)?;
+
+    let reveal = match client.send_raw_transaction(&reveal_tx) {
+      Ok(txid) => txid,
+      Err(err) => {
+        return Err(anyhow!(
+          "Failed to send reveal transaction: {err}\nCommit tx {commit} can be recovered with `wallet recover-commits` once mined"
+        ))
+      }
+    };
+
+    Ok(Box::new(Output {
+      commit: Some(commit),
+      cpfp: None,
+      reveal: Some(reveal),
+    }))
+  }
+
+  /// Recovery keys imported by `backup_recovery_key`, by the script pubkey of
+  /// the commit output they can spend.
+  fn recovery_keys(client: &Client) -> Result<BTreeMap<ScriptBuf, KeyPair>> {
+    let secp256k1 = Secp256k1::new();
+
+    let mut recovery_keys = BTreeMap::new();
+
+    for descriptor in client.list_descriptors(Some(true))?.descriptors {
+      let Some(wif) = descriptor
+        .desc
+        .strip_prefix("rawtr(")
+        .and_then(|desc| desc.split(')').next())
+      else {
+        continue;
+      };
+
+      let key_pair = KeyPair::from_secret_key(&secp256k1, &PrivateKey::from_wif(wif)?.inner);
+
+      recovery_keys.insert(
+        ScriptBuf::new_v1_p2tr_tweaked(TweakedPublicKey::dangerous_assume_tweaked(
+          key_pair.x_only_public_key().0,
+        )),
+        key_pair,
+      );
+    }
+
+    Ok(recovery_keys)
+  }
+
+  fn get_spending_transaction(client: &Client, outpoint: OutPoint) -> Result<Txid> {
+    #[derive(Deserialize)]
+    struct SpendingPrevout {
+      spendingtxid: Option<Txid>,
+    }
+
+    client
+      .call::<Vec<SpendingPrevout>>(
+        "gettxspendingprevout",
+        &[serde_json::json!([{"txid": outpoint.txid, "vout": outpoint.vout}])],
+      )?
+      .into_iter()
+      .next()
+      .and_then(|prevout| prevout.spendingtxid)
+      .ok_or_else(|| anyhow!("no reveal transaction spending commit output {outpoint} in mempool"))
+  }
+
+  /// Index of the input of `transaction` that spends a commit output with a
+  /// recovery key in the wallet through its reveal script. That is the first
+  /// input of a reveal transaction, or the second if the first spends the
+  /// parent inscription.
+  fn commit_input(
+    client: &Client,
+    transaction: &Transaction,
+    recovery_keys: &BTreeMap<ScriptBuf, KeyPair>,
+  ) -> Result<Option<usize>> {
+    for (input, txin) in transaction.input.iter().enumerate().take(2) {
+      if txin.witness.tapscript().is_some()
+        && recovery_keys
+          .contains_key(&Self::get_prevout(client, txin.previous_output)?.script_pubkey)
+      {
+        return Ok(Some(input));
+      }
+    }
+
+    Ok(None)
+  }
+
+  fn get_prevout(client: &Client, outpoint: OutPoint) -> Result<TxOut> {
+    client
+      .get_raw_transaction(&outpoint.txid, None)?
+      .output
+      .into_iter()
+      .nth(outpoint.vout.try_into().unwrap())
+      .ok_or_else(|| anyhow!("output {outpoint} not found"))
+  }
+
+  /// Replace `commit_tx` with a transaction paying `fee_rate`, taking the
+  /// additional fee from its change output. The commit output is unchanged,
+  /// so the commit script and address stay the same. By BIP125 rules 3 and 4,
+  /// the replacement must pay for the transactions it evicts, `replaced_fee`,
+  /// which includes the reveal, and for its own size at the incremental relay
+  /// fee rate.
+  fn bump_commit_transaction(
+    commit_tx: &Transaction,
+    commit_vout: usize,
+    input_value: u64,
+    input_scripts: &BTreeMap<OutPoint, ScriptBuf>,
+    replaced_fee: Amount,
+    incremental_relay_fee: Amount,
+    fee_rate: FeeRate,
+  ) -> Result<Transaction> {
+    let mut commit_tx = commit_tx.clone();
+
+    for txin in &mut commit_tx.input {
+      txin.script_sig = ScriptBuf::new();
+      txin.witness = Witness::new();
+    }
+
+    let fee = input_value
+      .checked_sub(commit_tx.output.iter().map(|output| output.value).sum())
+      .ok_or_else(|| anyhow!("commit transaction outputs exceed inputs"))?;
+
+    let vsize = TransactionBuilder::estimate_vsize(&commit_tx, input_scripts);
+
+    let bumped_fee = fee_rate.fee(vsize).to_sat();
+
+    ensure!(
+      bumped_fee > fee,
+      "commit transaction {} already pays {fee} sat, which is not less than {bumped_fee} sat at the new fee rate",
+      commit_tx.txid(),
+    );
+
+    // the incremental relay fee rate is given in sats per 1000 vB
+    let min_fee = replaced_fee.to_sat()
+      + (incremental_relay_fee.to_sat() * u64::try_from(vsize).unwrap() + 999) / 1000;
+
+    ensure!(
+      bumped_fee >= min_fee,
+      "commit transaction {} and its descendants pay {} sat, so its replacement must pay at least {min_fee} sat, but only pays {bumped_fee} sat at the new fee rate",
+      commit_tx.txid(),
+      replaced_fee.to_sat(),
+    );
+
+    let change = commit_tx
+      .output
+      .iter_mut()
+      .enumerate()
+      .find(|(vout, output)| *vout != commit_vout && !output.script_pubkey.is_op_return())
+      .map(|(_vout, output)| output)
+      .ok_or_else(|| anyhow!("commit transaction has no change output to pay for fee bump"))?;
+
+    change.value = change
+      .value
+      .checked_sub(bumped_fee - fee)
+      .filter(|value| *value >= change.script_pubkey.dust_value().to_sat())
+      .ok_or_else(|| {
+        anyhow!(
+          "commit transaction change output of {} sat is too small to pay for fee bump",
+          change.value
+        )
+      })?;
+
+    Ok(commit_tx)
+  }
+
+  /// Replace `reveal_tx` with a transaction paying `fee_rate`, taking the
+  /// additional fee from the postage of its last output. The postage never
+  /// goes below the output's dust limit or below the offset of any
+  /// inscription it holds, and the parent output of a reveal with a parent is
+  /// left alone. `prevouts` are the outputs spent by `reveal_tx`. As for
+  /// commit transactions, the replacement must pay `replaced_fee` and for its
+  /// own size at the incremental relay fee rate.
+  fn bump_reveal_transaction(
+    reveal_tx: &Transaction,
+    commit_input: usize,
+    prevouts: &[TxOut],
+    replaced_fee: Amount,
+    incremental_relay_fee: Amount,
+    fee_rate: FeeRate,
+  ) -> Result<Transaction> {
+    ensure!(
+      reveal_tx.output.len() > commit_input,
+      "reveal transaction {} has no postage output to pay for fee bump",
+      reveal_tx.txid(),
+    );
+
+    let output_value = reveal_tx
+      .output
+      .iter()
+      .map(|output| output.value)
+      .sum::<u64>();
+
+    let fee = prevouts
+      .iter()
+      .map(|prevout| prevout.value)
+      .sum::<u64>()
+      .checked_sub(output_value)
+      .ok_or_else(|| anyhow!("reveal transaction outputs exceed inputs"))?;
+
+    // the new signature has the same size as the old one
+    let vsize = reveal_tx.vsize();
+
+    let bumped_fee = fee_rate.fee(vsize).to_sat();
+
+    ensure!(
+      bumped_fee > fee,
+      "reveal transaction {} already pays {fee} sat, which is not less than {bumped_fee} sat at the new fee rate",
+      reveal_tx.txid(),
+    );
+
+    let min_fee = replaced_fee.to_sat()
+      + (incremental_relay_fee.to_sat() * u64::try_from(vsize).unwrap() + 999) / 1000;
+
+    ensure!(
+      bumped_fee >= min_fee,
+      "reveal transaction {} and its descendants pay {} sat, so its replacement must pay at least {min_fee} sat, but only pays {bumped_fee} sat at the new fee rate, use `--cpfp` to bump it with a child transaction instead",
+      reveal_tx.txid(),
+      replaced_fee.to_sat(),
+    );
+
+    let input_offsets = prevouts
+      .iter()
+      .scan(0, |offset, prevout| {
+        let start = *offset;
+        *offset += prevout.value;
+        Some(start)
+      })
+      .collect::<Vec<u64>>();
+
+    let postage = reveal_tx.output.last().unwrap();
+
+    let postage_offset = output_value - postage.value;
+
+    // inscriptions are indexed the same way, at their pointer if it is within
+    // the outputs, or else at the first sat of their input
+    let min_postage = ParsedEnvelope::from_transaction(reveal_tx)
+      .into_iter()
+      .map(|envelope| {
+        envelope
+          .payload
+          .pointer()
+          .filter(|&pointer| pointer < output_value)
+          .unwrap_or(input_offsets[usize::try_from(envelope.input).unwrap()])
+      })
+      .filter(|offset| (postage_offset..output_value).contains(offset))
+      .map(|offset| offset - postage_offset + 1)
+      .chain([postage.script_pubkey.dust_value().to_sat()])
+      .max()
+      .unwrap();
+
+    let mut reveal_tx = reveal_tx.clone();
+
+    let postage = reveal_tx.output.last_mut().unwrap();
+
+    postage.value = postage
+      .value
+      .checked_sub(bumped_fee - fee)
+      .filter(|value| *value >= min_postage)
+      .ok_or_else(|| {
+        anyhow!(
+          "reveal transaction output of {} sat is too small to pay for fee bump without going below {min_postage} sat, use `--cpfp` to bump it with a child transaction instead",
+          postage.value
+        )
+      })?;
+
+    Ok(reveal_tx)
+  }
+
+  /// Spend the first output of `reveal_tx` back to the same address, paying
+  /// enough fee for both transactions to reach `fee_rate`. `input_value` is
+  /// the value of all outputs spent by `reveal_tx`.
+  fn build_cpfp_transaction(
+    reveal_tx: &Transaction,
+    input_value: u64,
+    fee_rate: FeeRate,
+  ) -> Result<Transaction> {
+    let postage = reveal_tx
+      .output
+      .first()
+      .ok_or_else(|| anyhow!("reveal transaction has no outputs"))?;
+
+    let reveal_fee = input_value
+      .checked_sub(reveal_tx.output.iter().map(|output| output.value).sum())
+      .ok_or_else(|| anyhow!("reveal transaction outputs exceed inputs"))?;
+
+    let postage_outpoint = OutPoint {
+      txid: reveal_tx.txid(),
+      vout: 0,
+    };
+
+    let mut child = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: postage_outpoint,
+        script_sig: ScriptBuf::new(),
+        witness: Witness::new(),
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+      }],
+      output: vec![TxOut {
+        script_pubkey: postage.script_pubkey.clone(),
+        value: 0,
+      }],
+    };
+
+    let child_vsize = TransactionBuilder::estimate_vsize(
+      &child,
+      &[(postage_outpoint, postage.script_pubkey.clone())]
+        .into_iter()
+        .collect(),
+    );
+
+    let package_fee = fee_rate.fee(reveal_tx.vsize() + child_vsize).to_sat();
+
+    ensure!(
+      package_fee > reveal_fee,
+      "reveal transaction {} already pays {reveal_fee} sat, which is not less than {package_fee} sat at the new fee rate",
+      reveal_tx.txid(),
+    );
+
+    child.output[0].value = postage
+      .value
+      .checked_sub(package_fee - reveal_fee)
+      .filter(|value| *value >= postage.script_pubkey.dust_value().to_sat())
+      .ok_or_else(|| {
+        anyhow!(
+          "reveal transaction output of {} sat is too small to pay for child transaction",
+          postage.value
+        )
+      })?;
+
+    Ok(child)
+  }
+
+  /// Sign input `commit_input` of `reveal_tx` spending `commit_outpoint`
+  /// through its reveal script, given the outputs spent by all of its inputs.
+  /// Only the tweaked recovery key is kept, so the internal key that signs
+  /// the reveal script is recovered by subtracting the taproot tweak.
+  pub(crate) fn sign_reveal_transaction(
+    reveal_tx: &Transaction,
+    commit_input: usize,
+    commit_outpoint: OutPoint,
+    prevouts: &[TxOut],
+    recovery_key_pair: &KeyPair,
+  ) -> Result<Transaction> {
+    let secp256k1 = Secp256k1::new();
+
+    let witness = &reveal_tx.input[commit_input].witness;
+
+    let reveal_script = ScriptBuf::from(
+      witness
+        .tapscript()
+        .ok_or_else(|| anyhow!("reveal transaction is missing reveal script"))?,
+    );
+
+    let control_block = ControlBlock::decode(
+      witness
+        .last()
+        .ok_or_else(|| anyhow!("reveal transaction is missing control block"))?,
+    )?;
+
+    let merkle_root = TaprootBuilder::new()
+      .add_leaf(0, reveal_script.clone())?
+      .finalize(&secp256k1, control_block.internal_key)
+      .map_err(|_| anyhow!("reveal script taproot tree is incomplete"))?
+      .merkle_root();
+
+    let tweak = TapTweakHash::from_key_and_tweak(control_block.internal_key, merkle_root);
+
+    let key_pair = KeyPair::from_secret_key(
+      &secp256k1,
+      &recovery_key_pair
+        .secret_key()
+        .add_tweak(&Scalar::from_be_bytes(
+          SecretKey::from_slice(&tweak.to_scalar().to_be_bytes())?
+            .negate()
+            .secret_bytes(),
+        )?)?,
+    );
+
+    ensure!(
+      key_pair.x_only_public_key().0 == control_block.internal_key
+        && key_pair
+          .tap_tweak(&secp256k1, merkle_root)
+          .to_inner()
+          .x_only_public_key()
+          .0
+          == recovery_key_pair.x_only_public_key().0,
+      "recovery key does not match reveal transaction"
+    );
+
+    let mut reveal_tx = reveal_tx.clone();
+
+    reveal_tx.input[commit_input].previous_output = commit_outpoint;
+
+    let sighash = SighashCache::new(&reveal_tx)
+      .taproot_script_spend_signature_hash(
+        commit_input,
+        &Prevouts::All(prevouts),
+        TapLeafHash::from_script(&reveal_script, LeafVersion::TapScript),
+        TapSighashType::Default,
+      )
+      .expect("signature hash should compute");
+
+    let sig = secp256k1.sign_schnorr(
+      &Message::from_slice(sighash.as_ref()).expect("should be cryptographically secure hash"),
+      &key_pair,
+    );
+
+    let mut witness = Witness::new();
+    witness.push(
+      Signature {
+        sig,
+        hash_ty: TapSighashType::Default,
+      }
+      .to_vec(),
+    );
+    witness.push(reveal_script);
+    witness.push(control_block.serialize());
+
+    reveal_tx.input[commit_input].witness = witness;
+
+    Ok(reveal_tx)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    bitcoin::secp256k1::{constants::SCHNORR_SIGNATURE_SIZE, rand, schnorr},
+  };
+
+  struct Inscription {
+    commit_tx: Transaction,
+    reveal_tx: Transaction,
+    recovery_key_pair: KeyPair,
+  }
+
+  fn inscription() -> Inscription {
+    let secp256k1 = Secp256k1::new();
+    let key_pair = KeyPair::new(&secp256k1, &mut rand::thread_rng());
+    let (public_key, _parity) = key_pair.x_only_public_key();
+
+    let reveal_script = ScriptBuf::builder()
+      .push_slice(public_key.serialize())
+      .push_opcode(opcodes::all::OP_CHECKSIG)
+      .into_script();
+
+    let taproot_spend_info = TaprootBuilder::new()
+      .add_leaf(0, reveal_script.clone())
+      .unwrap()
+      .finalize(&secp256k1, public_key)
+      .unwrap();
+
+    let control_block = taproot_spend_info
+      .control_block(&(reveal_script.clone(), LeafVersion::TapScript))
+      .unwrap();
+
+    let commit_tx = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: vec![tx_in(outpoint(1))],
+      output: vec![
+        TxOut {
+          value: 10_000,
+          script_pubkey: ScriptBuf::new_v1_p2tr_tweaked(taproot_spend_info.output_key()),
+        },
+        tx_out(5_000, change(0)),
+      ],
+    };
+
+    let mut witness = Witness::new();
+    witness.push([0; SCHNORR_SIGNATURE_SIZE]);
+    witness.push(reveal_script);
+    witness.push(control_block.serialize());
+
+    let reveal_tx = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: OutPoint {
+          txid: commit_tx.txid(),
+          vout: 0,
+        },
+        script_sig: ScriptBuf::new(),
+        witness,
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+      }],
+      output: vec![tx_out(9_000, recipient())],
+    };
+
+    Inscription {
+      commit_tx,
+      reveal_tx,
+      recovery_key_pair: key_pair
+        .tap_tweak(&secp256k1, taproot_spend_info.merkle_root())
+        .to_inner(),
+    }
+  }
+
+  fn assert_reveal_signature_is_valid(
+    reveal_tx: &Transaction,
+    commit_input: usize,
+    prevouts: &[TxOut],
+  ) {
+    let witness = &reveal_tx.input[commit_input].witness;
+    let reveal_script = witness.tapscript().unwrap();
+    let control_block = ControlBlock::decode(witness.last().unwrap()).unwrap();
+
+    let sighash = SighashCache::new(reveal_tx)
+      .taproot_script_spend_signature_hash(
+        commit_input,
+        &Prevouts::All(prevouts),
+        TapLeafHash::from_script(reveal_script, LeafVersion::TapScript),
+        TapSighashType::Default,
+      )
+      .unwrap();
+
+    Secp256k1::new()
+      .verify_schnorr(
+        &schnorr::Signature::from_slice(&witness.nth(0).unwrap()[..SCHNORR_SIGNATURE_SIZE])
+          .unwrap(),
+        &Message::from_slice(sighash.as_ref()).unwrap(),
+        &control_block.internal_key,
+      )
+      .unwrap();
+  }
+
+  #[test]
+  fn reveal_is_signed_with_internal_key_recovered_from_recovery_key() {
+    let inscription = inscription();
+
+    let new_commit = OutPoint {
+      txid: txid(2),
+      vout: 0,
+    };
+
+    let prevouts = [inscription.commit_tx.output[0].clone()];
+
+    let reveal_tx = Bump::sign_reveal_transaction(
+      &inscription.reveal_tx,
+      0,
+      new_commit,
+      &prevouts,
+      &inscription.recovery_key_pair,
+    )
+    .unwrap();
+
+    assert_eq!(reveal_tx.input[0].previous_output, new_commit);
+    assert_eq!(reveal_tx.output, inscription.reveal_tx.output);
+    assert_reveal_signature_is_valid(&reveal_tx, 0, &prevouts);
+  }
+
+  #[test]
+  fn reveal_with_parent_is_signed_at_commit_input() {
+    let mut inscription = inscription();
+
+    inscription.reveal_tx.input.insert(0, tx_in(outpoint(3)));
+    inscription
+      .reveal_tx
+      .output
+      .insert(0, tx_out(10_000, change(1)));
+
+    let new_commit = OutPoint {
+      txid: txid(2),
+      vout: 0,
+    };
+
+    let prevouts = [
+      tx_out(10_000, change(1)),
+      inscription.commit_tx.output[0].clone(),
+    ];
+
+    let reveal_tx = Bump::sign_reveal_transaction(
+      &inscription.reveal_tx,
+      1,
+      new_commit,
+      &prevouts,
+      &inscription.recovery_key_pair,
+    )
+    .unwrap();
+
+    assert_eq!(reveal_tx.input[0], inscription.reveal_tx.input[0]);
+    assert_eq!(reveal_tx.input[1].previous_output, new_commit);
+    assert_reveal_signature_is_valid(&reveal_tx, 1, &prevouts);
+  }
+
+  #[test]
+  fn reveal_cannot_be_signed_with_other_recovery_key() {
+    let inscription = inscription();
+
+    assert_eq!(
+      Bump::sign_reveal_transaction(
+        &inscription.reveal_tx,
+        0,
+        inscription.reveal_tx.input[0].previous_output,
+        &[inscription.commit_tx.output[0].clone()],
+        &KeyPair::new(&Secp256k1::new(), &mut rand::thread_rng()),
+      )
+      .unwrap_err()
+      .to_string(),
+      "recovery key does not match reveal transaction",
+    );
+  }
+
+  #[test]
+  fn bump_commit_takes_fee_from_change() {
+    let inscription = inscription();
+
+    let commit_tx = Bump::bump_commit_transaction(
+      &inscription.commit_tx,
+      0,
+      16_000,
+      &BTreeMap::new(),
+      Amount::from_sat(2_000),
+      Amount::from_sat(1_000),
+      FeeRate::try_from(20.0).unwrap(),
+    )
+    .unwrap();
+
+    let fee = FeeRate::try_from(20.0)
+      .unwrap()
+      .fee(TransactionBuilder::estimate_vsize(
+        &commit_tx,
+        &BTreeMap::new(),
+      ))
+      .to_sat();
+
+    assert_eq!(commit_tx.input, inscription.commit_tx.input);
+    assert_eq!(commit_tx.output[0], inscription.commit_tx.output[0]);
+    assert_eq!(commit_tx.output[1].value, 16_000 - 10_000 - fee);
+  }
+
+  #[test]
+  fn bump_commit_requires_higher_fee() {
+    let inscription = inscription();
+
+    assert!(Bump::bump_commit_transaction(
+      &inscription.commit_tx,
+      0,
+      16_000,
+      &BTreeMap::new(),
+      Amount::from_sat(2_000),
+      Amount::from_sat(1_000),
+      FeeRate::try_from(1.0).unwrap(),
+    )
+    .unwrap_err()
+    .to_string()
+    .contains("already pays 1000 sat"));
+  }
+
+  #[test]
+  fn bump_commit_requires_change_output() {
+    let mut inscription = inscription();
+    inscription.commit_tx.output.pop();
+
+    assert_eq!(
+      Bump::bump_commit_transaction(
+        &inscription.commit_tx,
+        0,
+        11_000,
+        &BTreeMap::new(),
+        Amount::from_sat(2_000),
+        Amount::from_sat(1_000),
+        FeeRate::try_from(20.0).unwrap(),
+      )
+      .unwrap_err()
+      .to_string(),
+      "commit transaction has no change output to pay for fee bump",
+    );
+  }
+
+  #[test]
+  fn bump_commit_must_pay_for_replaced_transactions() {
+    let inscription = inscription();
+
+    let err = Bump::bump_commit_transaction(
+      &inscription.commit_tx,
+      0,
+      16_000,
+      &BTreeMap::new(),
+      Amount::from_sat(3_000),
+      Amount::from_sat(1_000),
+      FeeRate::try_from(20.0).unwrap(),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert!(
+      err.ends_with("and its descendants pay 3000 sat, so its replacement must pay at least 3142 sat, but only pays 2840 sat at the new fee rate"),
+      "{err}"
+    );
+  }
+
+  #[test]
+  fn bump_commit_estimates_inputs_by_script_type() {
+    let inscription = inscription();
+
+    let input_scripts = [(outpoint(1), change(1).script_pubkey())]
+      .into_iter()
+      .collect();
+
+    let commit_tx = Bump::bump_commit_transaction(
+      &inscription.commit_tx,
+      0,
+      16_000,
+      &input_scripts,
+      Amount::from_sat(2_000),
+      Amount::from_sat(1_000),
+      FeeRate::try_from(20.0).unwrap(),
+    )
+    .unwrap();
+
+    let fee = FeeRate::try_from(20.0)
+      .unwrap()
+      .fee(TransactionBuilder::estimate_vsize(
+        &commit_tx,
+        &input_scripts,
+      ))
+      .to_sat();
+
+    assert!(fee > 2840);
+    assert_eq!(commit_tx.output[1].value, 16_000 - 10_000 - fee);
+  }
+
+  #[test]
+  fn bump_commit_strips_script_sigs() {
+    let mut inscription = inscription();
+
+    inscription.commit_tx.input[0].script_sig =
+      script::Builder::new().push_slice([0; 22]).into_script();
+
+    let input_scripts = [(
+      outpoint(1),
+      ScriptBuf::new_p2sh(&bitcoin::ScriptHash::all_zeros()),
+    )]
+    .into_iter()
+    .collect();
+
+    let commit_tx = Bump::bump_commit_transaction(
+      &inscription.commit_tx,
+      0,
+      16_000,
+      &input_scripts,
+      Amount::from_sat(2_000),
+      Amount::from_sat(1_000),
+      FeeRate::try_from(20.0).unwrap(),
+    )
+    .unwrap();
+
+    assert!(commit_tx.input[0].script_sig.is_empty());
+
+    let fee = FeeRate::try_from(20.0)
+      .unwrap()
+      .fee(TransactionBuilder::estimate_vsize(
+        &commit_tx,
+        &input_scripts,
+      ))
+      .to_sat();
+
+    assert_eq!(commit_tx.output[1].value, 16_000 - 10_000 - fee);
+  }
+
+  #[test]
+  fn cpfp_pays_for_reveal_and_child() {
+    let inscription = inscription();
+
+    let child = Bump::build_cpfp_transaction(
+      &inscription.reveal_tx,
+      10_000,
+      FeeRate::try_from(20.0).unwrap(),
+    )
+    .unwrap();
+
+    let package_fee = FeeRate::try_from(20.0)
+      .unwrap()
+      .fee(
+        inscription.reveal_tx.vsize()
+          + TransactionBuilder::estimate_vsize(
+            &child,
+            &[(child.input[0].previous_output, recipient().script_pubkey())]
+              .into_iter()
+              .collect(),
+          ),
+      )
+      .to_sat();
+
+    assert_eq!(
+      child.input[0].previous_output.txid,
+      inscription.reveal_tx.txid()
+    );
+    assert_eq!(child.output[0].script_pubkey, recipient().script_pubkey());
+    assert_eq!(child.output[0].value, 9_000 - (package_fee - 1_000));
+  }
+
+  #[test]
+  fn bump_reveal_takes_fee_from_last_output() {
+    let inscription = inscription();
+
+    let prevouts = [inscription.commit_tx.output[0].clone()];
+
+    let reveal_tx = Bump::bump_reveal_transaction(
+      &inscription.reveal_tx,
+      0,
+      &prevouts,
+      Amount::from_sat(1_000),
+      Amount::from_sat(1_000),
+      FeeRate::try_from(20.0).unwrap(),
+    )
+    .unwrap();
+
+    let fee = FeeRate::try_from(20.0)
+      .unwrap()
+      .fee(inscription.reveal_tx.vsize())
+      .to_sat();
+
+    assert_eq!(reveal_tx.input, inscription.reveal_tx.input);
+    assert_eq!(
+      reveal_tx.output[0].script_pubkey,
+      recipient().script_pubkey()
+    );
+    assert_eq!(reveal_tx.output[0].value, 10_000 - fee);
+
+    let reveal_tx = Bump::sign_reveal_transaction(
+      &reveal_tx,
+      0,
+      reveal_tx.input[0].previous_output,
+      &prevouts,
+      &inscription.recovery_key_pair,
+    )
+    .unwrap();
+
+    assert_eq!(reveal_tx.vsize(), inscription.reveal_tx.vsize());
+    assert_reveal_signature_is_valid(&reveal_tx, 0, &prevouts);
+  }
+
+  #[test]
+  fn bump_reveal_leaves_parent_output_alone() {
+    let mut inscription = inscription();
+
+    inscription.reveal_tx.input.insert(0, tx_in(outpoint(3)));
+    inscription
+      .reveal_tx
+      .output
+      .insert(0, tx_out(10_000, change(1)));
+
+    let prevouts = [
+      tx_out(10_000, change(1)),
+      inscription.commit_tx.output[0].clone(),
+    ];
+
+    let reveal_tx = Bump::bump_reveal_transaction(
+      &inscription.reveal_tx,
+      1,
+      &prevouts,
+      Amount::from_sat(1_000),
+      Amount::from_sat(1_000),
+      FeeRate::try_from(20.0).unwrap(),
+    )
+    .unwrap();
+
+    let fee = FeeRate::try_from(20.0)
+      .unwrap()
+      .fee(inscription.reveal_tx.vsize())
+      .to_sat();
+
+    assert_eq!(reveal_tx.output[0], inscription.reveal_tx.output[0]);
+    assert_eq!(reveal_tx.output[1].value, 10_000 - fee);
+  }
+
+  #[test]
+  fn bump_reveal_requires_postage_output() {
+    let mut inscription = inscription();
+
+    inscription.reveal_tx.input.insert(0, tx_in(outpoint(3)));
+    inscription.reveal_tx.output = vec![tx_out(19_000, change(1))];
+
+    assert_eq!(
+      Bump::bump_reveal_transaction(
+        &inscription.reveal_tx,
+        1,
+        &[
+          tx_out(10_000, change(1)),
+          inscription.commit_tx.output[0].clone(),
+        ],
+        Amount::from_sat(1_000),
+        Amount::from_sat(1_000),
+        FeeRate::try_from(20.0).unwrap(),
+      )
+      .unwrap_err()
+      .to_string(),
+      format!(
+        "reveal transaction {} has no postage output to pay for fee bump",
+        inscription.reveal_tx.txid()
+      ),
+    );
+  }
+
+  #[test]
+  fn bump_reveal_must_pay_for_replaced_transactions() {
+    let inscription = inscription();
+
+    let err = Bump::bump_reveal_transaction(
+      &inscription.reveal_tx,
+      0,
+      &[inscription.commit_tx.output[0].clone()],
+      Amount::from_sat(5_000),
+      Amount::from_sat(1_000),
+      FeeRate::try_from(20.0).unwrap(),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert!(
+      err.contains("and its descendants pay 5000 sat")
+        && err.ends_with("use `--cpfp` to bump it with a child transaction instead"),
+      "{err}"
+    );
+  }
+
+  #[test]
+  fn bump_reveal_keeps_postage_above_dust_limit() {
+    let inscription = inscription();
+
+    let err = Bump::bump_reveal_transaction(
+      &inscription.reveal_tx,
+      0,
+      &[inscription.commit_tx.output[0].clone()],
+      Amount::from_sat(1_000),
+      Amount::from_sat(1_000),
+      FeeRate::try_from(100.0).unwrap(),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert!(
+      err.starts_with(
+        "reveal transaction output of 9000 sat is too small to pay for fee bump without going below 294 sat"
+      ),
+      "{err}"
+    );
+  }
+
+  #[test]
+  fn bump_reveal_keeps_inscriptions_in_postage() {
+    let mut inscription = inscription();
+
+    inscription.reveal_tx.input[0].witness = crate::Inscription {
+      pointer: Some(crate::Inscription::pointer_value(8_000)),
+      ..Default::default()
+    }
+    .to_witness();
+
+    let err = Bump::bump_reveal_transaction(
+      &inscription.reveal_tx,
+      0,
+      &[inscription.commit_tx.output[0].clone()],
+      Amount::from_sat(1_000),
+      Amount::from_sat(1_000),
+      FeeRate::try_from(30.0).unwrap(),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert!(
+      err.starts_with(
+        "reveal transaction output of 9000 sat is too small to pay for fee bump without going below 8001 sat"
+      ),
+      "{err}"
+    );
+  }
+}
@@ -1,10 +1,24 @@
 use bitcoin::key::{KeyPair, UntweakedKeyPair, XOnlyPublicKey};
-use bitcoin::taproot::{LeafVersion, TaprootBuilder};
+use bitcoin::taproot::{ControlBlock, LeafVersion, TaprootBuilder};
 use super::*;
 
 #[derive(Serialize, Deserialize)]
 pub struct Output {
     pub address: Option<String>,
+    pub tapscript: String,
+    pub control_block: String,
+    pub internal_key: String,
+}
+
+/// The commit address derived from a private key and inscription file,
+/// together with everything needed to spend it with a reveal transaction.
+pub(crate) struct Commitment {
+    pub(crate) secp256k1: Secp256k1<All>,
+    pub(crate) key_pair: KeyPair,
+    pub(crate) internal_key: XOnlyPublicKey,
+    pub(crate) reveal_script: ScriptBuf,
+    pub(crate) control_block: ControlBlock,
+    pub(crate) address: Address,
 }
 
 #[derive(Debug, Parser)]
@@ -57,10 +71,8 @@ impl CommitGenAddr {
         Ok((secp256k1, key_pair))
     }
 
-    pub(crate) fn run(self, options: Options) -> SubcommandResult {
-        let chain = options.chain();
-
-        let inscriptions = if let Some(file_path) = &self.file {
+    pub(crate) fn commitment(chain: Chain, prv: &str, file: Option<&Path>) -> Result<Commitment> {
+        let inscriptions = if let Some(file_path) = file {
             vec![Inscription::from_file(
                 chain,
                 file_path,
@@ -76,7 +88,7 @@ impl CommitGenAddr {
             vec![]
         };
 
-        let (secp256k1, key_pair) = Self::key_pair_from_str(&self.prv).unwrap();
+        let (secp256k1, key_pair) = Self::key_pair_from_str(prv)?;
         let (public_key, _parity) = XOnlyPublicKey::from_keypair(&key_pair);
 
         let reveal_script = Inscription::append_batch_reveal_script(
@@ -85,25 +97,37 @@ impl CommitGenAddr {
                 .push_slice(public_key.serialize())
                 .push_opcode(opcodes::all::OP_CHECKSIG),
         );
-        // println!("reveal_script {:?}", reveal_script);
 
         let taproot_spend_info = TaprootBuilder::new()
             .add_leaf(0, reveal_script.clone())
             .expect("adding leaf should work")
             .finalize(&secp256k1, public_key)
             .expect("finalizing taproot builder should work");
-        // println!("taproot_spend_info {:?}", taproot_spend_info);
 
-        // let control_block = taproot_spend_info
-        //     .control_block(&(reveal_script.clone(), LeafVersion::TapScript))
-        //     .expect("should compute control block");
-        // println!("control_block {:?}", control_block);
+        let control_block = taproot_spend_info
+            .control_block(&(reveal_script.clone(), LeafVersion::TapScript))
+            .expect("should compute control block");
 
-        let commit_tx_address = Address::p2tr_tweaked(taproot_spend_info.output_key(), chain.network());
-        // println!("commit_tx_address(temp addr) {:?}", commit_tx_address);
+        let address = Address::p2tr_tweaked(taproot_spend_info.output_key(), chain.network());
+
+        Ok(Commitment {
+            secp256k1,
+            key_pair,
+            internal_key: public_key,
+            reveal_script,
+            control_block,
+            address,
+        })
+    }
+
+    pub(crate) fn run(self, options: Options) -> SubcommandResult {
+        let commitment = Self::commitment(options.chain(), &self.prv, self.file.as_deref())?;
 
         Ok(Box::new(crate::subcommand::wallet::commit_gen_addr::Output {
-            address: Option::from(commit_tx_address.to_string()),
+            address: Option::from(commitment.address.to_string()),
+            tapscript: hex::encode(commitment.reveal_script.as_bytes()),
+            control_block: hex::encode(commitment.control_block.serialize()),
+            internal_key: commitment.internal_key.to_string(),
         }))
     }
 }
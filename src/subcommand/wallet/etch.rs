@@ -4,8 +4,11 @@ use super::*;
 pub(crate) struct Etch {
   #[clap(long, help = "Set divisibility to <DIVISIBILITY>.")]
   divisibility: u8,
-  #[clap(long, help = "Etch with fee rate of <FEE_RATE> sats/vB.")]
-  fee_rate: FeeRate,
+  #[clap(
+    long,
+    help = "Etch with fee rate of <FEE_RATE> sats/vB, or `auto:<BLOCKS>` to estimate a fee rate that confirms within <BLOCKS> blocks."
+  )]
+  fee_rate: FeeRateArgument,
   #[clap(long, help = "Etch rune <RUNE>. May contain `.` or `•`as spacers.")]
   rune: SpacedRune,
   #[clap(long, help = "Set supply to <SUPPLY>.")]
@@ -16,6 +19,7 @@ pub(crate) struct Etch {
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Output {
+  pub fee_rate: f64,
   pub transaction: Txid,
 }
 
@@ -115,7 +119,9 @@ impl Etch {
       bail!("failed to lock UTXOs");
     }
 
-    let unsigned_transaction = fund_raw_transaction(&client, self.fee_rate, &unfunded_transaction)?;
+    let fee_rate = self.fee_rate.resolve(&client, &options)?;
+
+    let unsigned_transaction = fund_raw_transaction(&client, fee_rate, &unfunded_transaction)?;
 
     let signed_transaction = client
       .sign_raw_transaction_with_wallet(&unsigned_transaction, None, None)?
@@ -123,6 +129,9 @@ impl Etch {
 
     let transaction = client.send_raw_transaction(&signed_transaction)?;
 
-    Ok(Box::new(Output { transaction }))
+    Ok(Box::new(Output {
+      fee_rate: fee_rate.n(),
+      transaction,
+    }))
   }
 }
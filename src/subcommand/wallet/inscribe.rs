@@ -2,21 +2,25 @@ use {
   self::batch::{Batch, Batchfile, Mode},
   super::*,
   crate::subcommand::wallet::transaction_builder::Target,
+  base64::Engine,
   bitcoin::{
+    address::Payload,
     blockdata::{opcodes, script},
     key::PrivateKey,
     key::{TapTweak, TweakedKeyPair, TweakedPublicKey, UntweakedKeyPair},
     policy::MAX_STANDARD_TX_WEIGHT,
+    psbt::{raw::ProprietaryKey, Psbt},
     secp256k1::{self, constants::SCHNORR_SIGNATURE_SIZE, rand, Secp256k1, XOnlyPublicKey},
     sighash::{Prevouts, SighashCache, TapSighashType},
     taproot::Signature,
-    taproot::{ControlBlock, LeafVersion, TapLeafHash, TaprootBuilder},
+    taproot::{ControlBlock, LeafVersion, TapLeafHash, TapTree, TaprootBuilder},
   },
   bitcoincore_rpc::bitcoincore_rpc_json::{ImportDescriptors, SignRawTransactionInput, Timestamp},
   bitcoincore_rpc::Client,
 };
 
 mod batch;
+pub mod finalize;
 
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct InscriptionInfo {
@@ -24,12 +28,36 @@ pub struct InscriptionInfo {
   pub location: SatPoint,
 }
 
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct InputInfo {
+  pub outpoint: OutPoint,
+  pub value: u64,
+}
+
+/// Fee rates are the ones requested, effective fee rates are the fees paid
+/// divided by the transaction vsizes. Raw transactions are signed unless the
+/// commit transaction was not broadcast.
 #[derive(Serialize, Deserialize)]
 pub struct Output {
   pub commit: Option<Txid>,
+  pub commit_address: Address<NetworkUnchecked>,
+  pub commit_effective_fee_rate: f64,
+  pub commit_fee: u64,
+  pub commit_fee_rate: f64,
+  pub commit_hex: String,
+  pub commit_vsize: usize,
+  pub inputs: Vec<InputInfo>,
   pub inscriptions: Vec<InscriptionInfo>,
   pub parent: Option<InscriptionId>,
+  pub preview: Option<crate::orddefi::Preview>,
+  pub psbt: Option<String>,
   pub reveal: Txid,
+  pub reveal_effective_fee_rate: f64,
+  pub reveal_fee: u64,
+  pub reveal_fee_rate: f64,
+  pub reveal_hex: String,
+  pub reveal_script: ScriptBuf,
+  pub reveal_vsize: usize,
   pub total_fees: u64,
 }
 
@@ -47,7 +75,22 @@ pub(crate) struct ParentInfo {
       .required(true)
       .args(&["file", "batch"]),
 )]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub(crate) struct Inscribe {
+  #[arg(
+    long,
+    default_value = AuthOutput::DEFAULT_PAYLOAD,
+    value_parser = AuthOutput::parse_payload,
+    help = "Include <AUTH_PAYLOAD> of at most 80 bytes in the commit transaction's OrdDeFi authentication OP_RETURN output. OrdDeFi indexers only accept the default payload, so any other payload disables authentication."
+  )]
+  pub(crate) auth_payload: String,
+  #[arg(
+    long,
+    value_enum,
+    default_value = "last",
+    help = "Put the commit transaction's OrdDeFi authentication OP_RETURN output at <AUTH_POSITION>."
+  )]
+  pub(crate) auth_position: AuthOutputPosition,
   #[arg(
     long,
     help = "Inscribe multiple inscriptions defined in a yaml <BATCH_FILE>.",
@@ -64,9 +107,9 @@ pub(crate) struct Inscribe {
   pub(crate) cbor_metadata: Option<PathBuf>,
   #[arg(
     long,
-    help = "Use <COMMIT_FEE_RATE> sats/vbyte for commit transaction.\nDefaults to <FEE_RATE> if unset."
+    help = "Use <COMMIT_FEE_RATE> sats/vbyte for commit transaction, or `auto:<BLOCKS>` to estimate a fee rate that confirms within <BLOCKS> blocks.\nDefaults to <FEE_RATE> if unset."
   )]
-  pub(crate) commit_fee_rate: Option<FeeRate>,
+  pub(crate) commit_fee_rate: Option<FeeRateArgument>,
   #[arg(long, help = "Compress inscription content with brotli.")]
   pub(crate) compress: bool,
   #[arg(long, help = "Send instruction from <ORIGIN>.")]
@@ -77,18 +120,30 @@ pub(crate) struct Inscribe {
   pub(crate) change: Option<Address<NetworkUnchecked>>,
   #[arg(long, help = "Don't sign or broadcast transactions.")]
   pub(crate) dry_run: bool,
-  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
-  pub(crate) fee_rate: FeeRate,
+  #[arg(
+    long,
+    help = "Use fee rate of <FEE_RATE> sats/vB, or `auto:<BLOCKS>` to estimate a fee rate that confirms within <BLOCKS> blocks."
+  )]
+  pub(crate) fee_rate: Option<FeeRateArgument>,
   #[arg(long, help = "Inscribe sat with contents of <FILE>.")]
   pub(crate) file: Option<PathBuf>,
+  #[arg(skip)]
+  pub(crate) instruction: Option<OrdDeFiInstruction>,
   #[arg(
     long,
     help = "Include JSON in file at <METADATA> converted to CBOR as inscription metadata",
     conflicts_with = "cbor_metadata"
   )]
   pub(crate) json_metadata: Option<PathBuf>,
+  #[arg(skip)]
+  pub(crate) max_slippage: Option<f64>,
   #[clap(long, help = "Set inscription metaprotocol to <METAPROTOCOL>.")]
   pub(crate) metaprotocol: Option<String>,
+  #[arg(
+    long,
+    help = "Do not check that <ORIGIN> equals <DESTINATION> for instructions that require authentication."
+  )]
+  pub(crate) no_auth_check: bool,
   #[arg(long, help = "Do not back up recovery key.")]
   pub(crate) no_backup: bool,
   #[arg(
@@ -98,6 +153,12 @@ pub(crate) struct Inscribe {
   pub(crate) no_limit: bool,
   #[clap(long, help = "Make inscription a child of <PARENT>.")]
   pub(crate) parent: Option<InscriptionId>,
+  #[arg(
+    long,
+    help = "Write the unsigned commit transaction to <PSBT_OUT> as a PSBT instead of signing and broadcasting it. Sign it elsewhere and broadcast with `wallet inscribe finalize`.",
+    conflicts_with = "dry_run"
+  )]
+  pub(crate) psbt_out: Option<PathBuf>,
   #[arg(
     long,
     help = "Amount of postage to include in the inscription. Default `10000sat`."
@@ -109,24 +170,102 @@ pub(crate) struct Inscribe {
   pub(crate) satpoint: Option<SatPoint>,
   #[arg(long, help = "Inscribe <SAT>.", conflicts_with = "satpoint")]
   pub(crate) sat: Option<Sat>,
+  #[arg(
+    long,
+    help = "Do not check that inscription content is a well-formed OrdDeFi instruction."
+  )]
+  pub(crate) skip_validation: bool,
+  #[command(subcommand)]
+  pub(crate) subcommand: Option<InscribeSubcommand>,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) enum InscribeSubcommand {
+  #[command(about = "Broadcast a signed commit PSBT and its reveal transaction")]
+  Finalize(finalize::Finalize),
 }
 
 impl Inscribe {
   pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+    if let Some(InscribeSubcommand::Finalize(finalize)) = self.subcommand {
+      return finalize.run(wallet, options);
+    }
+
+    Ok(Box::new(self.inscribe(wallet, options)?))
+  }
+
+  pub(crate) fn with_profile(self, profile: &Profile) -> Self {
+    Self {
+      change: self.change.or_else(|| profile.change.clone()),
+      destination: self.destination.or_else(|| profile.destination.clone()),
+      fee_rate: self.fee_rate.or(profile.fee_rate),
+      origin: self.origin.or_else(|| profile.origin.clone()),
+      postage: self.postage.or(profile.postage.map(Amount::from_sat)),
+      ..self
+    }
+  }
+
+  pub(crate) fn inscribe(self, wallet: String, options: Options) -> Result<Output> {
+    let chain = options.chain();
+
+    if !self.skip_validation {
+      self.validate_instructions(chain)?;
+    }
+
+    if !self.no_auth_check {
+      self.check_authentication(chain)?;
+    }
+
     let metadata = Inscribe::parse_metadata(self.cbor_metadata, self.json_metadata)?;
 
     let index = Index::open(&options)?;
+    index.update()?;
 
-    let client = bitcoin_rpc_client_for_wallet_command(wallet, &options)?;
-    let chain = options.chain();
+    let client = bitcoin_rpc_client_for_wallet_command(wallet.clone(), &options)?;
+
+    let fee_rate = self
+      .fee_rate
+      .ok_or_else(|| anyhow!("no fee rate given, pass `--fee-rate` or select a profile with one"))?
+      .resolve(&client, &options)?;
 
-    let origin: Option<Address> = self.origin.clone().and_then(|origin| origin.require_network(chain.network()).ok());
-    println!("origin address: {:?}", origin);
-    println!("destination address: {:?}", self.destination);
+    let commit_fee_rate = match self.commit_fee_rate {
+      Some(commit_fee_rate) => commit_fee_rate.resolve(&client, &options)?,
+      None => fee_rate,
+    };
+
+    let preview = Inscribe::preview_instruction(
+      self.instruction.as_ref(),
+      self.max_slippage,
+      &index,
+      &client,
+    )?;
 
-    let utxos = get_unspent_outputs_with_address(&client, &index, &origin, 1000)?;
+    let origin: Option<Address> = self
+      .origin
+      .clone()
+      .map(|origin| origin.require_network(chain.network()))
+      .transpose()?;
+    if let Some(origin) = &origin {
+      Inscribe::check_origin_type(origin)?;
+    }
+    log::info!("origin address: {origin:?}");
+    log::info!("destination address: {:?}", self.destination);
+
+    // outputs of watch-only origins are only known to the watch-only wallet
+    let watch_client = match origin {
+      Some(_) => watch::client(&wallet, &options)?,
+      None => None,
+    };
+
+    let utxos = get_indexed_unspent_outputs_with_address(
+      &client,
+      watch_client.as_ref(),
+      &index,
+      &origin,
+      1000,
+    )?;
     let locked_utxos = get_locked_outputs(&client)?;
-    let runic_utxos = BTreeSet::new();
+    let runic_utxos = index.get_runic_outputs(&utxos.keys().cloned().collect::<Vec<OutPoint>>())?;
 
     let postage;
     let destinations;
@@ -138,24 +277,34 @@ impl Inscribe {
 
     changes = vec![match self.change.clone() {
       Some(change) => change.require_network(chain.network())?,
-      None => get_change_address(&client, chain)?,
+      None => get_change_address_or_placeholder(&client, chain, self.dry_run)?,
     }];
 
     match (self.file, self.batch) {
-      (Some(file), None) => {
-        parent_info = None;
+      (file, None) => {
+        parent_info = Inscribe::get_parent_info(self.parent, &index, &client, chain, self.dry_run)?;
 
         postage = self.postage.unwrap_or(TARGET_POSTAGE);
 
-        inscriptions = vec![Inscription::from_file(
-          chain,
-          file,
-          self.parent,
-          None,
-          self.metaprotocol,
-          metadata,
-          self.compress,
-        )?];
+        inscriptions = vec![match (file, self.instruction) {
+          (Some(file), None) => Inscription::from_file(
+            chain,
+            file,
+            self.parent,
+            None,
+            self.metaprotocol,
+            metadata,
+            self.compress,
+          )?,
+          (None, Some(instruction)) => instruction.inscription(
+            chain,
+            self.parent,
+            self.metaprotocol,
+            metadata,
+            self.compress,
+          )?,
+          _ => unreachable!(),
+        }];
 
         mode = Mode::SeparateOutputs;
 
@@ -163,13 +312,14 @@ impl Inscribe {
 
         destinations = vec![match self.destination.clone() {
           Some(destination) => destination.require_network(chain.network())?,
-          None => get_change_address(&client, chain)?,
+          None => get_change_address_or_placeholder(&client, chain, self.dry_run)?,
         }];
       }
       (None, Some(batch)) => {
         let batchfile = Batchfile::load(&batch)?;
 
-        parent_info = Inscribe::get_parent_info(batchfile.parent, &index, &utxos, &client, chain)?;
+        parent_info =
+          Inscribe::get_parent_info(batchfile.parent, &index, &client, chain, self.dry_run)?;
 
         postage = batchfile
           .postage
@@ -183,6 +333,7 @@ impl Inscribe {
           metadata,
           postage,
           self.compress,
+          self.dry_run,
         )?;
 
         mode = batchfile.mode;
@@ -210,8 +361,19 @@ impl Inscribe {
       self.satpoint
     };
 
-    Batch {
-      commit_fee_rate: self.commit_fee_rate.unwrap_or(self.fee_rate),
+    let orddefi_instructions = inscriptions
+      .iter()
+      .map(OrdDeFiInstruction::from_inscription)
+      .collect::<Vec<Option<OrdDeFiInstruction>>>();
+
+    let dry_run = self.dry_run;
+
+    let output = Batch {
+      auth_output: AuthOutput {
+        payload: self.auth_payload.into_bytes(),
+        position: self.auth_position,
+      },
+      commit_fee_rate,
       destinations,
       changes,
       dry_run: self.dry_run,
@@ -219,13 +381,125 @@ impl Inscribe {
       mode,
       no_backup: self.no_backup,
       no_limit: self.no_limit,
+      origin,
       parent_info,
       postage,
+      preview,
+      psbt_out: self.psbt_out,
       reinscribe: self.reinscribe,
-      reveal_fee_rate: self.fee_rate,
+      reveal_fee_rate: fee_rate,
       satpoint,
     }
-    .inscribe(chain, &index, &client, &locked_utxos, runic_utxos, &utxos)
+    .inscribe(chain, &index, &client, &locked_utxos, runic_utxos, &utxos)?;
+
+    // the transactions are already broadcast, so failing to record them
+    // must not hide their txids
+    if !dry_run && output.psbt.is_none() {
+      if let Some(commit) = output.commit {
+        if let Err(err) = instructions::record(
+          &wallet,
+          &options,
+          commit,
+          output.reveal,
+          orddefi_instructions,
+        ) {
+          log::warn!("failed to record inscribed instructions: {err}");
+        }
+      }
+    }
+
+    Ok(output)
+  }
+
+  /// Preview `addlp`, `rmlp` and `swap` instructions against the indexed
+  /// pool. If `max_slippage` is given, pool instructions waiting in the
+  /// mempool are assumed to execute first, and the instruction is refused if
+  /// they would cause more than `max_slippage` percent slippage.
+  fn preview_instruction(
+    instruction: Option<&OrdDeFiInstruction>,
+    max_slippage: Option<f64>,
+    index: &Index,
+    client: &Client,
+  ) -> Result<Option<crate::orddefi::Preview>> {
+    let Some(instruction) = instruction else {
+      return Ok(None);
+    };
+
+    let Some(pair) = instruction.pair() else {
+      return Ok(None);
+    };
+
+    if !index.has_orddefi_index() {
+      ensure!(
+        max_slippage.is_none(),
+        "`--max-slippage` requires index created with `--index-orddefi` flag",
+      );
+      return Ok(None);
+    }
+
+    let (ltick, rtick) = crate::orddefi::split_pair(&pair).unwrap();
+
+    let pending = if max_slippage.is_some() {
+      Inscribe::pending_instructions(client)?
+    } else {
+      Vec::new()
+    };
+
+    let Some(preview) =
+      crate::orddefi::Preview::new(instruction, index.get_orddefi_pool(ltick, rtick)?, &pending)?
+    else {
+      return Ok(None);
+    };
+
+    if let Some(max_slippage) = max_slippage {
+      let slippage = preview.slippage * 100.0;
+
+      ensure!(
+        slippage <= max_slippage,
+        "{} pending instructions on pool `{pair}` cause {slippage:.2}% slippage, more than `--max-slippage` of {max_slippage}%",
+        preview.pending,
+      );
+    }
+
+    Ok(Some(preview))
+  }
+
+  /// Fetch mempool transactions in batches of `PENDING_BATCH_SIZE` requests
+  /// and collect the OrdDeFi instructions they inscribe. Transactions which
+  /// leave the mempool before they are fetched are skipped.
+  fn pending_instructions(client: &Client) -> Result<Vec<OrdDeFiInstruction>> {
+    const PENDING_BATCH_SIZE: usize = 1000;
+
+    let rpc = client.get_jsonrpc_client();
+    let mut instructions = Vec::new();
+
+    for txids in client.get_raw_mempool()?.chunks(PENDING_BATCH_SIZE) {
+      let params = txids
+        .iter()
+        .map(|txid| Ok(vec![serde_json::value::to_raw_value(txid)?]))
+        .collect::<Result<Vec<Vec<_>>>>()?;
+
+      let requests = params
+        .iter()
+        .map(|params| rpc.build_request("getrawtransaction", params))
+        .collect::<Vec<_>>();
+
+      for response in rpc.send_batch(&requests)?.into_iter().flatten() {
+        let Ok(hex) = response.result::<String>() else {
+          continue;
+        };
+
+        let tx: Transaction = consensus::deserialize(&hex::decode(hex)?)?;
+
+        instructions.extend(
+          ParsedEnvelope::from_transaction(&tx)
+            .iter()
+            .filter_map(|envelope| OrdDeFiInstruction::from_inscription(&envelope.payload)),
+        );
+      }
+    }
+
+    Ok(instructions)
   }
 
   fn parse_metadata(cbor: Option<PathBuf>, json: Option<PathBuf>) -> Result<Option<Vec<u8>>> {
@@ -247,13 +521,444 @@ impl Inscribe {
     }
   }
 
+  fn validate_instructions(&self, chain: Chain) -> Result {
+    if let Some(instruction) = &self.instruction {
+      instruction.validate(chain)?;
+    }
+
+    if let Some(file) = &self.file {
+      Inscribe::validate_instruction_file(file, chain)?;
+    }
+
+    if let Some(batch) = &self.batch {
+      for entry in Batchfile::load(batch)?.inscriptions {
+        Inscribe::validate_instruction_file(&entry.file, chain)?;
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Instructions that require authentication are aborted by the protocol
+  /// unless the commit is funded by the address the instruction is sent to,
+  /// so refuse to inscribe them when origin and destination differ.
+  fn check_authentication(&self, chain: Chain) -> Result {
+    let origin = self
+      .origin
+      .clone()
+      .map(|origin| origin.require_network(chain.network()))
+      .transpose()?;
+
+    let mut instructions = Vec::new();
+
+    if let Some(instruction) = &self.instruction {
+      instructions.push((instruction.clone(), self.destination.clone()));
+    }
+
+    if let Some(file) = &self.file {
+      if let Some(instruction) = Inscribe::read_instruction(file) {
+        instructions.push((instruction, self.destination.clone()));
+      }
+    }
+
+    if let Some(batch) = &self.batch {
+      for entry in Batchfile::load(batch)?.inscriptions {
+        if let Some(instruction) = Inscribe::read_instruction(&entry.file) {
+          instructions.push((instruction, entry.destination));
+        }
+      }
+    }
+
+    for (instruction, destination) in instructions {
+      if !instruction.requires_authentication() {
+        continue;
+      }
+
+      let destination = destination
+        .map(|destination| destination.require_network(chain.network()))
+        .transpose()?;
+
+      match (&origin, &destination) {
+        (Some(origin), Some(destination)) if origin == destination => {}
+        _ => bail!(
+          "`{}` instruction requires <ORIGIN> to equal <DESTINATION> for authentication, but origin is {} and destination is {}, use `--no-auth-check` to inscribe it anyway",
+          instruction.op(),
+          origin
+            .as_ref()
+            .map_or("unset".into(), |origin| format!("`{origin}`")),
+          destination
+            .as_ref()
+            .map_or("unset".into(), |destination| format!("`{destination}`")),
+        ),
+      }
+    }
+
+    Ok(())
+  }
+
+  /// The commit transaction's fee is estimated from the sizes of its signed
+  /// inputs, so only origins whose inputs have a predictable size can fund
+  /// commits. P2SH origins are assumed to wrap P2WPKH.
+  fn check_origin_type(origin: &Address) -> Result {
+    ensure!(
+      matches!(
+        origin.address_type(),
+        Some(
+          bitcoin::AddressType::P2pkh
+            | bitcoin::AddressType::P2sh
+            | bitcoin::AddressType::P2wpkh
+            | bitcoin::AddressType::P2tr
+        )
+      ),
+      "origin `{origin}` is not a P2PKH, P2SH-P2WPKH, P2WPKH or P2TR address, so the size of its signed inputs can't be estimated",
+    );
+
+    Ok(())
+  }
+
+  fn read_instruction(path: &Path) -> Option<OrdDeFiInstruction> {
+    fs::read_to_string(path).ok()?.parse().ok()
+  }
+
+  fn validate_instruction_file(path: &Path, chain: Chain) -> Result {
+    let content = fs::read(path).with_context(|| format!("io error reading {}", path.display()))?;
+
+    let Ok(content) = String::from_utf8(content) else {
+      bail!(
+        "{} is not UTF-8 text, so it cannot contain an OrdDeFi instruction, use `--skip-validation` to inscribe it anyway",
+        path.display()
+      );
+    };
+
+    content
+      .parse::<OrdDeFiInstruction>()
+      .and_then(|instruction| instruction.validate(chain))
+      .with_context(|| {
+        format!(
+          "{} is not a valid OrdDeFi instruction, use `--skip-validation` to inscribe it anyway",
+          path.display()
+        )
+      })
+  }
+
+  /// The parent must be owned by the wallet, though not necessarily by the
+  /// origin, so it is looked up among all wallet outputs.
   fn get_parent_info(
     parent: Option<InscriptionId>,
     index: &Index,
-    utxos: &BTreeMap<OutPoint, Amount>,
     client: &Client,
     chain: Chain,
+    dry_run: bool,
   ) -> Result<Option<ParentInfo>> {
-      Ok(None)
+    let Some(parent_id) = parent else {
+      return Ok(None);
+    };
+
+    let satpoint = index
+      .get_inscription_satpoint_by_id(parent_id)?
+      .ok_or_else(|| anyhow!("parent {parent_id} does not exist"))?;
+
+    ensure!(
+      get_unspent_outputs(client, index)?.contains_key(&satpoint.outpoint),
+      "parent {parent_id} not in wallet"
+    );
+
+    Ok(Some(ParentInfo {
+      destination: get_change_address_or_placeholder(client, chain, dry_run)?,
+      id: parent_id,
+      location: satpoint,
+      tx_out: index
+        .get_transaction(satpoint.outpoint.txid)?
+        .ok_or_else(|| anyhow!("parent transaction {} not found", satpoint.outpoint.txid))?
+        .output
+        .into_iter()
+        .nth(satpoint.outpoint.vout.try_into().unwrap())
+        .ok_or_else(|| anyhow!("parent output {} not found", satpoint.outpoint))?,
+    }))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse_inscribe_args(args: &str) -> Inscribe {
+    match Arguments::try_parse_from(args.split_whitespace()) {
+      Ok(arguments) => match arguments.subcommand {
+        crate::subcommand::Subcommand::Wallet(Wallet {
+          subcommand: Subcommand::Inscribe(inscribe),
+          ..
+        }) => inscribe,
+        subcommand => panic!("unexpected subcommand: {subcommand:?}"),
+      },
+      Err(err) => panic!("error parsing arguments: {err}"),
+    }
+  }
+
+  fn inscribe_instruction(instruction: &str, args: &str) -> (TempDir, Inscribe) {
+    let tempdir = TempDir::new().unwrap();
+    let file = tempdir.path().join("instruction.txt");
+    fs::write(&file, instruction).unwrap();
+
+    let inscribe = parse_inscribe_args(&format!(
+      "ord wallet inscribe --fee-rate 1 --file {} {args}",
+      file.display()
+    ));
+
+    (tempdir, inscribe)
+  }
+
+  const MINT: &str = r#"{"p":"orddefi","op":"mint","tick":"odfi","amt":"1000"}"#;
+
+  #[test]
+  fn authenticated_instruction_requires_origin_equal_to_destination() {
+    let (_tempdir, inscribe) = inscribe_instruction(
+      MINT,
+      &format!("--origin {} --destination {}", address(), address()),
+    );
+    inscribe.check_authentication(Chain::Mainnet).unwrap();
+
+    let (_tempdir, inscribe) = inscribe_instruction(
+      MINT,
+      &format!(
+        "--origin {} --destination bc1pm8wv7dwnzs5dd6fhgdnurhhpat0zzgly6yugtr472nqhlxatlhdsq6t3ku",
+        address()
+      ),
+    );
+    assert_eq!(
+      inscribe
+        .check_authentication(Chain::Mainnet)
+        .unwrap_err()
+        .to_string(),
+      format!(
+        "`mint` instruction requires <ORIGIN> to equal <DESTINATION> for authentication, but origin is `{}` and destination is `bc1pm8wv7dwnzs5dd6fhgdnurhhpat0zzgly6yugtr472nqhlxatlhdsq6t3ku`, use `--no-auth-check` to inscribe it anyway",
+        address()
+      ),
+    );
+  }
+
+  #[test]
+  fn authenticated_instruction_requires_origin_and_destination() {
+    let (_tempdir, inscribe) = inscribe_instruction(MINT, &format!("--origin {}", address()));
+    assert_eq!(
+      inscribe
+        .check_authentication(Chain::Mainnet)
+        .unwrap_err()
+        .to_string(),
+      format!(
+        "`mint` instruction requires <ORIGIN> to equal <DESTINATION> for authentication, but origin is `{}` and destination is unset, use `--no-auth-check` to inscribe it anyway",
+        address()
+      ),
+    );
+  }
+
+  #[test]
+  fn transferable_transfer_does_not_require_authentication() {
+    let (_tempdir, inscribe) = inscribe_instruction(
+      r#"{"p":"orddefi","op":"transfer","tick":"odfi","amt":"1000"}"#,
+      "",
+    );
+    inscribe.check_authentication(Chain::Mainnet).unwrap();
+  }
+
+  #[test]
+  fn non_instruction_content_is_not_checked() {
+    let (_tempdir, inscribe) = inscribe_instruction("HELLOWORLD", "");
+    inscribe.check_authentication(Chain::Mainnet).unwrap();
+  }
+
+  #[test]
+  fn validation_rejects_malformed_instruction_file() {
+    let (_tempdir, inscribe) = inscribe_instruction(
+      r#"{"p":"orddefi","op":"mint","tick":"odfi","amt":"1."}"#,
+      "",
+    );
+
+    let err = inscribe.validate_instructions(Chain::Mainnet).unwrap_err();
+
+    assert!(err.to_string().ends_with(
+      "is not a valid OrdDeFi instruction, use `--skip-validation` to inscribe it anyway"
+    ));
+    assert_eq!(
+      err.root_cause().to_string(),
+      "`amt` `1.` is not a decimal number"
+    );
+  }
+
+  #[test]
+  fn origin_inputs_must_have_predictable_size() {
+    for origin in [
+      "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2",
+      "3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy",
+      "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+      "bc1pm8wv7dwnzs5dd6fhgdnurhhpat0zzgly6yugtr472nqhlxatlhdsq6t3ku",
+    ] {
+      let origin = origin
+        .parse::<Address<NetworkUnchecked>>()
+        .unwrap()
+        .assume_checked();
+
+      Inscribe::check_origin_type(&origin).unwrap();
+    }
+
+    let origin = "bc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qccfmv3"
+      .parse::<Address<NetworkUnchecked>>()
+      .unwrap()
+      .assume_checked();
+
+    assert_eq!(
+      Inscribe::check_origin_type(&origin).unwrap_err().to_string(),
+      format!(
+        "origin `{origin}` is not a P2PKH, P2SH-P2WPKH, P2WPKH or P2TR address, so the size of its signed inputs can't be estimated"
+      ),
+    );
+  }
+
+  #[test]
+  fn validation_rejects_non_utf8_file() {
+    let tempdir = TempDir::new().unwrap();
+    let file = tempdir.path().join("image.png");
+    fs::write(&file, [0x89, b'P', b'N', b'G', 0xff]).unwrap();
+
+    let inscribe = parse_inscribe_args(&format!(
+      "ord wallet inscribe --fee-rate 1 --file {}",
+      file.display()
+    ));
+
+    assert_eq!(
+      inscribe
+        .validate_instructions(Chain::Mainnet)
+        .unwrap_err()
+        .to_string(),
+      format!(
+        "{} is not UTF-8 text, so it cannot contain an OrdDeFi instruction, use `--skip-validation` to inscribe it anyway",
+        file.display()
+      )
+    );
+  }
+
+  #[test]
+  fn finalize_does_not_require_inscribe_arguments() {
+    let inscribe = parse_inscribe_args("ord wallet inscribe finalize --psbt-in commit.psbt");
+
+    assert_eq!(inscribe.fee_rate, None);
+    assert!(matches!(
+      inscribe.subcommand,
+      Some(InscribeSubcommand::Finalize(finalize::Finalize { psbt_in }))
+        if psbt_in == Path::new("commit.psbt")
+    ));
+  }
+
+  #[test]
+  fn fee_rate_may_come_from_profile() {
+    let inscribe = parse_inscribe_args("ord wallet inscribe --file foo.txt");
+    assert_eq!(inscribe.fee_rate, None);
+
+    let inscribe = inscribe.with_profile(&Profile {
+      fee_rate: Some("auto:3".parse().unwrap()),
+      ..Default::default()
+    });
+    assert_eq!(inscribe.fee_rate, Some("auto:3".parse().unwrap()));
+  }
+
+  #[test]
+  fn flags_override_profile() {
+    let inscribe = parse_inscribe_args(&format!(
+      "ord wallet inscribe --fee-rate 1 --file foo.txt --origin {}",
+      address()
+    ))
+    .with_profile(&Profile {
+      change: Some(change(0).to_string().parse().unwrap()),
+      fee_rate: Some("auto:3".parse().unwrap()),
+      origin: Some(recipient().to_string().parse().unwrap()),
+      postage: Some(546),
+      ..Default::default()
+    });
+
+    assert_eq!(
+      inscribe.change,
+      Some(change(0).to_string().parse().unwrap())
+    );
+    assert_eq!(inscribe.fee_rate, Some("1".parse().unwrap()));
+    assert_eq!(
+      inscribe.origin,
+      Some(address().to_string().parse().unwrap())
+    );
+    assert_eq!(inscribe.postage, Some(Amount::from_sat(546)));
+  }
+
+  #[test]
+  fn auth_payload_must_fit_in_standard_op_return() {
+    let inscribe = parse_inscribe_args(&format!(
+      "ord wallet inscribe --fee-rate 1 --file foo.txt --auth-payload {}",
+      "a".repeat(80)
+    ));
+    assert_eq!(inscribe.auth_payload, "a".repeat(80));
+
+    assert!(Arguments::try_parse_from(
+      format!(
+        "ord wallet inscribe --fee-rate 1 --file foo.txt --auth-payload {}",
+        "a".repeat(81)
+      )
+      .split_whitespace()
+    )
+    .is_err());
+  }
+
+  #[test]
+  fn psbt_out_conflicts_with_dry_run() {
+    let inscribe =
+      parse_inscribe_args("ord wallet inscribe --fee-rate 1 --file foo.txt --psbt-out commit.psbt");
+    assert_eq!(inscribe.psbt_out, Some("commit.psbt".into()));
+
+    assert!(Arguments::try_parse_from(
+      "ord wallet inscribe --fee-rate 1 --file foo.txt --psbt-out commit.psbt --dry-run"
+        .split_whitespace()
+    )
+    .is_err());
+  }
+
+  #[test]
+  fn pending_instructions_are_fetched_from_the_mempool() {
+    let rpc_server = test_bitcoincore_rpc::spawn();
+    rpc_server.mine_blocks(2);
+
+    let swap = OrdDeFiInstruction::Swap {
+      ltick: "odfi".into(),
+      rtick: "odgv".into(),
+      spend: "odfi".into(),
+      amt: "100".into(),
+    };
+
+    rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        swap
+          .inscription(Chain::Mainnet, None, None, None, false)
+          .unwrap()
+          .to_witness(),
+      )],
+      ..Default::default()
+    });
+
+    rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..Default::default()
+    });
+
+    let client = Options::try_parse_from([
+      "ord",
+      "--cookie-file",
+      rpc_server.cookie_file().to_str().unwrap(),
+      "--rpc-url",
+      &rpc_server.url(),
+    ])
+    .unwrap()
+    .bitcoin_rpc_client(None)
+    .unwrap();
+
+    assert_eq!(Inscribe::pending_instructions(&client).unwrap(), vec![swap]);
   }
 }
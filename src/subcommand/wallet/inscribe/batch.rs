@@ -1,6 +1,7 @@
-use super::*;
+use {super::*, crate::subcommand::wallet::bump::Bump};
 
 pub(super) struct Batch {
+  pub(super) auth_output: AuthOutput,
   pub(super) commit_fee_rate: FeeRate,
   pub(super) destinations: Vec<Address>,
   pub(super) changes: Vec<Address>,
@@ -9,8 +10,11 @@ pub(super) struct Batch {
   pub(super) mode: Mode,
   pub(super) no_backup: bool,
   pub(super) no_limit: bool,
+  pub(super) origin: Option<Address>,
   pub(super) parent_info: Option<ParentInfo>,
   pub(super) postage: Amount,
+  pub(super) preview: Option<crate::orddefi::Preview>,
+  pub(super) psbt_out: Option<PathBuf>,
   pub(super) reinscribe: bool,
   pub(super) reveal_fee_rate: FeeRate,
   pub(super) satpoint: Option<SatPoint>,
@@ -19,6 +23,10 @@ pub(super) struct Batch {
 impl Default for Batch {
   fn default() -> Batch {
     Batch {
+      auth_output: AuthOutput {
+        payload: AuthOutput::DEFAULT_PAYLOAD.into(),
+        position: AuthOutputPosition::Last,
+      },
       commit_fee_rate: 1.0.try_into().unwrap(),
       destinations: Vec::new(),
       changes: Vec::new(),
@@ -27,8 +35,11 @@ impl Default for Batch {
       mode: Mode::SharedOutput,
       no_backup: false,
       no_limit: false,
+      origin: None,
       parent_info: None,
       postage: Amount::from_sat(10_000),
+      preview: None,
+      psbt_out: None,
       reinscribe: false,
       reveal_fee_rate: 1.0.try_into().unwrap(),
       satpoint: None,
@@ -45,12 +56,12 @@ impl Batch {
     locked_utxos: &BTreeSet<OutPoint>,
     runic_utxos: BTreeSet<OutPoint>,
     utxos: &BTreeMap<OutPoint, Amount>,
-  ) -> SubcommandResult {
-    let wallet_inscriptions: BTreeMap<SatPoint, InscriptionId> = BTreeMap::new();
+  ) -> Result<super::Output> {
+    let wallet_inscriptions = index.get_inscriptions(utxos)?;
 
     let change = &self.changes[0];
 
-    let (commit_tx, reveal_tx, recovery_key_pair, total_fees) = self
+    let (commit_tx, reveal_tx, recovery_key_pair, commit_fee, reveal_fee) = self
       .create_batch_inscription_transactions(
         wallet_inscriptions,
         chain,
@@ -60,18 +71,105 @@ impl Batch {
         change.clone(),
       )?;
 
-    // if self.dry_run {
-    //   return Ok(Box::new(self.output(
-    //     commit_tx.txid(),
-    //     reveal_tx.txid(),
-    //     total_fees,
-    //     self.inscriptions.clone(),
-    //   )));
-    // }
+    let prevouts = if self.origin.is_some() || self.psbt_out.is_some() {
+      let mut prevouts = BTreeMap::new();
+
+      for txin in &commit_tx.input {
+        let outpoint = txin.previous_output;
+        let tx_out = client
+          .get_tx_out(&outpoint.txid, outpoint.vout, Some(true))?
+          .ok_or_else(|| anyhow!("commit input {outpoint} is not unspent"))?;
+        prevouts.insert(
+          outpoint,
+          TxOut {
+            value: tx_out.value.to_sat(),
+            script_pubkey: tx_out.script_pub_key.script()?,
+          },
+        );
+      }
+
+      prevouts
+    } else {
+      BTreeMap::new()
+    };
+
+    if let Some(origin) = &self.origin {
+      let script_pubkeys = prevouts
+        .iter()
+        .map(|(outpoint, tx_out)| (*outpoint, tx_out.script_pubkey.clone()))
+        .collect();
+
+      Self::check_commit_inputs(&commit_tx, origin, &script_pubkeys)?;
+    }
+
+    if self.psbt_out.is_some() {
+      ensure!(
+        self.parent_info.is_none(),
+        "--psbt-out cannot be used with a parent inscription"
+      );
+
+      return self.external_commit(
+        chain,
+        client,
+        &commit_tx,
+        &reveal_tx,
+        &prevouts,
+        recovery_key_pair,
+        commit_fee,
+        reveal_fee,
+        utxos,
+      );
+    }
+
+    // dry runs stop before anything touches the wallet: nothing is signed by
+    // the wallet and the recovery key is not imported
+    if self.dry_run {
+      log::info!(
+        "unsigned raw commit transaction: {}",
+        consensus::encode::serialize_hex(&commit_tx)
+      );
+      log::info!(
+        "raw reveal transaction: {}",
+        consensus::encode::serialize_hex(&reveal_tx)
+      );
 
-    let signed_commit_tx = client
-      .sign_raw_transaction_with_wallet(&commit_tx, None, None)?
-      .hex;
+      return Ok(self.output(chain, &commit_tx, &reveal_tx, commit_fee, reveal_fee, utxos));
+    }
+
+    let signed_commit = client.sign_raw_transaction_with_wallet(&commit_tx, None, None)?;
+
+    // the wallet can't sign for watch-only origins, so hand the commit out
+    // for external signing before the recovery key is imported
+    if !signed_commit.complete {
+      ensure!(
+        self.origin.is_some() && self.parent_info.is_none(),
+        "wallet could not sign the commit transaction, and it can only be signed externally when inscribing from `--origin` without a parent inscription",
+      );
+
+      return self.external_commit(
+        chain,
+        client,
+        &commit_tx,
+        &reveal_tx,
+        &prevouts,
+        recovery_key_pair,
+        commit_fee,
+        reveal_fee,
+        utxos,
+      );
+    }
+
+    let signed_commit_tx = signed_commit.hex;
+
+    let signed_commit_txid =
+      consensus::encode::deserialize::<Transaction>(&signed_commit_tx)?.txid();
+
+    let reveal_tx = self.sign_reveal_against_signed_commit(
+      &commit_tx,
+      reveal_tx,
+      signed_commit_txid,
+      recovery_key_pair,
+    )?;
 
     let signed_reveal_tx = if self.parent_info.is_some() {
       client
@@ -83,7 +181,7 @@ impl Batch {
               .iter()
               .enumerate()
               .map(|(vout, output)| SignRawTransactionInput {
-                txid: commit_tx.txid(),
+                txid: signed_commit_txid,
                 vout: vout.try_into().unwrap(),
                 script_pub_key: output.script_pubkey.clone(),
                 redeem_script: None,
@@ -98,56 +196,125 @@ impl Batch {
       consensus::encode::serialize(&reveal_tx)
     };
 
-    let commit_hex_string: String = signed_commit_tx
-        .iter()
-        .map(|byte| format!("{:02x}", byte))
-        .collect();
-    println!("Signed raw commit transaction:");
-    println!("{commit_hex_string}");
-
-    let reveal_hex_string: String = signed_reveal_tx
-        .iter()
-        .map(|byte| format!("{:02x}", byte))
-        .collect();
-    println!("Signed raw reveal transaction:");
-    println!("{reveal_hex_string}");
+    log::info!(
+      "signed raw commit transaction: {}",
+      hex::encode(&signed_commit_tx)
+    );
+    log::info!(
+      "signed raw reveal transaction: {}",
+      hex::encode(&signed_reveal_tx)
+    );
 
     if !self.no_backup {
       Self::backup_recovery_key(client, recovery_key_pair, chain.network())?;
     }
 
-    let mut commit = Txid::all_zeros();
-    let mut reveal = Txid::all_zeros();
-
-    if self.dry_run == false {
-      commit = client.send_raw_transaction(&signed_commit_tx)?;
-      reveal = match client.send_raw_transaction(&signed_reveal_tx) {
-        Ok(txid) => txid,
-        Err(err) => {
-            return Err(anyhow!(
-              "Failed to send reveal transaction: {err}\nCommit tx {commit} will be recovered once mined"
-          ))
-        }
-      };
+    let commit = client.send_raw_transaction(&signed_commit_tx)?;
+
+    if let Err(err) = client.send_raw_transaction(&signed_reveal_tx) {
+      return Err(anyhow!(
+        "Failed to send reveal transaction: {err}\nCommit tx {commit} can be recovered with `wallet recover-commits` once mined"
+      ));
     }
 
-    Ok(Box::new(self.output(
-      Option::from(commit),
-      reveal,
-      total_fees,
-      self.inscriptions.clone(),
-    )))
+    Ok(self.output(
+      chain,
+      &consensus::encode::deserialize(&signed_commit_tx)?,
+      &consensus::encode::deserialize(&signed_reveal_tx)?,
+      commit_fee,
+      reveal_fee,
+      utxos,
+    ))
+  }
+
+  /// The reveal transaction is signed against the unsigned commit's txid,
+  /// which the script sigs of P2SH-P2WPKH and P2PKH inputs change, so it is
+  /// signed again against the signed commit if its txid differs.
+  fn sign_reveal_against_signed_commit(
+    &self,
+    commit_tx: &Transaction,
+    reveal_tx: Transaction,
+    signed_commit_txid: Txid,
+    recovery_key_pair: TweakedKeyPair,
+  ) -> Result<Transaction> {
+    if signed_commit_txid == commit_tx.txid() {
+      return Ok(reveal_tx);
+    }
+
+    let commit_input = usize::from(self.parent_info.is_some());
+
+    let commit_outpoint = reveal_tx.input[commit_input].previous_output;
+
+    let mut prevouts =
+      vec![commit_tx.output[usize::try_from(commit_outpoint.vout).unwrap()].clone()];
+
+    if let Some(parent_info) = &self.parent_info {
+      prevouts.insert(0, parent_info.tx_out.clone());
+    }
+
+    Bump::sign_reveal_transaction(
+      &reveal_tx,
+      commit_input,
+      OutPoint {
+        txid: signed_commit_txid,
+        vout: commit_outpoint.vout,
+      },
+      &prevouts,
+      &recovery_key_pair.to_inner(),
+    )
+  }
+
+  /// Return the unsigned commit transaction as a base64 PSBT, also written
+  /// to `--psbt-out` if given, to be signed elsewhere and broadcast together
+  /// with the reveal transaction by `wallet inscribe finalize`. The reveal
+  /// transaction is already signed with the ephemeral key.
+  fn external_commit(
+    &self,
+    chain: Chain,
+    client: &Client,
+    commit_tx: &Transaction,
+    reveal_tx: &Transaction,
+    prevouts: &BTreeMap<OutPoint, TxOut>,
+    recovery_key_pair: TweakedKeyPair,
+    commit_fee: u64,
+    reveal_fee: u64,
+    utxos: &BTreeMap<OutPoint, Amount>,
+  ) -> Result<super::Output> {
+    let psbt = base64::engine::general_purpose::STANDARD
+      .encode(Self::commit_psbt(commit_tx, reveal_tx, prevouts)?.serialize());
+
+    if let Some(psbt_out) = &self.psbt_out {
+      fs::write(psbt_out, &psbt)
+        .with_context(|| format!("io error writing {}", psbt_out.display()))?;
+
+      log::info!("unsigned commit PSBT written to {}", psbt_out.display());
+    }
+
+    if !self.no_backup {
+      Self::backup_recovery_key(client, recovery_key_pair, chain.network())?;
+    }
+
+    Ok(super::Output {
+      psbt: Some(psbt),
+      ..self.output(chain, commit_tx, reveal_tx, commit_fee, reveal_fee, utxos)
+    })
   }
 
+  /// `commit_tx` and `reveal_tx` are the signed transactions if they were
+  /// broadcast, otherwise the unsigned commit and presigned reveal.
   fn output(
     &self,
-    commit: Option<Txid>,
-    reveal: Txid,
-    total_fees: u64,
-    inscriptions: Vec<Inscription>,
+    chain: Chain,
+    commit_tx: &Transaction,
+    reveal_tx: &Transaction,
+    commit_fee: u64,
+    reveal_fee: u64,
+    utxos: &BTreeMap<OutPoint, Amount>,
   ) -> super::Output {
+    let reveal = reveal_tx.txid();
+
     let mut inscriptions_output = Vec::new();
-    for index in 0..inscriptions.len() {
+    for index in 0..self.inscriptions.len() {
       let index = u32::try_from(index).unwrap();
 
       let vout = match self.mode {
@@ -184,15 +351,70 @@ impl Batch {
       });
     }
 
+    let commit_input = if self.parent_info.is_some() { 1 } else { 0 };
+
+    let commit_output = &commit_tx.output
+      [usize::try_from(reveal_tx.input[commit_input].previous_output.vout).unwrap()];
+
+    let mut inputs = commit_tx
+      .input
+      .iter()
+      .map(|txin| InputInfo {
+        outpoint: txin.previous_output,
+        value: utxos[&txin.previous_output].to_sat(),
+      })
+      .collect::<Vec<InputInfo>>();
+
+    if let Some(parent_info) = &self.parent_info {
+      inputs.push(InputInfo {
+        outpoint: parent_info.location.outpoint,
+        value: parent_info.tx_out.value,
+      });
+    }
+
+    let commit_vsize = Self::estimate_vsize(
+      commit_tx,
+      &self.input_scripts(commit_tx.input.iter().map(|txin| txin.previous_output)),
+    );
+
+    let reveal_vsize = Self::estimate_vsize(reveal_tx, &BTreeMap::new());
+
     super::Output {
-      commit,
+      commit: Some(commit_tx.txid()),
+      commit_address: Address::new(
+        chain.network(),
+        Payload::from_script(&commit_output.script_pubkey)
+          .expect("commit output should have an address"),
+      ),
+      commit_effective_fee_rate: Self::effective_fee_rate(commit_fee, commit_vsize),
+      commit_fee,
+      commit_fee_rate: self.commit_fee_rate.n(),
+      commit_hex: consensus::encode::serialize_hex(commit_tx),
+      commit_vsize,
+      inputs,
       reveal,
-      total_fees,
+      reveal_effective_fee_rate: Self::effective_fee_rate(reveal_fee, reveal_vsize),
+      reveal_fee,
+      reveal_fee_rate: self.reveal_fee_rate.n(),
+      reveal_hex: consensus::encode::serialize_hex(reveal_tx),
+      reveal_script: reveal_tx.input[commit_input]
+        .witness
+        .tapscript()
+        .map(ScriptBuf::from)
+        .unwrap_or_default(),
+      reveal_vsize,
+      total_fees: commit_fee + reveal_fee,
       parent: self.parent_info.clone().map(|info| info.id),
+      preview: self.preview.clone(),
+      psbt: None,
       inscriptions: inscriptions_output,
     }
   }
 
+  fn effective_fee_rate(fee: u64, vsize: usize) -> f64 {
+    fee as f64 / vsize as f64
+  }
+
   pub(crate) fn create_batch_inscription_transactions(
     &self,
     wallet_inscriptions: BTreeMap<SatPoint, InscriptionId>,
@@ -201,7 +423,7 @@ impl Batch {
     runic_utxos: BTreeSet<OutPoint>,
     mut utxos: BTreeMap<OutPoint, Amount>,
     change: Address,
-  ) -> Result<(Transaction, Transaction, TweakedKeyPair, u64)> {
+  ) -> Result<(Transaction, Transaction, TweakedKeyPair, u64, u64)> {
     match self.mode {
       Mode::SameSat => assert_eq!(
         self.destinations.len(),
@@ -220,9 +442,41 @@ impl Batch {
       ),
     }
 
-    println!("change address: {:?}", change);
+    log::info!("change address: {change:?}");
 
     let satpoint = if let Some(satpoint) = self.satpoint {
+      let mut reinscription = false;
+
+      for (inscribed_satpoint, inscription_id) in &wallet_inscriptions {
+        if *inscribed_satpoint == satpoint {
+          reinscription = true;
+          if self.reinscribe {
+            continue;
+          } else {
+            return Err(anyhow!("sat at {} already inscribed", satpoint));
+          }
+        }
+
+        if inscribed_satpoint.outpoint == satpoint.outpoint {
+          return Err(anyhow!(
+            "utxo {} already inscribed with inscription {inscription_id} on sat {inscribed_satpoint}",
+            satpoint.outpoint,
+          ));
+        }
+      }
+
+      if self.reinscribe && !reinscription {
+        return Err(anyhow!(
+          "reinscribe flag set but this would not be a reinscription"
+        ));
+      }
+
+      ensure!(
+        !runic_utxos.contains(&satpoint.outpoint),
+        "utxo {} holds runes and cannot be inscribed on",
+        satpoint.outpoint,
+      );
+
       satpoint
     } else {
       let inscribed_utxos = wallet_inscriptions
@@ -244,7 +498,7 @@ impl Batch {
         })
         .ok_or_else(|| anyhow!("wallet contains no cardinal utxos"))?
     };
-    println!("using UTXO as outpoint {}:{}", satpoint.outpoint.txid.to_string(), satpoint.outpoint.vout);
+    log::info!("using UTXO as outpoint {}", satpoint.outpoint);
 
     let secp256k1 = Secp256k1::new();
     let key_pair = UntweakedKeyPair::new(&secp256k1, &mut rand::thread_rng());
@@ -256,22 +510,22 @@ impl Batch {
         .push_slice(public_key.serialize())
         .push_opcode(opcodes::all::OP_CHECKSIG),
     );
-    println!("reveal_script {:?}", reveal_script);
+    log::info!("reveal_script {reveal_script:?}");
 
     let taproot_spend_info = TaprootBuilder::new()
       .add_leaf(0, reveal_script.clone())
       .expect("adding leaf should work")
       .finalize(&secp256k1, public_key)
       .expect("finalizing taproot builder should work");
-    println!("taproot_spend_info {:?}", taproot_spend_info);
+    log::info!("taproot_spend_info {taproot_spend_info:?}");
 
     let control_block = taproot_spend_info
       .control_block(&(reveal_script.clone(), LeafVersion::TapScript))
       .expect("should compute control block");
-    println!("control_block {:?}", control_block);
+    log::info!("control_block {control_block:?}");
 
     let commit_tx_address = Address::p2tr_tweaked(taproot_spend_info.output_key(), chain.network());
-    println!("commit_tx_address(temp addr) {:?}", commit_tx_address);
+    log::info!("commit_tx_address(temp addr) {commit_tx_address:?}");
 
     let total_postage = match self.mode {
       Mode::SameSat => self.postage,
@@ -279,7 +533,7 @@ impl Batch {
         self.postage * u64::try_from(self.inscriptions.len()).unwrap()
       }
     };
-    println!("total_postage {:?}", total_postage);
+    log::info!("total_postage {total_postage:?}");
 
     let mut reveal_inputs = vec![OutPoint::null()];
     let mut reveal_outputs = self
@@ -293,10 +547,27 @@ impl Batch {
         },
       })
       .collect::<Vec<TxOut>>();
-    // println!("reveal_inputs {:?}", reveal_inputs);
-    // println!("reveal_outputs {:?}", reveal_outputs);
 
-    let commit_input = 0;
+    // the parent is spent by the first reveal input and returned to its owner
+    // in the first reveal output, ahead of the inscriptions
+    if let Some(ParentInfo {
+      location,
+      id: _,
+      destination,
+      tx_out,
+    }) = self.parent_info.clone()
+    {
+      reveal_inputs.insert(0, location.outpoint);
+      reveal_outputs.insert(
+        0,
+        TxOut {
+          script_pubkey: destination.script_pubkey(),
+          value: tx_out.value,
+        },
+      );
+    }
+
+    let commit_input = if self.parent_info.is_some() { 1 } else { 0 };
 
     let (_, reveal_fee) = Self::build_reveal_transaction(
       &control_block,
@@ -318,6 +589,8 @@ impl Batch {
       self.commit_fee_rate,
       Target::Value(reveal_fee + total_postage),
     )
+    .with_auth_output(self.auth_output.clone())
+    .with_input_scripts(self.input_scripts(utxos.keys().cloned()))
     .build_transaction()?;
 
     let (vout, _commit_output) = unsigned_commit_tx
@@ -415,10 +688,105 @@ impl Batch {
       ),
     );
 
-    let total_fees =
-      Self::calculate_fee(&unsigned_commit_tx, &utxos) + Self::calculate_fee(&reveal_tx, &utxos);
+    if let Some(parent_info) = &self.parent_info {
+      utxos.insert(
+        parent_info.location.outpoint,
+        Amount::from_sat(parent_info.tx_out.value),
+      );
+    }
+
+    let commit_fee = Self::calculate_fee(&unsigned_commit_tx, &utxos);
+    let reveal_fee = Self::calculate_fee(&reveal_tx, &utxos);
+
+    Ok((
+      unsigned_commit_tx,
+      reveal_tx,
+      recovery_key_pair,
+      commit_fee,
+      reveal_fee,
+    ))
+  }
+
+  /// OrdDeFi authenticates an instruction by the address funding its commit
+  /// transaction, so every commit input must be owned by the origin.
+  fn check_commit_inputs(
+    commit_tx: &Transaction,
+    origin: &Address,
+    script_pubkeys: &BTreeMap<OutPoint, ScriptBuf>,
+  ) -> Result {
+    let origin_script_pubkey = origin.script_pubkey();
+
+    for txin in &commit_tx.input {
+      let outpoint = txin.previous_output;
+
+      let script_pubkey = script_pubkeys
+        .get(&outpoint)
+        .ok_or_else(|| anyhow!("could not find script pubkey of commit input {outpoint}"))?;
+
+      ensure!(
+        *script_pubkey == origin_script_pubkey,
+        "commit input {outpoint} is not owned by origin {origin}",
+      );
+    }
 
-    Ok((unsigned_commit_tx, reveal_tx, recovery_key_pair, total_fees))
+    Ok(())
+  }
+
+  /// Build a PSBT for the commit transaction so that it can be signed by keys
+  /// outside of the wallet. Inputs carry their witness UTXO, the commit
+  /// output carries the taproot tree of the reveal script, and the pre-signed
+  /// reveal transaction is stored under `Finalize::reveal_key` so that
+  /// `wallet inscribe finalize` can broadcast it after the commit.
+  fn commit_psbt(
+    commit_tx: &Transaction,
+    reveal_tx: &Transaction,
+    prevouts: &BTreeMap<OutPoint, TxOut>,
+  ) -> Result<Psbt> {
+    let mut psbt = Psbt::from_unsigned_tx(commit_tx.clone())?;
+
+    for (input, txin) in psbt.inputs.iter_mut().zip(&commit_tx.input) {
+      let outpoint = txin.previous_output;
+
+      let tx_out = prevouts
+        .get(&outpoint)
+        .ok_or_else(|| anyhow!("could not find previous output of commit input {outpoint}"))?;
+
+      ensure!(
+        tx_out.script_pubkey.is_witness_program(),
+        "commit input {outpoint} is not a native segwit output, and signing it externally would change the txid of the commit transaction that the pre-signed reveal transaction spends",
+      );
+
+      input.witness_utxo = Some(tx_out.clone());
+    }
+
+    let reveal_input = &reveal_tx.input[0];
+
+    let reveal_script = reveal_input
+      .witness
+      .tapscript()
+      .ok_or_else(|| anyhow!("reveal transaction is missing reveal script"))?;
+
+    let control_block = ControlBlock::decode(
+      reveal_input
+        .witness
+        .last()
+        .ok_or_else(|| anyhow!("reveal transaction is missing control block"))?,
+    )?;
+
+    let output = &mut psbt.outputs[usize::try_from(reveal_input.previous_output.vout).unwrap()];
+
+    output.tap_internal_key = Some(control_block.internal_key);
+    output.tap_tree = Some(
+      TapTree::try_from(TaprootBuilder::new().add_leaf(0, reveal_script.into())?)
+        .map_err(|_| anyhow!("reveal script taproot tree is incomplete"))?,
+    );
+
+    psbt.proprietary.insert(
+      finalize::Finalize::reveal_key(),
+      consensus::encode::serialize(reveal_tx),
+    );
+
+    Ok(psbt)
   }
 
   fn backup_recovery_key(
@@ -496,6 +864,26 @@ impl Batch {
     (reveal_tx, fee)
   }
 
+  /// Size of `tx` once signed, assuming that inputs without a witness yet
+  /// are taproot key path spends, like those of wallet outputs.
+  fn estimate_vsize(tx: &Transaction, input_scripts: &BTreeMap<OutPoint, ScriptBuf>) -> usize {
+    TransactionBuilder::estimate_vsize(tx, input_scripts)
+  }
+
+  /// Commit inputs must be owned by the origin, if one is given, so they all
+  /// share its script pubkey.
+  fn input_scripts(
+    &self,
+    outpoints: impl Iterator<Item = OutPoint>,
+  ) -> BTreeMap<OutPoint, ScriptBuf> {
+    match &self.origin {
+      Some(origin) => outpoints
+        .map(|outpoint| (outpoint, origin.script_pubkey()))
+        .collect(),
+      None => BTreeMap::new(),
+    }
+  }
+
   fn calculate_fee(tx: &Transaction, utxos: &BTreeMap<OutPoint, Amount>) -> u64 {
     tx.input
       .iter()
@@ -568,6 +956,7 @@ impl Batchfile {
     metadata: Option<Vec<u8>>,
     postage: Amount,
     compress: bool,
+    dry_run: bool,
   ) -> Result<(Vec<Inscription>, Vec<Address>)> {
     assert!(!self.inscriptions.is_empty());
 
@@ -610,13 +999,15 @@ impl Batchfile {
     }
 
     let destinations = match self.mode {
-      Mode::SharedOutput | Mode::SameSat => vec![get_change_address(client, chain)?],
+      Mode::SharedOutput | Mode::SameSat => {
+        vec![get_change_address_or_placeholder(client, chain, dry_run)?]
+      }
       Mode::SeparateOutputs => self
         .inscriptions
         .iter()
         .map(|entry| {
           entry.destination.as_ref().map_or_else(
-            || get_change_address(client, chain),
+            || get_change_address_or_placeholder(client, chain, dry_run),
             |address| {
               address
                 .clone()
@@ -631,3 +1022,495 @@ impl Batchfile {
     Ok((inscriptions, destinations))
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn commit_tx(inputs: &[OutPoint]) -> Transaction {
+    Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: inputs.iter().copied().map(tx_in).collect(),
+      output: vec![tx_out(10_000, recipient())],
+    }
+  }
+
+  #[test]
+  fn estimate_vsize_accounts_for_missing_signatures() {
+    let unsigned = commit_tx(&[outpoint(1), outpoint(2)]);
+
+    let mut signed = unsigned.clone();
+    for txin in &mut signed.input {
+      txin.witness = Witness::from_slice(&[&[1; SCHNORR_SIGNATURE_SIZE]]);
+    }
+
+    assert!(unsigned.vsize() < signed.vsize());
+    assert_eq!(
+      Batch::estimate_vsize(&unsigned, &BTreeMap::new()),
+      signed.vsize()
+    );
+    assert_eq!(
+      Batch::estimate_vsize(&signed, &BTreeMap::new()),
+      signed.vsize()
+    );
+  }
+
+  #[test]
+  fn commit_inputs_owned_by_origin_pass() {
+    let script_pubkeys = [
+      (outpoint(1), address().script_pubkey()),
+      (outpoint(2), address().script_pubkey()),
+    ]
+    .into_iter()
+    .collect();
+
+    Batch::check_commit_inputs(
+      &commit_tx(&[outpoint(1), outpoint(2)]),
+      &address(),
+      &script_pubkeys,
+    )
+    .unwrap();
+  }
+
+  #[test]
+  fn commit_input_owned_by_other_address_fails() {
+    let script_pubkeys = [
+      (outpoint(1), address().script_pubkey()),
+      (outpoint(2), change(0).script_pubkey()),
+    ]
+    .into_iter()
+    .collect();
+
+    assert_eq!(
+      Batch::check_commit_inputs(
+        &commit_tx(&[outpoint(1), outpoint(2)]),
+        &address(),
+        &script_pubkeys,
+      )
+      .unwrap_err()
+      .to_string(),
+      format!(
+        "commit input {} is not owned by origin {}",
+        outpoint(2),
+        address()
+      ),
+    );
+  }
+
+  #[test]
+  fn commit_input_with_unknown_script_pubkey_fails() {
+    assert_eq!(
+      Batch::check_commit_inputs(&commit_tx(&[outpoint(1)]), &address(), &BTreeMap::new())
+        .unwrap_err()
+        .to_string(),
+      format!(
+        "could not find script pubkey of commit input {}",
+        outpoint(1)
+      ),
+    );
+  }
+
+  #[test]
+  fn commit_psbt_includes_witness_utxos_taproot_tree_and_reveal() {
+    let utxos = vec![(outpoint(1), Amount::from_sat(20_000))];
+
+    let batch = Batch {
+      destinations: vec![recipient()],
+      inscriptions: vec![inscription("text/plain", "ord")],
+      mode: Mode::SeparateOutputs,
+      ..Default::default()
+    };
+
+    let (commit_tx, reveal_tx, _recovery_key_pair, _commit_fee, _reveal_fee) = batch
+      .create_batch_inscription_transactions(
+        BTreeMap::new(),
+        Chain::Mainnet,
+        BTreeSet::new(),
+        BTreeSet::new(),
+        utxos.into_iter().collect(),
+        change(0),
+      )
+      .unwrap();
+
+    let prevouts = [(outpoint(1), tx_out(20_000, address()))]
+      .into_iter()
+      .collect();
+
+    let psbt = Batch::commit_psbt(&commit_tx, &reveal_tx, &prevouts).unwrap();
+
+    assert_eq!(psbt.unsigned_tx, commit_tx);
+    assert_eq!(psbt.inputs[0].witness_utxo, Some(tx_out(20_000, address())));
+
+    let commit_output =
+      &psbt.outputs[usize::try_from(reveal_tx.input[0].previous_output.vout).unwrap()];
+    assert!(commit_output.tap_internal_key.is_some());
+    assert_eq!(
+      commit_output
+        .tap_tree
+        .as_ref()
+        .unwrap()
+        .script_leaves()
+        .map(|leaf| leaf.script().to_owned())
+        .collect::<Vec<ScriptBuf>>(),
+      vec![ScriptBuf::from(
+        reveal_tx.input[0].witness.tapscript().unwrap()
+      )],
+    );
+
+    assert_eq!(
+      psbt.proprietary.get(&finalize::Finalize::reveal_key()),
+      Some(&consensus::encode::serialize(&reveal_tx)),
+    );
+  }
+
+  #[test]
+  fn reveal_is_signed_again_if_signing_changes_commit_txid() {
+    let utxos = [(outpoint(1), Amount::from_sat(20_000))]
+      .into_iter()
+      .collect::<BTreeMap<OutPoint, Amount>>();
+
+    let batch = Batch {
+      destinations: vec![recipient()],
+      inscriptions: vec![inscription("text/plain", "ord")],
+      mode: Mode::SeparateOutputs,
+      ..Default::default()
+    };
+
+    let (commit_tx, reveal_tx, recovery_key_pair, _commit_fee, _reveal_fee) = batch
+      .create_batch_inscription_transactions(
+        BTreeMap::new(),
+        Chain::Mainnet,
+        BTreeSet::new(),
+        BTreeSet::new(),
+        utxos,
+        change(0),
+      )
+      .unwrap();
+
+    assert_eq!(
+      batch
+        .sign_reveal_against_signed_commit(
+          &commit_tx,
+          reveal_tx.clone(),
+          commit_tx.txid(),
+          recovery_key_pair,
+        )
+        .unwrap(),
+      reveal_tx,
+    );
+
+    let mut signed_commit_tx = commit_tx.clone();
+    signed_commit_tx.input[0].script_sig = script::Builder::new().push_slice([0; 22]).into_script();
+
+    let signed_reveal_tx = batch
+      .sign_reveal_against_signed_commit(
+        &commit_tx,
+        reveal_tx.clone(),
+        signed_commit_tx.txid(),
+        recovery_key_pair,
+      )
+      .unwrap();
+
+    assert_eq!(
+      signed_reveal_tx.input[0].previous_output,
+      OutPoint {
+        txid: signed_commit_tx.txid(),
+        vout: reveal_tx.input[0].previous_output.vout,
+      },
+    );
+    assert_ne!(
+      signed_reveal_tx.input[0].witness,
+      reveal_tx.input[0].witness
+    );
+    assert_eq!(
+      signed_reveal_tx.input[0].witness.tapscript(),
+      reveal_tx.input[0].witness.tapscript(),
+    );
+    assert_eq!(signed_reveal_tx.output, reveal_tx.output);
+  }
+
+  #[test]
+  fn output_includes_raw_transactions_inputs_and_effective_fee_rates() {
+    let utxos = [(outpoint(1), Amount::from_sat(20_000))]
+      .into_iter()
+      .collect::<BTreeMap<OutPoint, Amount>>();
+
+    let batch = Batch {
+      destinations: vec![recipient()],
+      inscriptions: vec![inscription("text/plain", "ord")],
+      mode: Mode::SeparateOutputs,
+      ..Default::default()
+    };
+
+    let (commit_tx, reveal_tx, _recovery_key_pair, commit_fee, reveal_fee) = batch
+      .create_batch_inscription_transactions(
+        BTreeMap::new(),
+        Chain::Mainnet,
+        BTreeSet::new(),
+        BTreeSet::new(),
+        utxos.clone(),
+        change(0),
+      )
+      .unwrap();
+
+    let output = batch.output(
+      Chain::Mainnet,
+      &commit_tx,
+      &reveal_tx,
+      commit_fee,
+      reveal_fee,
+      &utxos,
+    );
+
+    assert_eq!(output.commit, Some(commit_tx.txid()));
+    assert_eq!(output.reveal, reveal_tx.txid());
+    assert_ne!(output.reveal, Txid::all_zeros());
+    assert_eq!(
+      output.commit_hex,
+      consensus::encode::serialize_hex(&commit_tx)
+    );
+    assert_eq!(
+      output.reveal_hex,
+      consensus::encode::serialize_hex(&reveal_tx)
+    );
+    assert_eq!(
+      output.inputs,
+      vec![InputInfo {
+        outpoint: outpoint(1),
+        value: 20_000,
+      }]
+    );
+
+    let commit_output =
+      &commit_tx.output[usize::try_from(reveal_tx.input[0].previous_output.vout).unwrap()];
+    assert_eq!(
+      output.commit_address.assume_checked().script_pubkey(),
+      commit_output.script_pubkey
+    );
+
+    assert_eq!(
+      output.commit_effective_fee_rate,
+      commit_fee as f64 / output.commit_vsize as f64
+    );
+    assert!(output.reveal_effective_fee_rate >= 1.0);
+  }
+
+  #[test]
+  fn commit_psbt_requires_segwit_inputs() {
+    let commit_tx = commit_tx(&[outpoint(1)]);
+
+    let prevouts = [(
+      outpoint(1),
+      TxOut {
+        value: 20_000,
+        script_pubkey: ScriptBuf::new_p2pkh(&bitcoin::PubkeyHash::all_zeros()),
+      },
+    )]
+    .into_iter()
+    .collect();
+
+    assert_eq!(
+      Batch::commit_psbt(&commit_tx, &commit_tx, &prevouts)
+        .unwrap_err()
+        .to_string(),
+      format!(
+        "commit input {} is not a native segwit output, and signing it externally would change the txid of the commit transaction that the pre-signed reveal transaction spends",
+        outpoint(1)
+      ),
+    );
+  }
+
+  fn protected_utxos_batch(satpoint: Option<SatPoint>) -> Batch {
+    Batch {
+      destinations: vec![recipient()],
+      inscriptions: vec![inscription("text/plain", "ord")],
+      mode: Mode::SeparateOutputs,
+      satpoint,
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn commit_does_not_spend_inscribed_or_runic_utxos() {
+    let utxos = vec![
+      (outpoint(1), Amount::from_sat(20_000)),
+      (outpoint(2), Amount::from_sat(20_000)),
+      (outpoint(3), Amount::from_sat(20_000)),
+    ];
+
+    let (commit_tx, _reveal_tx, _recovery_key_pair, _commit_fee, _reveal_fee) =
+      protected_utxos_batch(None)
+        .create_batch_inscription_transactions(
+          [(satpoint(1, 0), inscription_id(1))].into_iter().collect(),
+          Chain::Mainnet,
+          BTreeSet::new(),
+          [outpoint(2)].into_iter().collect(),
+          utxos.into_iter().collect(),
+          change(0),
+        )
+        .unwrap();
+
+    assert_eq!(
+      commit_tx
+        .input
+        .iter()
+        .map(|txin| txin.previous_output)
+        .collect::<Vec<OutPoint>>(),
+      [outpoint(3)],
+    );
+  }
+
+  #[test]
+  fn inscribed_and_runic_utxos_are_not_used_as_fee_money() {
+    let utxos = vec![
+      (outpoint(1), Amount::from_sat(20_000)),
+      (outpoint(2), Amount::from_sat(20_000)),
+    ];
+
+    assert_eq!(
+      protected_utxos_batch(None)
+        .create_batch_inscription_transactions(
+          [(satpoint(1, 0), inscription_id(1))].into_iter().collect(),
+          Chain::Mainnet,
+          BTreeSet::new(),
+          [outpoint(2)].into_iter().collect(),
+          utxos.into_iter().collect(),
+          change(0),
+        )
+        .unwrap_err()
+        .to_string(),
+      "wallet contains no cardinal utxos",
+    );
+  }
+
+  #[test]
+  fn inscribing_on_inscribed_sat_requires_reinscribe() {
+    let utxos = vec![
+      (outpoint(1), Amount::from_sat(20_000)),
+      (outpoint(2), Amount::from_sat(20_000)),
+    ];
+
+    assert_eq!(
+      protected_utxos_batch(Some(satpoint(1, 0)))
+        .create_batch_inscription_transactions(
+          [(satpoint(1, 0), inscription_id(1))].into_iter().collect(),
+          Chain::Mainnet,
+          BTreeSet::new(),
+          BTreeSet::new(),
+          utxos.into_iter().collect(),
+          change(0),
+        )
+        .unwrap_err()
+        .to_string(),
+      format!("sat at {} already inscribed", satpoint(1, 0)),
+    );
+  }
+
+  #[test]
+  fn inscribing_on_runic_utxo_fails() {
+    let utxos = vec![
+      (outpoint(1), Amount::from_sat(20_000)),
+      (outpoint(2), Amount::from_sat(20_000)),
+    ];
+
+    assert_eq!(
+      protected_utxos_batch(Some(satpoint(1, 0)))
+        .create_batch_inscription_transactions(
+          BTreeMap::new(),
+          Chain::Mainnet,
+          BTreeSet::new(),
+          [outpoint(1)].into_iter().collect(),
+          utxos.into_iter().collect(),
+          change(0),
+        )
+        .unwrap_err()
+        .to_string(),
+      format!(
+        "utxo {} holds runes and cannot be inscribed on",
+        outpoint(1)
+      ),
+    );
+  }
+
+  #[test]
+  fn reveal_spends_parent_and_returns_it_to_owner() {
+    let utxos = [(outpoint(1), Amount::from_sat(20_000))]
+      .into_iter()
+      .collect::<BTreeMap<OutPoint, Amount>>();
+
+    let batch = Batch {
+      destinations: vec![recipient()],
+      inscriptions: vec![inscription("text/plain", "ord")],
+      mode: Mode::SeparateOutputs,
+      parent_info: Some(ParentInfo {
+        destination: change(1),
+        id: inscription_id(2),
+        location: satpoint(2, 0),
+        tx_out: tx_out(546, change(2)),
+      }),
+      ..Default::default()
+    };
+
+    let (commit_tx, reveal_tx, _recovery_key_pair, _commit_fee, reveal_fee) = batch
+      .create_batch_inscription_transactions(
+        BTreeMap::new(),
+        Chain::Mainnet,
+        BTreeSet::new(),
+        BTreeSet::new(),
+        utxos.clone(),
+        change(0),
+      )
+      .unwrap();
+
+    assert_eq!(reveal_tx.input.len(), 2);
+    assert_eq!(reveal_tx.input[0].previous_output, outpoint(2));
+    assert!(reveal_tx.input[0].witness.is_empty());
+    assert_eq!(reveal_tx.input[1].previous_output.txid, commit_tx.txid());
+    assert!(reveal_tx.input[1].witness.tapscript().is_some());
+
+    assert_eq!(reveal_tx.output[0], tx_out(546, change(1)));
+    assert_eq!(
+      reveal_tx.output[1].script_pubkey,
+      recipient().script_pubkey()
+    );
+
+    let commit_value =
+      commit_tx.output[usize::try_from(reveal_tx.input[1].previous_output.vout).unwrap()].value;
+
+    assert_eq!(
+      reveal_fee,
+      546 + commit_value
+        - reveal_tx
+          .output
+          .iter()
+          .map(|output| output.value)
+          .sum::<u64>()
+    );
+
+    let output = batch.output(
+      Chain::Mainnet,
+      &commit_tx,
+      &reveal_tx,
+      0,
+      reveal_fee,
+      &utxos,
+    );
+
+    assert_eq!(output.parent, Some(inscription_id(2)));
+    assert_eq!(output.inscriptions[0].location.outpoint.vout, 1);
+    assert_eq!(
+      output.inputs,
+      vec![
+        InputInfo {
+          outpoint: outpoint(1),
+          value: 20_000,
+        },
+        InputInfo {
+          outpoint: outpoint(2),
+          value: 546,
+        },
+      ]
+    );
+  }
+}
@@ -0,0 +1,227 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Finalize {
+  #[arg(long, help = "Read signed commit PSBT from <PSBT_IN>.")]
+  pub(crate) psbt_in: PathBuf,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Output {
+  pub commit: Txid,
+  pub reveal: Txid,
+}
+
+impl Finalize {
+  const PSBT_MAGIC: &'static [u8] = b"psbt\xff";
+
+  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+    let psbt = Self::load(&self.psbt_in)?;
+
+    let reveal_tx = Self::reveal_tx(&psbt)?;
+
+    let client = bitcoin_rpc_client_for_wallet_command(wallet.clone(), &options)?;
+
+    let result = client.finalize_psbt(
+      &base64::engine::general_purpose::STANDARD.encode(psbt.serialize()),
+      Some(true),
+    )?;
+
+    ensure!(result.complete, "commit PSBT is not fully signed");
+
+    let signed_commit_tx: Transaction = consensus::encode::deserialize(
+      &result
+        .hex
+        .ok_or_else(|| anyhow!("finalized commit PSBT did not contain a transaction"))?,
+    )?;
+
+    ensure!(
+      signed_commit_tx.txid() == psbt.unsigned_tx.txid(),
+      "signed commit transaction {} does not match unsigned commit transaction {}",
+      signed_commit_tx.txid(),
+      psbt.unsigned_tx.txid(),
+    );
+
+    let commit = client.send_raw_transaction(&signed_commit_tx)?;
+
+    let reveal = match client.send_raw_transaction(&reveal_tx) {
+      Ok(txid) => txid,
+      Err(err) => {
+        return Err(anyhow!(
+          "Failed to send reveal transaction: {err}\nCommit tx {commit} can be recovered with `wallet recover-commits` once mined"
+        ))
+      }
+    };
+
+    if let Err(err) = instructions::record(
+      &wallet,
+      &options,
+      commit,
+      reveal,
+      ParsedEnvelope::from_transaction(&reveal_tx)
+        .iter()
+        .map(|envelope| OrdDeFiInstruction::from_inscription(&envelope.payload)),
+    ) {
+      log::warn!("failed to record inscribed instructions: {err}");
+    }
+
+    Ok(Box::new(Output { commit, reveal }))
+  }
+
+  pub(super) fn reveal_key() -> ProprietaryKey {
+    ProprietaryKey {
+      prefix: crate::orddefi::PROTOCOL_ID.into(),
+      subtype: 0,
+      key: Vec::new(),
+    }
+  }
+
+  fn load(path: &Path) -> Result<Psbt> {
+    let contents =
+      fs::read(path).with_context(|| format!("io error reading {}", path.display()))?;
+
+    let bytes = if contents.starts_with(Self::PSBT_MAGIC) {
+      contents
+    } else {
+      base64::engine::general_purpose::STANDARD
+        .decode(String::from_utf8(contents)?.trim())
+        .with_context(|| format!("{} is not a base64 or binary PSBT", path.display()))?
+    };
+
+    Psbt::deserialize(&bytes).with_context(|| format!("failed to parse PSBT {}", path.display()))
+  }
+
+  fn reveal_tx(psbt: &Psbt) -> Result<Transaction> {
+    let reveal_tx: Transaction = consensus::encode::deserialize(
+      psbt
+        .proprietary
+        .get(&Self::reveal_key())
+        .ok_or_else(|| anyhow!("PSBT does not contain a reveal transaction"))?,
+    )?;
+
+    let commit = psbt.unsigned_tx.txid();
+
+    ensure!(
+      reveal_tx
+        .input
+        .iter()
+        .any(|txin| txin.previous_output.txid == commit),
+      "reveal transaction {} does not spend commit transaction {commit}",
+      reveal_tx.txid(),
+    );
+
+    Ok(reveal_tx)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, tempfile::TempDir};
+
+  fn psbt() -> Psbt {
+    let commit_tx = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: vec![tx_in(outpoint(1))],
+      output: vec![tx_out(10_000, recipient())],
+    };
+
+    let reveal_tx = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: vec![tx_in(OutPoint {
+        txid: commit_tx.txid(),
+        vout: 0,
+      })],
+      output: vec![tx_out(9_000, recipient())],
+    };
+
+    let mut psbt = Psbt::from_unsigned_tx(commit_tx).unwrap();
+
+    psbt.proprietary.insert(
+      Finalize::reveal_key(),
+      consensus::encode::serialize(&reveal_tx),
+    );
+
+    psbt
+  }
+
+  #[test]
+  fn load_base64_psbt() {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("commit.psbt");
+
+    fs::write(
+      &path,
+      format!(
+        "{}\n",
+        base64::engine::general_purpose::STANDARD.encode(psbt().serialize())
+      ),
+    )
+    .unwrap();
+
+    assert_eq!(Finalize::load(&path).unwrap(), psbt());
+  }
+
+  #[test]
+  fn load_binary_psbt() {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("commit.psbt");
+
+    fs::write(&path, psbt().serialize()).unwrap();
+
+    assert_eq!(Finalize::load(&path).unwrap(), psbt());
+  }
+
+  #[test]
+  fn load_invalid_psbt_fails() {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("commit.psbt");
+
+    fs::write(&path, "foo").unwrap();
+
+    assert_eq!(
+      Finalize::load(&path).unwrap_err().to_string(),
+      format!("{} is not a base64 or binary PSBT", path.display()),
+    );
+  }
+
+  #[test]
+  fn reveal_tx_is_read_from_psbt() {
+    let psbt = psbt();
+
+    assert_eq!(
+      Finalize::reveal_tx(&psbt).unwrap().input[0]
+        .previous_output
+        .txid,
+      psbt.unsigned_tx.txid(),
+    );
+  }
+
+  #[test]
+  fn missing_reveal_tx_fails() {
+    let mut psbt = psbt();
+    psbt.proprietary.clear();
+
+    assert_eq!(
+      Finalize::reveal_tx(&psbt).unwrap_err().to_string(),
+      "PSBT does not contain a reveal transaction",
+    );
+  }
+
+  #[test]
+  fn reveal_tx_must_spend_commit() {
+    let mut psbt = psbt();
+    psbt.unsigned_tx.output[0].value += 1;
+
+    let reveal_tx = Finalize::reveal_tx(&psbt).unwrap_err().to_string();
+
+    assert!(
+      reveal_tx.ends_with(&format!(
+        "does not spend commit transaction {}",
+        psbt.unsigned_tx.txid()
+      )),
+      "{reveal_tx}",
+    );
+  }
+}
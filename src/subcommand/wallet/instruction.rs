@@ -0,0 +1,351 @@
+use {super::*, inscribe::Inscribe};
+
+const MAX_SLIPPAGE_HELP: &str = "Refuse to inscribe if instructions waiting in the mempool would make the outcome more than <MAX_SLIPPAGE> percent worse than expected from indexed pool reserves. Requires index created with `--index-orddefi`.";
+
+fn parse_max_slippage(s: &str) -> Result<f64> {
+  let max_slippage = s.parse::<f64>()?;
+
+  ensure!(
+    (0.0..=100.0).contains(&max_slippage),
+    "max slippage must be a percentage between 0 and 100",
+  );
+
+  Ok(max_slippage)
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Instruction {
+  #[command(subcommand)]
+  pub(crate) op: Op,
+  #[arg(skip)]
+  pub(crate) profile: Profile,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) enum Op {
+  #[command(about = "Inscribe a `mint` instruction")]
+  Mint {
+    #[arg(long, help = "Mint <TICK>.")]
+    tick: String,
+    #[arg(long, help = "Mint <AMT> tokens.")]
+    amt: String,
+    #[command(flatten)]
+    inscribe: InscribeOptions,
+  },
+  #[command(about = "Inscribe a `transfer` instruction")]
+  Transfer {
+    #[arg(long, help = "Transfer <TICK>.")]
+    tick: String,
+    #[arg(long, help = "Transfer <AMT> tokens.")]
+    amt: String,
+    #[arg(
+      long,
+      help = "Transfer directly to <TO> instead of creating a transferable inscription."
+    )]
+    to: Option<Address<NetworkUnchecked>>,
+    #[command(flatten)]
+    inscribe: InscribeOptions,
+  },
+  #[command(about = "Inscribe an `addlp` instruction")]
+  Addlp {
+    #[arg(long, help = "Add <LAMT> of <LTICK>.")]
+    ltick: String,
+    #[arg(long, help = "Add <LAMT> of <LTICK>.")]
+    lamt: String,
+    #[arg(long, help = "Add <RAMT> of <RTICK>.")]
+    rtick: String,
+    #[arg(long, help = "Add <RAMT> of <RTICK>.")]
+    ramt: String,
+    #[arg(long, value_parser = parse_max_slippage, help = MAX_SLIPPAGE_HELP)]
+    max_slippage: Option<f64>,
+    #[command(flatten)]
+    inscribe: InscribeOptions,
+  },
+  #[command(about = "Inscribe an `rmlp` instruction")]
+  Rmlp {
+    #[arg(long, help = "Remove liquidity from pool <LTICK>-<RTICK>.")]
+    ltick: String,
+    #[arg(long, help = "Remove liquidity from pool <LTICK>-<RTICK>.")]
+    rtick: String,
+    #[arg(long, help = "Remove <AMT> LP tokens.")]
+    amt: String,
+    #[arg(long, value_parser = parse_max_slippage, help = MAX_SLIPPAGE_HELP)]
+    max_slippage: Option<f64>,
+    #[command(flatten)]
+    inscribe: InscribeOptions,
+  },
+  #[command(about = "Inscribe a `swap` instruction")]
+  Swap {
+    #[arg(long, help = "Swap in pool <LTICK>-<RTICK>.")]
+    ltick: String,
+    #[arg(long, help = "Swap in pool <LTICK>-<RTICK>.")]
+    rtick: String,
+    #[arg(
+      long,
+      help = "Spend <AMT> of <SPEND>, which must be <LTICK> or <RTICK>."
+    )]
+    spend: String,
+    #[arg(long, help = "Spend <AMT> of <SPEND>.")]
+    amt: String,
+    #[arg(long, value_parser = parse_max_slippage, help = MAX_SLIPPAGE_HELP)]
+    max_slippage: Option<f64>,
+    #[command(flatten)]
+    inscribe: InscribeOptions,
+  },
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct InscribeOptions {
+  #[arg(long, help = "Send change to <CHANGE>.")]
+  pub(crate) change: Option<Address<NetworkUnchecked>>,
+  #[arg(
+    long,
+    help = "Use <COMMIT_FEE_RATE> sats/vbyte for commit transaction, or `auto:<BLOCKS>` to estimate a fee rate that confirms within <BLOCKS> blocks.\nDefaults to <FEE_RATE> if unset."
+  )]
+  pub(crate) commit_fee_rate: Option<FeeRateArgument>,
+  #[arg(long, help = "Send instruction to <DESTINATION>.")]
+  pub(crate) destination: Option<Address<NetworkUnchecked>>,
+  #[arg(long, help = "Don't sign or broadcast transactions.")]
+  pub(crate) dry_run: bool,
+  #[arg(
+    long,
+    help = "Use fee rate of <FEE_RATE> sats/vB, or `auto:<BLOCKS>` to estimate a fee rate that confirms within <BLOCKS> blocks."
+  )]
+  pub(crate) fee_rate: Option<FeeRateArgument>,
+  #[arg(
+    long,
+    help = "Do not check that <ORIGIN> equals <DESTINATION> for instructions that require authentication."
+  )]
+  pub(crate) no_auth_check: bool,
+  #[arg(long, help = "Do not back up recovery key.")]
+  pub(crate) no_backup: bool,
+  #[arg(long, help = "Send instruction from <ORIGIN>.")]
+  pub(crate) origin: Option<Address<NetworkUnchecked>>,
+  #[arg(
+    long,
+    help = "Amount of postage to include in the inscription. Default `546sat`."
+  )]
+  pub(crate) postage: Option<Amount>,
+  #[arg(
+    long,
+    help = "Write the unsigned commit transaction to <PSBT_OUT> as a PSBT instead of signing and broadcasting it. Sign it elsewhere and broadcast with `wallet inscribe finalize`.",
+    conflicts_with = "dry_run"
+  )]
+  pub(crate) psbt_out: Option<PathBuf>,
+}
+
+impl Op {
+  fn into_parts(self) -> (OrdDeFiInstruction, InscribeOptions, Option<f64>) {
+    match self {
+      Self::Mint {
+        tick,
+        amt,
+        inscribe,
+      } => (OrdDeFiInstruction::Mint { tick, amt }, inscribe, None),
+      Self::Transfer {
+        tick,
+        amt,
+        to,
+        inscribe,
+      } => (
+        OrdDeFiInstruction::Transfer {
+          tick,
+          amt,
+          to: to.map(|to| to.assume_checked().to_string()),
+        },
+        inscribe,
+        None,
+      ),
+      Self::Addlp {
+        ltick,
+        lamt,
+        rtick,
+        ramt,
+        max_slippage,
+        inscribe,
+      } => (
+        OrdDeFiInstruction::Addlp {
+          ltick,
+          lamt,
+          rtick,
+          ramt,
+        },
+        inscribe,
+        max_slippage,
+      ),
+      Self::Rmlp {
+        ltick,
+        rtick,
+        amt,
+        max_slippage,
+        inscribe,
+      } => (
+        OrdDeFiInstruction::Rmlp { ltick, rtick, amt },
+        inscribe,
+        max_slippage,
+      ),
+      Self::Swap {
+        ltick,
+        rtick,
+        spend,
+        amt,
+        max_slippage,
+        inscribe,
+      } => (
+        OrdDeFiInstruction::Swap {
+          ltick,
+          rtick,
+          spend,
+          amt,
+        },
+        inscribe,
+        max_slippage,
+      ),
+    }
+  }
+}
+
+impl Instruction {
+  pub(crate) fn with_profile(self, profile: &Profile) -> Self {
+    Self {
+      profile: profile.clone(),
+      ..self
+    }
+  }
+
+  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+    let (instruction, inscribe, max_slippage) = self.op.into_parts();
+
+    Inscribe {
+      auth_payload: AuthOutput::DEFAULT_PAYLOAD.into(),
+      auth_position: AuthOutputPosition::Last,
+      batch: None,
+      cbor_metadata: None,
+      commit_fee_rate: inscribe.commit_fee_rate,
+      compress: false,
+      origin: inscribe.origin,
+      destination: inscribe.destination,
+      change: inscribe.change,
+      dry_run: inscribe.dry_run,
+      fee_rate: inscribe.fee_rate,
+      file: None,
+      instruction: Some(instruction),
+      json_metadata: None,
+      max_slippage,
+      metaprotocol: None,
+      no_auth_check: inscribe.no_auth_check,
+      no_backup: inscribe.no_backup,
+      no_limit: false,
+      parent: None,
+      postage: inscribe.postage,
+      psbt_out: inscribe.psbt_out,
+      reinscribe: false,
+      satpoint: None,
+      sat: None,
+      skip_validation: false,
+      subcommand: None,
+    }
+    .with_profile(&self.profile)
+    .run(wallet, options)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse_instruction_args(args: &str) -> (OrdDeFiInstruction, InscribeOptions, Option<f64>) {
+    match Arguments::try_parse_from(args.split_whitespace()) {
+      Ok(arguments) => match arguments.subcommand {
+        crate::subcommand::Subcommand::Wallet(Wallet {
+          subcommand: Subcommand::Instruction(instruction),
+          ..
+        }) => instruction.op.into_parts(),
+        subcommand => panic!("unexpected subcommand: {subcommand:?}"),
+      },
+      Err(err) => panic!("error parsing arguments: {err}"),
+    }
+  }
+
+  #[test]
+  fn mint_builds_canonical_instruction() {
+    let (instruction, inscribe, max_slippage) =
+      parse_instruction_args("ord wallet instruction mint --tick odfi --amt 1000 --fee-rate 36");
+
+    assert_eq!(
+      instruction.to_string(),
+      r#"{"p":"orddefi","op":"mint","tick":"odfi","amt":"1000"}"#
+    );
+    assert_eq!(inscribe.fee_rate, Some("36".parse().unwrap()));
+    assert!(!inscribe.dry_run);
+    assert_eq!(max_slippage, None);
+  }
+
+  #[test]
+  fn swap_builds_canonical_instruction() {
+    let (instruction, _, max_slippage) = parse_instruction_args(
+      "ord wallet instruction swap --ltick odfi --rtick odgv --spend odfi --amt 100 --fee-rate 1 \
+       --max-slippage 0.5",
+    );
+
+    assert_eq!(max_slippage, Some(0.5));
+
+    assert_eq!(
+      instruction.to_string(),
+      r#"{"p":"orddefi","op":"swap","ltick":"odfi","rtick":"odgv","spend":"odfi","amt":"100"}"#
+    );
+  }
+
+  #[test]
+  fn direct_transfer_includes_recipient() {
+    let (instruction, _, _) = parse_instruction_args(
+      "ord wallet instruction transfer --tick odgv --amt 1000 --fee-rate 1 \
+       --to bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+    );
+
+    assert_eq!(
+      instruction.to_string(),
+      r#"{"p":"orddefi","op":"transfer","tick":"odgv","amt":"1000","to":"bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"}"#
+    );
+  }
+
+  #[test]
+  fn max_slippage_is_only_accepted_for_pool_instructions() {
+    assert!(Arguments::try_parse_from(
+      "ord wallet instruction mint --tick odfi --amt 1000 --fee-rate 1 --max-slippage 1"
+        .split_whitespace()
+    )
+    .is_err());
+  }
+
+  #[test]
+  fn max_slippage_must_be_a_percentage() {
+    for max_slippage in ["-1", "100.5", "NaN", "inf"] {
+      assert!(
+        Arguments::try_parse_from(
+          format!(
+            "ord wallet instruction swap --ltick odfi --rtick odgv --spend odfi --amt 100 \
+             --fee-rate 1 --max-slippage {max_slippage}"
+          )
+          .split_whitespace()
+        )
+        .is_err(),
+        "{max_slippage}"
+      );
+    }
+
+    let (_, _, max_slippage) = parse_instruction_args(
+      "ord wallet instruction rmlp --ltick odfi --rtick odgv --amt 1 --fee-rate 1 --max-slippage 100",
+    );
+
+    assert_eq!(max_slippage, Some(100.0));
+  }
+
+  #[test]
+  fn fee_rate_may_come_from_profile() {
+    let (_, inscribe, _) =
+      parse_instruction_args("ord wallet --profile mm1 instruction mint --tick odfi --amt 1000");
+
+    assert_eq!(inscribe.fee_rate, None);
+  }
+}
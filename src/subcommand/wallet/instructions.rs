@@ -0,0 +1,227 @@
+use {
+  super::*,
+  crate::orddefi::{AbortReason, Status},
+};
+
+/// An instruction broadcast by this wallet, as recorded when its commit and
+/// reveal transactions were sent.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct Record {
+  pub(crate) commit: Txid,
+  pub(crate) inscription: InscriptionId,
+  pub(crate) instruction: OrdDeFiInstruction,
+}
+
+/// How far a recorded instruction has progressed. Instructions whose reveal
+/// transaction is no longer in the active chain, after a reorg or while still
+/// in the mempool, are pending.
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "status", content = "reason")]
+pub enum State {
+  Pending,
+  Confirmed,
+  Executed,
+  Aborted(AbortReason),
+}
+
+impl State {
+  fn new(in_active_chain: bool, status: Option<Status>) -> Self {
+    match (in_active_chain, status) {
+      (false, _) => Self::Pending,
+      (true, None) => Self::Confirmed,
+      (true, Some(Status::Executed)) => Self::Executed,
+      (true, Some(Status::Aborted(reason))) => Self::Aborted(reason),
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub commit: Txid,
+  pub height: Option<u32>,
+  pub inscription: InscriptionId,
+  pub instruction: OrdDeFiInstruction,
+  pub reveal: Txid,
+  #[serde(flatten)]
+  pub state: State,
+}
+
+pub(crate) fn run(wallet: String, options: Options) -> SubcommandResult {
+  let index = Index::open(&options)?;
+
+  ensure!(
+    index.has_orddefi_index(),
+    "`ord wallet instructions` requires index created with `--index-orddefi` flag",
+  );
+
+  index.update()?;
+
+  let mut output = Vec::new();
+
+  for record in load(&path(&wallet, &options))? {
+    let reveal = record.inscription.txid;
+
+    let receipt = index.get_orddefi_receipt(record.inscription)?;
+
+    let in_active_chain = index.is_transaction_in_active_chain(reveal)?;
+
+    output.push(Output {
+      commit: record.commit,
+      height: receipt
+        .as_ref()
+        .filter(|_| in_active_chain)
+        .map(|receipt| receipt.height),
+      inscription: record.inscription,
+      instruction: record.instruction,
+      reveal,
+      state: State::new(in_active_chain, receipt.map(|receipt| receipt.status)),
+    });
+  }
+
+  Ok(Box::new(output))
+}
+
+/// Record the instructions among the inscriptions of a broadcast reveal
+/// transaction, in inscription order, so `wallet instructions` can track them.
+pub(crate) fn record(
+  wallet: &str,
+  options: &Options,
+  commit: Txid,
+  reveal: Txid,
+  instructions: impl IntoIterator<Item = Option<OrdDeFiInstruction>>,
+) -> Result {
+  let records = instructions
+    .into_iter()
+    .enumerate()
+    .filter_map(|(index, instruction)| {
+      Some(Record {
+        commit,
+        inscription: InscriptionId {
+          txid: reveal,
+          index: u32::try_from(index).unwrap(),
+        },
+        instruction: instruction?,
+      })
+    })
+    .collect::<Vec<Record>>();
+
+  if records.is_empty() {
+    return Ok(());
+  }
+
+  append(&path(wallet, options), records)
+}
+
+fn path(wallet: &str, options: &Options) -> PathBuf {
+  options
+    .data_dir()
+    .join("instructions")
+    .join(format!("{wallet}.json"))
+}
+
+fn load(path: &Path) -> Result<Vec<Record>> {
+  if !path.exists() {
+    return Ok(Vec::new());
+  }
+
+  serde_json::from_reader(
+    File::open(path).with_context(|| format!("io error reading {}", path.display()))?,
+  )
+  .with_context(|| format!("failed to parse instruction records {}", path.display()))
+}
+
+fn append(path: &Path, records: Vec<Record>) -> Result {
+  let mut existing = load(path)?;
+
+  for record in records {
+    if !existing.contains(&record) {
+      existing.push(record);
+    }
+  }
+
+  if let Some(parent) = path.parent() {
+    fs::create_dir_all(parent)
+      .with_context(|| format!("io error creating {}", parent.display()))?;
+  }
+
+  fs::write(path, serde_json::to_string_pretty(&existing)?)
+    .with_context(|| format!("io error writing {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn mint() -> OrdDeFiInstruction {
+    r#"{"p":"orddefi","op":"mint","tick":"odfi","amt":"1000"}"#
+      .parse()
+      .unwrap()
+  }
+
+  #[test]
+  fn state() {
+    assert_eq!(State::new(false, None), State::Pending);
+    assert_eq!(State::new(false, Some(Status::Executed)), State::Pending);
+    assert_eq!(State::new(true, None), State::Confirmed);
+    assert_eq!(State::new(true, Some(Status::Executed)), State::Executed);
+    assert_eq!(
+      State::new(true, Some(Status::Aborted(AbortReason::UnknownTick))),
+      State::Aborted(AbortReason::UnknownTick)
+    );
+  }
+
+  #[test]
+  fn state_serde() {
+    assert_eq!(
+      serde_json::to_string(&State::Pending).unwrap(),
+      r#"{"status":"pending"}"#
+    );
+    assert_eq!(
+      serde_json::to_string(&State::Aborted(AbortReason::BadAuth)).unwrap(),
+      r#"{"status":"aborted","reason":"bad_auth"}"#
+    );
+  }
+
+  #[test]
+  fn records_are_appended_once() {
+    let tempdir = TempDir::new().unwrap();
+    let path = tempdir.path().join("instructions").join("ord.json");
+
+    assert_eq!(load(&path).unwrap(), Vec::new());
+
+    let record = Record {
+      commit: txid(1),
+      inscription: inscription_id(2),
+      instruction: mint(),
+    };
+
+    append(&path, vec![record.clone()]).unwrap();
+    append(&path, vec![record.clone()]).unwrap();
+
+    assert_eq!(load(&path).unwrap(), vec![record]);
+  }
+
+  #[test]
+  fn record_skips_inscriptions_which_are_not_instructions() {
+    let tempdir = TempDir::new().unwrap();
+
+    let options = Options {
+      data_dir: tempdir.path().into(),
+      ..Default::default()
+    };
+
+    record("ord", &options, txid(1), txid(2), vec![None, Some(mint())]).unwrap();
+
+    assert_eq!(
+      load(&path("ord", &options)).unwrap(),
+      vec![Record {
+        commit: txid(1),
+        inscription: InscriptionId {
+          txid: txid(2),
+          index: 1,
+        },
+        instruction: mint(),
+      }]
+    );
+  }
+}
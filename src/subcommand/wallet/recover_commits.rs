@@ -0,0 +1,303 @@
+use {
+  super::*,
+  bitcoin::{
+    key::{TweakedPublicKey, XOnlyPublicKey},
+    secp256k1::{constants::SCHNORR_SIGNATURE_SIZE, PublicKey},
+  },
+};
+
+#[derive(Debug, Parser)]
+pub(crate) struct RecoverCommits {
+  #[arg(long, help = "Send recovered outputs to <DESTINATION>.")]
+  pub(crate) destination: Address<NetworkUnchecked>,
+  #[arg(
+    long,
+    help = "List recoverable outputs without signing or broadcasting."
+  )]
+  pub(crate) dry_run: bool,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
+  pub(crate) fee_rate: FeeRate,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct RecoverableOutput {
+  pub address: Address<NetworkUnchecked>,
+  pub amount: u64,
+  pub output: OutPoint,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Output {
+  pub fee: u64,
+  pub outputs: Vec<RecoverableOutput>,
+  pub recovered: u64,
+  pub transaction: Option<Txid>,
+}
+
+impl RecoverCommits {
+  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+    let chain = options.chain();
+
+    let destination = self.destination.require_network(chain.network())?;
+
+    let client = bitcoin_rpc_client_for_wallet_command(wallet, &options)?;
+
+    let addresses = client
+      .list_descriptors(None)?
+      .descriptors
+      .iter()
+      .filter_map(|descriptor| Self::recovery_address(&descriptor.desc, chain.network()))
+      .collect::<Vec<Address>>();
+
+    let outputs = if addresses.is_empty() {
+      Vec::new()
+    } else {
+      client
+        .list_unspent(
+          None,
+          None,
+          Some(&addresses.iter().collect::<Vec<&Address>>()),
+          None,
+          None,
+        )?
+        .into_iter()
+        .map(|utxo| {
+          let output = OutPoint::new(utxo.txid, utxo.vout);
+
+          Ok(RecoverableOutput {
+            address: utxo
+              .address
+              .ok_or_else(|| anyhow!("recovery output {output} has no address"))?,
+            amount: utxo.amount.to_sat(),
+            output,
+          })
+        })
+        .collect::<Result<Vec<RecoverableOutput>>>()?
+    };
+
+    if outputs.is_empty() {
+      if self.dry_run {
+        return Ok(Box::new(Output {
+          fee: 0,
+          outputs,
+          recovered: 0,
+          transaction: None,
+        }));
+      }
+
+      bail!("wallet contains no unspent commit outputs to recover");
+    }
+
+    let (unsigned_transaction, fee) =
+      Self::build_sweep_transaction(&outputs, &destination, self.fee_rate)?;
+
+    let recovered = unsigned_transaction.output[0].value;
+
+    if self.dry_run {
+      return Ok(Box::new(Output {
+        fee,
+        outputs,
+        recovered,
+        transaction: None,
+      }));
+    }
+
+    let signed_transaction =
+      client.sign_raw_transaction_with_wallet(&unsigned_transaction, None, None)?;
+
+    ensure!(
+      signed_transaction.complete,
+      "failed to sign recovery transaction: {:?}",
+      signed_transaction.errors
+    );
+
+    let transaction = client.send_raw_transaction(&signed_transaction.hex)?;
+
+    Ok(Box::new(Output {
+      fee,
+      outputs,
+      recovered,
+      transaction: Some(transaction),
+    }))
+  }
+
+  /// `backup_recovery_key` imports each commit transaction recovery key as a
+  /// `rawtr` descriptor, whose output key is used as is, without a tweak.
+  fn recovery_address(descriptor: &str, network: Network) -> Option<Address> {
+    let key = descriptor.strip_prefix("rawtr(")?.split(')').next()?;
+
+    let key = hex::decode(key).ok()?;
+
+    let key = match key.len() {
+      32 => XOnlyPublicKey::from_slice(&key).ok()?,
+      33 => PublicKey::from_slice(&key).ok()?.x_only_public_key().0,
+      _ => return None,
+    };
+
+    Some(Address::p2tr_tweaked(
+      TweakedPublicKey::dangerous_assume_tweaked(key),
+      network,
+    ))
+  }
+
+  /// Spend every output with a key path signature, sending all but the fee
+  /// to `destination`.
+  fn build_sweep_transaction(
+    outputs: &[RecoverableOutput],
+    destination: &Address,
+    fee_rate: FeeRate,
+  ) -> Result<(Transaction, u64)> {
+    let mut transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: outputs
+        .iter()
+        .map(|output| TxIn {
+          previous_output: output.output,
+          script_sig: ScriptBuf::new(),
+          witness: Witness::from_slice(&[&[0; SCHNORR_SIGNATURE_SIZE]]),
+          sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+        })
+        .collect(),
+      output: vec![TxOut {
+        script_pubkey: destination.script_pubkey(),
+        value: 0,
+      }],
+    };
+
+    let fee = fee_rate.fee(transaction.vsize()).to_sat();
+
+    let total = outputs.iter().map(|output| output.amount).sum::<u64>();
+
+    transaction.output[0].value = total
+      .checked_sub(fee)
+      .filter(|value| *value >= destination.script_pubkey().dust_value().to_sat())
+      .ok_or_else(|| {
+        anyhow!("commit outputs worth {total} sat are too small to pay recovery fee of {fee} sat")
+      })?;
+
+    for txin in &mut transaction.input {
+      txin.witness = Witness::new();
+    }
+
+    Ok((transaction, fee))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, bitcoin::secp256k1::Secp256k1};
+
+  fn recovery_key() -> (PublicKey, Address) {
+    let secp256k1 = Secp256k1::new();
+    let public_key = PublicKey::from_secret_key(
+      &secp256k1,
+      &bitcoin::secp256k1::SecretKey::from_slice(&[1; 32]).unwrap(),
+    );
+    let address = Address::p2tr_tweaked(
+      TweakedPublicKey::dangerous_assume_tweaked(public_key.x_only_public_key().0),
+      Network::Bitcoin,
+    );
+    (public_key, address)
+  }
+
+  fn recoverable_output(n: u64, amount: u64) -> RecoverableOutput {
+    RecoverableOutput {
+      address: recovery_key().1.to_string().parse().unwrap(),
+      amount,
+      output: outpoint(n),
+    }
+  }
+
+  #[test]
+  fn recovery_address_from_x_only_key() {
+    let (public_key, address) = recovery_key();
+
+    assert_eq!(
+      RecoverCommits::recovery_address(
+        &format!("rawtr({})#00000000", public_key.x_only_public_key().0),
+        Network::Bitcoin
+      ),
+      Some(address),
+    );
+  }
+
+  #[test]
+  fn recovery_address_from_compressed_key() {
+    let (public_key, address) = recovery_key();
+
+    assert_eq!(
+      RecoverCommits::recovery_address(&format!("rawtr({public_key})#00000000"), Network::Bitcoin),
+      Some(address),
+    );
+  }
+
+  #[test]
+  fn other_descriptors_are_not_recovery_addresses() {
+    let (public_key, _address) = recovery_key();
+
+    assert_eq!(
+      RecoverCommits::recovery_address(
+        &format!("tr({})#00000000", public_key.x_only_public_key().0),
+        Network::Bitcoin
+      ),
+      None,
+    );
+
+    assert_eq!(
+      RecoverCommits::recovery_address("rawtr(foo)#00000000", Network::Bitcoin),
+      None,
+    );
+  }
+
+  #[test]
+  fn sweep_spends_all_outputs_to_destination() {
+    let outputs = [recoverable_output(1, 10_000), recoverable_output(2, 20_000)];
+
+    let (transaction, fee) = RecoverCommits::build_sweep_transaction(
+      &outputs,
+      &recipient(),
+      FeeRate::try_from(2.0).unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(
+      transaction
+        .input
+        .iter()
+        .map(|txin| txin.previous_output)
+        .collect::<Vec<OutPoint>>(),
+      [outpoint(1), outpoint(2)],
+    );
+    assert!(transaction.input.iter().all(|txin| txin.witness.is_empty()));
+    assert_eq!(transaction.output.len(), 1);
+    assert_eq!(
+      transaction.output[0].script_pubkey,
+      recipient().script_pubkey()
+    );
+    assert_eq!(transaction.output[0].value, 30_000 - fee);
+
+    let mut signed = transaction.clone();
+    for txin in &mut signed.input {
+      txin.witness = Witness::from_slice(&[&[0; SCHNORR_SIGNATURE_SIZE]]);
+    }
+    assert_eq!(
+      fee,
+      FeeRate::try_from(2.0).unwrap().fee(signed.vsize()).to_sat()
+    );
+  }
+
+  #[test]
+  fn sweep_fails_if_outputs_cannot_pay_fee() {
+    assert_eq!(
+      RecoverCommits::build_sweep_transaction(
+        &[recoverable_output(1, 100)],
+        &recipient(),
+        FeeRate::try_from(1.0).unwrap(),
+      )
+      .unwrap_err()
+      .to_string(),
+      "commit outputs worth 100 sat are too small to pay recovery fee of 99 sat",
+    );
+  }
+}
@@ -0,0 +1,291 @@
+use {
+  super::*,
+  bitcoin::{
+    secp256k1::{constants::SCHNORR_SIGNATURE_SIZE, Message},
+    sighash::{Prevouts, SighashCache, TapSighashType},
+    taproot::{LeafVersion, Signature, TapLeafHash},
+  },
+  commit_gen_addr::{CommitGenAddr, Commitment},
+};
+
+#[derive(Serialize, Deserialize)]
+pub struct Output {
+  pub commit: OutPoint,
+  pub inscription: InscriptionId,
+  pub reveal: Txid,
+  pub reveal_fee: u64,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct RevealFromCommit {
+  #[arg(long, help = "Spend commit output <COMMIT_OUTPOINT>.")]
+  pub(crate) commit_outpoint: OutPoint,
+  #[arg(long, help = "Send inscription to <DESTINATION>.")]
+  pub(crate) destination: Address<NetworkUnchecked>,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
+  pub(crate) fee_rate: FeeRate,
+  #[arg(
+    long,
+    help = "Inscribe contents of <FILE>. Must be the file passed to `commit-gen-addr`."
+  )]
+  pub(crate) file: PathBuf,
+  #[arg(
+    long,
+    help = "Use <PRV> to derive private key. Must be the key passed to `commit-gen-addr`."
+  )]
+  pub(crate) prv: String,
+}
+
+impl RevealFromCommit {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let chain = options.chain();
+
+    let commitment = CommitGenAddr::commitment(chain, &self.prv, Some(&self.file))?;
+
+    let destination = self.destination.require_network(chain.network())?;
+
+    let client = options.bitcoin_rpc_client(None)?;
+
+    let outpoint = self.commit_outpoint;
+
+    let tx_out = client
+      .get_tx_out(&outpoint.txid, outpoint.vout, Some(true))?
+      .ok_or_else(|| anyhow!("commit output {outpoint} is not unspent"))?;
+
+    let (reveal_tx, reveal_fee) = Self::build_reveal_transaction(
+      &commitment,
+      outpoint,
+      TxOut {
+        value: tx_out.value.to_sat(),
+        script_pubkey: tx_out.script_pub_key.script()?,
+      },
+      &destination,
+      self.fee_rate,
+    )?;
+
+    let reveal = client.send_raw_transaction(&reveal_tx)?;
+
+    Ok(Box::new(Output {
+      commit: outpoint,
+      inscription: InscriptionId {
+        txid: reveal,
+        index: 0,
+      },
+      reveal,
+      reveal_fee: reveal_fee.to_sat(),
+    }))
+  }
+
+  /// Spend `commit_output` through the reveal script leaf, sending everything
+  /// but the fee to `destination`.
+  fn build_reveal_transaction(
+    commitment: &Commitment,
+    commit_outpoint: OutPoint,
+    commit_output: TxOut,
+    destination: &Address,
+    fee_rate: FeeRate,
+  ) -> Result<(Transaction, Amount)> {
+    ensure!(
+      commit_output.script_pubkey == commitment.address.script_pubkey(),
+      "commit output {commit_outpoint} does not pay to commit address {}",
+      commitment.address,
+    );
+
+    let mut reveal_tx = Transaction {
+      input: vec![TxIn {
+        previous_output: commit_outpoint,
+        script_sig: ScriptBuf::new(),
+        witness: Witness::new(),
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+      }],
+      output: vec![TxOut {
+        script_pubkey: destination.script_pubkey(),
+        value: 0,
+      }],
+      lock_time: LockTime::ZERO,
+      version: 2,
+    };
+
+    let fee = {
+      let mut reveal_tx = reveal_tx.clone();
+
+      reveal_tx.input[0].witness = Self::witness(
+        commitment,
+        Signature::from_slice(&[0; SCHNORR_SIGNATURE_SIZE]).unwrap(),
+      );
+
+      fee_rate.fee(reveal_tx.vsize())
+    };
+
+    let value = commit_output
+      .value
+      .checked_sub(fee.to_sat())
+      .filter(|value| *value >= reveal_tx.output[0].script_pubkey.dust_value().to_sat())
+      .ok_or_else(|| {
+        anyhow!(
+          "commit output {commit_outpoint} value of {} sat is too small to pay reveal fee of {} sat",
+          commit_output.value,
+          fee.to_sat(),
+        )
+      })?;
+
+    reveal_tx.output[0].value = value;
+
+    let sighash = SighashCache::new(&reveal_tx)
+      .taproot_script_spend_signature_hash(
+        0,
+        &Prevouts::All(&[commit_output]),
+        TapLeafHash::from_script(&commitment.reveal_script, LeafVersion::TapScript),
+        TapSighashType::Default,
+      )
+      .expect("signature hash should compute");
+
+    let sig = commitment.secp256k1.sign_schnorr(
+      &Message::from_slice(sighash.as_ref()).expect("should be cryptographically secure hash"),
+      &commitment.key_pair,
+    );
+
+    reveal_tx.input[0].witness = Self::witness(
+      commitment,
+      Signature {
+        sig,
+        hash_ty: TapSighashType::Default,
+      },
+    );
+
+    Ok((reveal_tx, fee))
+  }
+
+  fn witness(commitment: &Commitment, signature: Signature) -> Witness {
+    let mut witness = Witness::new();
+    witness.push(signature.to_vec());
+    witness.push(&commitment.reveal_script);
+    witness.push(commitment.control_block.serialize());
+    witness
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, tempfile::TempDir};
+
+  const PRV: &str = "0000000000000000000000000000000000000000000000000000000000000001";
+
+  fn commitment() -> (TempDir, Commitment) {
+    let tempdir = TempDir::new().unwrap();
+    let file = tempdir.path().join("instruction.txt");
+    fs::write(
+      &file,
+      r#"{"p":"orddefi","op":"mint","tick":"odfi","amt":"1000"}"#,
+    )
+    .unwrap();
+
+    let commitment = CommitGenAddr::commitment(Chain::Mainnet, PRV, Some(&file)).unwrap();
+
+    (tempdir, commitment)
+  }
+
+  #[test]
+  fn commitment_matches_commit_address() {
+    let (_tempdir, commitment) = commitment();
+
+    assert_eq!(
+      commitment.internal_key,
+      commitment.control_block.internal_key
+    );
+
+    let output_key = match commitment.address.payload {
+      bitcoin::address::Payload::WitnessProgram(program) => {
+        bitcoin::secp256k1::XOnlyPublicKey::from_slice(program.program().as_bytes()).unwrap()
+      }
+      payload => panic!("unexpected payload: {payload:?}"),
+    };
+
+    assert!(commitment.control_block.verify_taproot_commitment(
+      &commitment.secp256k1,
+      output_key,
+      &commitment.reveal_script,
+    ));
+  }
+
+  #[test]
+  fn reveal_spends_commit_output_to_destination() {
+    let (_tempdir, commitment) = commitment();
+
+    let (reveal_tx, fee) = RevealFromCommit::build_reveal_transaction(
+      &commitment,
+      outpoint(1),
+      TxOut {
+        value: 10_000,
+        script_pubkey: commitment.address.script_pubkey(),
+      },
+      &recipient(),
+      FeeRate::try_from(1.0).unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(reveal_tx.input[0].previous_output, outpoint(1));
+    assert_eq!(
+      reveal_tx.input[0].witness.tapscript(),
+      Some(commitment.reveal_script.as_script())
+    );
+    assert_eq!(reveal_tx.output.len(), 1);
+    assert_eq!(
+      reveal_tx.output[0].script_pubkey,
+      recipient().script_pubkey()
+    );
+    assert_eq!(reveal_tx.output[0].value, 10_000 - fee.to_sat());
+    assert_eq!(fee, FeeRate::try_from(1.0).unwrap().fee(reveal_tx.vsize()));
+  }
+
+  #[test]
+  fn reveal_requires_output_paying_to_commit_address() {
+    let (_tempdir, commitment) = commitment();
+
+    assert_eq!(
+      RevealFromCommit::build_reveal_transaction(
+        &commitment,
+        outpoint(1),
+        TxOut {
+          value: 10_000,
+          script_pubkey: recipient().script_pubkey(),
+        },
+        &recipient(),
+        FeeRate::try_from(1.0).unwrap(),
+      )
+      .unwrap_err()
+      .to_string(),
+      format!(
+        "commit output {} does not pay to commit address {}",
+        outpoint(1),
+        commitment.address
+      ),
+    );
+  }
+
+  #[test]
+  fn reveal_requires_commit_output_to_cover_fee() {
+    let (_tempdir, commitment) = commitment();
+
+    let err = RevealFromCommit::build_reveal_transaction(
+      &commitment,
+      outpoint(1),
+      TxOut {
+        value: 100,
+        script_pubkey: commitment.address.script_pubkey(),
+      },
+      &recipient(),
+      FeeRate::try_from(1.0).unwrap(),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert!(
+      err.starts_with(&format!(
+        "commit output {} value of 100 sat is too small to pay reveal fee of",
+        outpoint(1)
+      )),
+      "{err}",
+    );
+  }
+}
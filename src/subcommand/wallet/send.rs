@@ -4,8 +4,11 @@ use {super::*, crate::subcommand::wallet::transaction_builder::Target};
 pub(crate) struct Send {
   address: Address<NetworkUnchecked>,
   outgoing: Outgoing,
-  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB")]
-  fee_rate: FeeRate,
+  #[arg(
+    long,
+    help = "Use fee rate of <FEE_RATE> sats/vB, or `auto:<BLOCKS>` to estimate a fee rate that confirms within <BLOCKS> blocks"
+  )]
+  fee_rate: FeeRateArgument,
   #[arg(
     long,
     help = "Target amount of postage to include with sent inscriptions. Default `10000sat`"
@@ -15,6 +18,7 @@ pub(crate) struct Send {
 
 #[derive(Serialize, Deserialize)]
 pub struct Output {
+  pub fee_rate: f64,
   pub transaction: Txid,
 }
 
@@ -33,6 +37,8 @@ impl Send {
 
     let chain = options.chain();
 
+    let fee_rate = self.fee_rate.resolve(&client, &options)?;
+
     let unspent_outputs = get_unspent_outputs(&client, &index)?;
 
     let locked_outputs = get_locked_outputs(&client)?;
@@ -45,8 +51,11 @@ impl Send {
     let satpoint = match self.outgoing {
       Outgoing::Amount(amount) => {
         Self::lock_non_cardinal_outputs(&client, &inscriptions, &runic_outputs, unspent_outputs)?;
-        let transaction = Self::send_amount(&client, amount, address, self.fee_rate)?;
-        return Ok(Box::new(Output { transaction }));
+        let transaction = Self::send_amount(&client, amount, address, fee_rate)?;
+        return Ok(Box::new(Output {
+          fee_rate: fee_rate.n(),
+          transaction,
+        }));
       }
       Outgoing::InscriptionId(id) => index
         .get_inscription_satpoint_by_id(id)?
@@ -57,14 +66,17 @@ impl Send {
           chain,
           &client,
           decimal,
-          self.fee_rate,
+          fee_rate,
           &index,
           inscriptions,
           rune,
           runic_outputs,
           unspent_outputs,
         )?;
-        return Ok(Box::new(Output { transaction }));
+        return Ok(Box::new(Output {
+          fee_rate: fee_rate.n(),
+          transaction,
+        }));
       }
       Outgoing::SatPoint(satpoint) => {
         for inscription_satpoint in inscriptions.keys() {
@@ -98,7 +110,7 @@ impl Send {
       runic_outputs,
       address.clone(),
       change,
-      self.fee_rate,
+      fee_rate,
       postage,
     )
     .build_transaction()?;
@@ -109,7 +121,10 @@ impl Send {
 
     let txid = client.send_raw_transaction(&signed_tx)?;
 
-    Ok(Box::new(Output { transaction: txid }))
+    Ok(Box::new(Output {
+      fee_rate: fee_rate.n(),
+      transaction: txid,
+    }))
   }
 
   fn lock_non_cardinal_outputs(
@@ -20,6 +20,16 @@
 //! `Target::Value(Amount)` ensures that the outgoing value is exactly the
 //! requested amount,
 //!
+//! `TransactionBuilder::with_auth_output` adds an OrdDeFi authentication
+//! `OP_RETURN` output to the built transaction. Only inscription commit
+//! transactions should carry it, since it marks the transaction's inputs as
+//! authenticating the instruction being inscribed.
+//!
+//! `TransactionBuilder::with_input_scripts` records the script pubkeys of
+//! UTXOs that are not taproot key path spends, such as those of an
+//! inscription origin address, so that fees are estimated for the inputs
+//! actually spent.
+//!
 //! Internally, `TransactionBuilder` calls multiple methods that implement
 //! transformations responsible for individual concerns, such as ensuring that
 //! the transaction fee is paid, and that outgoing outputs aren't too large.
@@ -33,8 +43,9 @@
 
 use {
   super::*,
+  bitcoin::{blockdata::script::Builder, secp256k1::constants::PUBLIC_KEY_SIZE},
+  clap::ValueEnum,
   std::cmp::{max, min},
-  bitcoin::blockdata::script::Builder,
 };
 
 #[derive(Debug, PartialEq)]
@@ -55,6 +66,121 @@ pub enum Error {
   ValueOverflow,
 }
 
+#[derive(Debug, PartialEq, Clone)]
+pub struct AuthOutput {
+  pub payload: Vec<u8>,
+  pub position: AuthOutputPosition,
+}
+
+impl AuthOutput {
+  pub const DEFAULT_PAYLOAD: &'static str = crate::orddefi::AUTH_PAYLOAD;
+
+  /// Bitcoin Core only relays `OP_RETURN` outputs of up to 83 bytes, which
+  /// leaves 80 bytes of data after the opcode and push.
+  pub const MAX_PAYLOAD_LEN: usize = 80;
+
+  pub(crate) fn parse_payload(payload: &str) -> anyhow::Result<String> {
+    ensure!(
+      payload.len() <= Self::MAX_PAYLOAD_LEN,
+      "auth payload is {} bytes, but `OP_RETURN` outputs with more than {} bytes are not relayed",
+      payload.len(),
+      Self::MAX_PAYLOAD_LEN,
+    );
+
+    Ok(payload.into())
+  }
+
+  fn tx_out(&self) -> TxOut {
+    let mut data = script::PushBytesBuf::new();
+    data
+      .extend_from_slice(&self.payload)
+      .expect("auth payload should fit in a push");
+
+    TxOut {
+      value: 0,
+      script_pubkey: Builder::new()
+        .push_opcode(opcodes::all::OP_RETURN)
+        .push_slice(data)
+        .into_script(),
+    }
+  }
+}
+
+#[derive(Default, ValueEnum, Debug, PartialEq, Copy, Clone)]
+pub enum AuthOutputPosition {
+  First,
+  #[default]
+  Last,
+}
+
+/// Script types whose signed input sizes can be estimated. Scripts of any other
+/// type are estimated as taproot key path spends. P2SH outputs are assumed to
+/// wrap P2WPKH, and P2PKH outputs to be spent with compressed public keys.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub(crate) enum InputType {
+  P2pkh,
+  P2shP2wpkh,
+  P2tr,
+  P2wpkh,
+}
+
+impl InputType {
+  /// Maximum size of a DER encoded ECDSA signature with sighash type
+  const ECDSA_SIGNATURE_SIZE: usize = 72;
+  /// Size of the P2WPKH redeem script of a P2SH-P2WPKH output
+  const P2WPKH_REDEEM_SCRIPT_SIZE: usize = 22;
+  const SCHNORR_SIGNATURE_SIZE: usize = 64;
+
+  pub(crate) fn from_script(script: &Script) -> Self {
+    if script.is_v0_p2wpkh() {
+      Self::P2wpkh
+    } else if script.is_p2sh() {
+      Self::P2shP2wpkh
+    } else if script.is_p2pkh() {
+      Self::P2pkh
+    } else {
+      Self::P2tr
+    }
+  }
+
+  /// Virtual size of a signed input of this type in a segwit transaction,
+  /// rounded up
+  pub(crate) fn vbytes(self) -> usize {
+    match self {
+      Self::P2pkh => 149,
+      Self::P2shP2wpkh => 91,
+      Self::P2tr => 58,
+      Self::P2wpkh => 68,
+    }
+  }
+
+  /// Script sig of the same size as that of a signed input
+  fn dummy_script_sig(self) -> ScriptBuf {
+    match self {
+      Self::P2pkh => Builder::new()
+        .push_slice([0; Self::ECDSA_SIGNATURE_SIZE])
+        .push_slice([0; PUBLIC_KEY_SIZE])
+        .into_script(),
+      Self::P2shP2wpkh => Builder::new()
+        .push_slice([0; Self::P2WPKH_REDEEM_SCRIPT_SIZE])
+        .into_script(),
+      Self::P2tr | Self::P2wpkh => ScriptBuf::new(),
+    }
+  }
+
+  /// Witness of the same size as that of a signed input
+  fn dummy_witness(self) -> Witness {
+    match self {
+      Self::P2pkh => Witness::new(),
+      Self::P2tr => Witness::from_slice(&[&[0; Self::SCHNORR_SIGNATURE_SIZE]]),
+      Self::P2shP2wpkh | Self::P2wpkh => Witness::from_slice(&[
+        &[0; Self::ECDSA_SIGNATURE_SIZE][..],
+        &[0; PUBLIC_KEY_SIZE][..],
+      ]),
+    }
+  }
+}
+
 #[derive(Debug, PartialEq)]
 pub enum Target {
   Value(Amount),
@@ -94,8 +220,10 @@ impl std::error::Error for Error {}
 #[derive(Debug, PartialEq)]
 pub struct TransactionBuilder {
   amounts: BTreeMap<OutPoint, Amount>,
+  auth_output: Option<AuthOutput>,
   change_addresses: Address,
   fee_rate: FeeRate,
+  input_scripts: BTreeMap<OutPoint, ScriptBuf>,
   inputs: Vec<OutPoint>,
   inscriptions: BTreeMap<SatPoint, InscriptionId>,
   locked_utxos: BTreeSet<OutPoint>,
@@ -111,9 +239,7 @@ pub struct TransactionBuilder {
 type Result<T> = std::result::Result<T, Error>;
 
 impl TransactionBuilder {
-  const ADDITIONAL_INPUT_VBYTES: usize = 58;
   const ADDITIONAL_OUTPUT_VBYTES: usize = 43;
-  const SCHNORR_SIGNATURE_SIZE: usize = 64;
   pub(crate) const MAX_POSTAGE: Amount = Amount::from_sat(2 * 10_000);
 
   pub fn new(
@@ -130,8 +256,10 @@ impl TransactionBuilder {
     Self {
       utxos: amounts.keys().cloned().collect(),
       amounts,
+      auth_output: None,
       change_addresses: change.clone(),
       fee_rate,
+      input_scripts: BTreeMap::new(),
       inputs: Vec::new(),
       inscriptions,
       locked_utxos,
@@ -144,6 +272,20 @@ impl TransactionBuilder {
     }
   }
 
+  pub fn with_auth_output(self, auth_output: AuthOutput) -> Self {
+    Self {
+      auth_output: Some(auth_output),
+      ..self
+    }
+  }
+
+  pub fn with_input_scripts(self, input_scripts: BTreeMap<OutPoint, ScriptBuf>) -> Self {
+    Self {
+      input_scripts,
+      ..self
+    }
+  }
+
   pub fn build_transaction(self) -> Result<Transaction> {
     match self.target {
       Target::Value(output_value) | Target::ExactPostage(output_value) => {
@@ -287,15 +429,25 @@ impl TransactionBuilder {
       .ok_or(Error::ValueOverflow)?;
 
     if let Some(mut deficit) = total.checked_sub(self.outputs.last().unwrap().1) {
-      while deficit > Amount::ZERO {
-        let additional_fee = self.fee_rate.fee(Self::ADDITIONAL_INPUT_VBYTES);
+      let additional_input_vbytes = self
+        .input_scripts
+        .values()
+        .map(|script| InputType::from_script(script).vbytes())
+        .chain([InputType::P2tr.vbytes()])
+        .max()
+        .unwrap();
 
+      while deficit > Amount::ZERO {
         let needed = deficit
-          .checked_add(additional_fee)
+          .checked_add(self.fee_rate.fee(additional_input_vbytes))
           .ok_or(Error::ValueOverflow)?;
 
         let (utxo, value) = self.select_cardinal_utxo(needed, false)?;
 
+        let additional_fee = self
+          .fee_rate
+          .fee(Self::input_type(&self.input_scripts, utxo).vbytes());
+
         let benefit = value
           .checked_sub(additional_fee)
           .ok_or(Error::NotEnoughCardinalUtxos)?;
@@ -401,72 +553,58 @@ impl TransactionBuilder {
     self
   }
 
-  /// Estimate the size in virtual bytes of the transaction under construction.
-  /// We initialize wallets with taproot descriptors only, so we know that all
-  /// inputs are taproot key path spends, which allows us to know that witnesses
-  /// will all consist of single Schnorr signatures.
+  /// Estimate the size in virtual bytes of the transaction under construction,
+  /// including the auth output.
   fn estimate_vbytes(&self) -> usize {
-    Self::estimate_vbytes_with(
-      self.inputs.len(),
-      self
-        .outputs
-        .iter()
-        .map(|(address, _amount)| address)
-        .cloned()
-        .collect(),
-    )
+    Self::estimate_vsize(&self.unsigned_transaction(), &self.input_scripts)
   }
 
-  fn estimate_vbytes_with(inputs: usize, outputs: Vec<Address>) -> usize {
-    Transaction {
-      version: 2,
-      lock_time: LockTime::ZERO,
-      input: (0..inputs)
-        .map(|_| TxIn {
-          previous_output: OutPoint::null(),
-          script_sig: ScriptBuf::new(),
-          sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
-          witness: Witness::from_slice(&[&[0; Self::SCHNORR_SIGNATURE_SIZE]]),
-        })
-        .collect(),
-      output: outputs
-        .into_iter()
-        .map(|address| TxOut {
-          value: 0,
-          script_pubkey: address.script_pubkey(),
-        })
-        .collect(),
+  /// Estimate the size in virtual bytes of `transaction` once its unsigned
+  /// inputs are signed. We initialize wallets with taproot descriptors only, so
+  /// inputs missing from `input_scripts` are assumed to be taproot key path
+  /// spends, whose witnesses consist of single Schnorr signatures.
+  pub(crate) fn estimate_vsize(
+    transaction: &Transaction,
+    input_scripts: &BTreeMap<OutPoint, ScriptBuf>,
+  ) -> usize {
+    let mut transaction = transaction.clone();
+
+    for tx_in in &mut transaction.input {
+      if tx_in.script_sig.is_empty() && tx_in.witness.is_empty() {
+        let input_type = Self::input_type(input_scripts, tx_in.previous_output);
+        tx_in.script_sig = input_type.dummy_script_sig();
+        tx_in.witness = input_type.dummy_witness();
+      }
     }
-    .vsize()
+
+    transaction.vsize()
   }
 
-  fn estimate_fee(&self) -> Amount {
-    self.fee_rate.fee(self.estimate_vbytes())
+  fn input_type(input_scripts: &BTreeMap<OutPoint, ScriptBuf>, outpoint: OutPoint) -> InputType {
+    input_scripts
+      .get(&outpoint)
+      .map(|script| InputType::from_script(script))
+      .unwrap_or(InputType::P2tr)
   }
 
-  fn build(self) -> Result<Transaction> {
-    let recipient = self.recipient.script_pubkey();
+  fn unsigned_transaction(&self) -> Transaction {
     let mut outputs: Vec<TxOut> = self
-        .outputs
-        .iter()
-        .map(|(address, amount)| TxOut {
-          value: amount.to_sat(),
-          script_pubkey: address.script_pubkey(),
-        })
-        .collect();
-    // append OrdDeFi auth OpReturn
-    let data = b"orddefi:auth";
-    let op_return_script = Builder::new()
-        .push_opcode(opcodes::all::OP_RETURN)
-        .push_slice(data)
-        .into_script();
-    let op_return_output = TxOut {
-      value: 0,
-      script_pubkey: op_return_script,
-    };
-    outputs.push(op_return_output);
+      .outputs
+      .iter()
+      .map(|(address, amount)| TxOut {
+        value: amount.to_sat(),
+        script_pubkey: address.script_pubkey(),
+      })
+      .collect();
+
+    if let Some(auth_output) = &self.auth_output {
+      match auth_output.position {
+        AuthOutputPosition::First => outputs.insert(0, auth_output.tx_out()),
+        AuthOutputPosition::Last => outputs.push(auth_output.tx_out()),
+      }
+    }
 
-    let transaction = Transaction {
+    Transaction {
       version: 2,
       lock_time: LockTime::ZERO,
       input: self
@@ -480,7 +618,27 @@ impl TransactionBuilder {
         })
         .collect(),
       output: outputs,
-    };
+    }
+  }
+
+  fn estimate_fee(&self) -> Amount {
+    self.fee_rate.fee(self.estimate_vbytes())
+  }
+
+  fn build(self) -> Result<Transaction> {
+    let recipient = self.recipient.script_pubkey();
+
+    let transaction = self.unsigned_transaction();
+
+    assert_eq!(
+      transaction
+        .output
+        .iter()
+        .filter(|tx_out| tx_out.script_pubkey.is_op_return())
+        .count(),
+      usize::from(self.auth_output.is_some()),
+      "invariant: only requested auth output is added"
+    );
 
     assert_eq!(
       self
@@ -565,16 +723,14 @@ impl TransactionBuilder {
       actual_fee -= Amount::from_sat(output.value);
     }
 
-    let mut modified_tx = transaction.clone();
-    for input in &mut modified_tx.input {
-      input.witness = Witness::from_slice(&[&[0; 64]]);
-    }
-    let expected_fee = self.fee_rate.fee(modified_tx.vsize());
+    let expected_fee = self
+      .fee_rate
+      .fee(Self::estimate_vsize(&transaction, &self.input_scripts));
 
-    // assert_eq!(
-    //   actual_fee, expected_fee,
-    //   "invariant: fee estimation is correct",
-    // );
+    assert_eq!(
+      actual_fee, expected_fee,
+      "invariant: fee estimation is correct",
+    );
 
     for tx_out in &transaction.output {
       assert!(
@@ -673,3 +829,252 @@ impl TransactionBuilder {
     Ok((utxo, value))
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    bitcoin::{PubkeyHash, ScriptHash, WPubkeyHash},
+  };
+
+  fn builder(target: Target) -> TransactionBuilder {
+    TransactionBuilder::new(
+      satpoint(1, 0),
+      BTreeMap::new(),
+      [
+        (outpoint(1), Amount::from_sat(5_000)),
+        (outpoint(2), Amount::from_sat(49 * COIN_VALUE)),
+      ]
+      .into_iter()
+      .collect(),
+      BTreeSet::new(),
+      BTreeSet::new(),
+      recipient(),
+      change(0),
+      FeeRate::try_from(1.0).unwrap(),
+      target,
+    )
+  }
+
+  fn auth_output(position: AuthOutputPosition) -> AuthOutput {
+    AuthOutput {
+      payload: AuthOutput::DEFAULT_PAYLOAD.into(),
+      position,
+    }
+  }
+
+  fn is_auth_output(tx_out: &TxOut, payload: &[u8]) -> bool {
+    tx_out.value == 0
+      && tx_out.script_pubkey.is_op_return()
+      && tx_out.script_pubkey.as_bytes()[2..] == *payload
+  }
+
+  #[test]
+  fn send_transactions_have_no_auth_output() {
+    for target in [
+      Target::Postage,
+      Target::ExactPostage(Amount::from_sat(5_000)),
+      Target::Value(Amount::from_sat(10_000)),
+    ] {
+      let transaction = builder(target).build_transaction().unwrap();
+
+      assert!(
+        transaction
+          .output
+          .iter()
+          .all(|tx_out| !tx_out.script_pubkey.is_op_return()),
+        "{transaction:?}"
+      );
+    }
+  }
+
+  #[test]
+  fn auth_output_is_added_last_by_default() {
+    let transaction = builder(Target::Value(Amount::from_sat(10_000)))
+      .with_auth_output(auth_output(AuthOutputPosition::default()))
+      .build_transaction()
+      .unwrap();
+
+    assert_eq!(transaction.output.len(), 3);
+    assert_eq!(
+      transaction.output[0].script_pubkey,
+      recipient().script_pubkey()
+    );
+    assert_eq!(transaction.output[0].value, 10_000);
+    assert!(is_auth_output(
+      transaction.output.last().unwrap(),
+      b"orddefi:auth"
+    ));
+  }
+
+  #[test]
+  fn auth_output_can_be_added_first() {
+    let transaction = builder(Target::Value(Amount::from_sat(10_000)))
+      .with_auth_output(auth_output(AuthOutputPosition::First))
+      .build_transaction()
+      .unwrap();
+
+    assert_eq!(transaction.output.len(), 3);
+    assert!(is_auth_output(&transaction.output[0], b"orddefi:auth"));
+    assert_eq!(
+      transaction.output[1].script_pubkey,
+      recipient().script_pubkey()
+    );
+    assert_eq!(transaction.output[1].value, 10_000);
+  }
+
+  #[test]
+  fn auth_output_payload_is_configurable() {
+    let transaction = builder(Target::Value(Amount::from_sat(10_000)))
+      .with_auth_output(AuthOutput {
+        payload: b"orddefi:auth:v2".to_vec(),
+        position: AuthOutputPosition::Last,
+      })
+      .build_transaction()
+      .unwrap();
+
+    assert!(is_auth_output(
+      transaction.output.last().unwrap(),
+      b"orddefi:auth:v2"
+    ));
+  }
+
+  fn actual_fee(amounts: &BTreeMap<OutPoint, Amount>, transaction: &Transaction) -> Amount {
+    transaction
+      .input
+      .iter()
+      .map(|tx_in| amounts[&tx_in.previous_output])
+      .sum::<Amount>()
+      - Amount::from_sat(
+        transaction
+          .output
+          .iter()
+          .map(|tx_out| tx_out.value)
+          .sum::<u64>(),
+      )
+  }
+
+  #[test]
+  fn fee_estimation_includes_auth_output() {
+    let fee_rate = FeeRate::try_from(36.0).unwrap();
+
+    let builder = TransactionBuilder {
+      fee_rate,
+      ..builder(Target::Value(Amount::from_sat(10_000)))
+    }
+    .with_auth_output(auth_output(AuthOutputPosition::Last));
+
+    let amounts = builder.amounts.clone();
+
+    let mut transaction = builder.build_transaction().unwrap();
+
+    let actual_fee = actual_fee(&amounts, &transaction);
+
+    for tx_in in &mut transaction.input {
+      tx_in.witness = Witness::from_slice(&[&[0; 64]]);
+    }
+
+    assert_eq!(actual_fee, fee_rate.fee(transaction.vsize()));
+  }
+
+  #[test]
+  fn fee_estimation_accounts_for_input_script_types() {
+    let fee_rate = FeeRate::try_from(36.0).unwrap();
+
+    for (script_pubkey, script_sig, witness) in [
+      (
+        ScriptBuf::new_v0_p2wpkh(&WPubkeyHash::all_zeros()),
+        ScriptBuf::new(),
+        Witness::from_slice(&[&[0; 71][..], &[0; 33][..]]),
+      ),
+      (
+        ScriptBuf::new_p2sh(&ScriptHash::all_zeros()),
+        Builder::new().push_slice([0; 22]).into_script(),
+        Witness::from_slice(&[&[0; 71][..], &[0; 33][..]]),
+      ),
+      (
+        ScriptBuf::new_p2pkh(&PubkeyHash::all_zeros()),
+        Builder::new()
+          .push_slice([0; 71])
+          .push_slice([0; 33])
+          .into_script(),
+        Witness::new(),
+      ),
+      (
+        ScriptBuf::from_bytes([vec![0x51, 0x20], vec![0; 32]].concat()),
+        ScriptBuf::new(),
+        Witness::from_slice(&[&[0; 64]]),
+      ),
+    ] {
+      let builder = TransactionBuilder {
+        fee_rate,
+        ..builder(Target::Value(Amount::from_sat(10_000)))
+      }
+      .with_auth_output(auth_output(AuthOutputPosition::Last))
+      .with_input_scripts(
+        [
+          (outpoint(1), script_pubkey.clone()),
+          (outpoint(2), script_pubkey.clone()),
+        ]
+        .into_iter()
+        .collect(),
+      );
+
+      let amounts = builder.amounts.clone();
+
+      let mut transaction = builder.build_transaction().unwrap();
+
+      let actual_fee = actual_fee(&amounts, &transaction);
+
+      for tx_in in &mut transaction.input {
+        tx_in.script_sig = script_sig.clone();
+        tx_in.witness = witness.clone();
+      }
+
+      assert!(
+        actual_fee >= fee_rate.fee(transaction.vsize()),
+        "{script_pubkey}: {actual_fee} < {}",
+        fee_rate.fee(transaction.vsize()),
+      );
+      assert!(
+        actual_fee <= fee_rate.fee(transaction.vsize() + transaction.input.len()),
+        "{script_pubkey}: {actual_fee} overpays",
+      );
+    }
+  }
+
+  #[test]
+  fn input_vbytes_match_dummy_inputs() {
+    for input_type in [
+      InputType::P2pkh,
+      InputType::P2shP2wpkh,
+      InputType::P2tr,
+      InputType::P2wpkh,
+    ] {
+      // the first input is a taproot input, so that the transaction is a
+      // segwit transaction like the commit transactions inputs are added to
+      let transaction = |inputs: u64| Transaction {
+        version: 2,
+        lock_time: LockTime::ZERO,
+        input: iter::once((InputType::P2tr, 0))
+          .chain((1..inputs).map(|n| (input_type, n)))
+          .map(|(input_type, n)| TxIn {
+            previous_output: outpoint(n),
+            script_sig: input_type.dummy_script_sig(),
+            sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+            witness: input_type.dummy_witness(),
+          })
+          .collect(),
+        output: Vec::new(),
+      };
+
+      let weight = transaction(3).weight() - transaction(2).weight();
+
+      assert_eq!(
+        (weight.to_wu() + 3) / 4,
+        u64::try_from(input_type.vbytes()).unwrap(),
+        "{input_type:?}",
+      );
+    }
+  }
+}
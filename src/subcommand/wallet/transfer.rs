@@ -0,0 +1,322 @@
+use {
+  super::*,
+  crate::subcommand::wallet::{inscribe::Inscribe, transaction_builder::Target},
+};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Transfer {
+  #[arg(long, help = "Transfer <TICK>.")]
+  tick: String,
+  #[arg(long, help = "Transfer <AMT> tokens.")]
+  amt: String,
+  #[arg(long, help = "Send the transfer inscription to <TO>.")]
+  to: Address<NetworkUnchecked>,
+  #[arg(long, help = "Inscribe the transfer from and to <ORIGIN>.")]
+  origin: Option<Address<NetworkUnchecked>>,
+  #[arg(long, help = "Send change to <CHANGE>.")]
+  change: Option<Address<NetworkUnchecked>>,
+  #[arg(
+    long,
+    help = "Use fee rate of <FEE_RATE> sats/vB, or `auto:<BLOCKS>` to estimate a fee rate that confirms within <BLOCKS> blocks."
+  )]
+  fee_rate: Option<FeeRateArgument>,
+  #[arg(long, help = "Do not back up recovery key.")]
+  no_backup: bool,
+  #[arg(
+    long,
+    help = "Amount of postage to include in the inscription. Default `546sat`."
+  )]
+  postage: Option<Amount>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Output {
+  pub commit: Option<Txid>,
+  pub inscription: InscriptionId,
+  pub reveal: Txid,
+  pub send: Txid,
+}
+
+impl Transfer {
+  pub(crate) fn with_profile(self, profile: &Profile) -> Self {
+    Self {
+      change: self.change.or_else(|| profile.change.clone()),
+      fee_rate: self.fee_rate.or(profile.fee_rate),
+      origin: self.origin.or_else(|| profile.origin.clone()),
+      postage: self.postage.or(profile.postage.map(Amount::from_sat)),
+      ..self
+    }
+  }
+
+  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+    let chain = options.chain();
+
+    let recipient = self.to.clone().require_network(chain.network())?;
+
+    let origin = self
+      .origin
+      .ok_or_else(|| anyhow!("no origin given, pass `--origin` or select a profile with one"))?;
+
+    let fee_rate = self.fee_rate.ok_or_else(|| {
+      anyhow!("no fee rate given, pass `--fee-rate` or select a profile with one")
+    })?;
+
+    let inscribed = Inscribe {
+      auth_payload: AuthOutput::DEFAULT_PAYLOAD.into(),
+      auth_position: AuthOutputPosition::Last,
+      batch: None,
+      cbor_metadata: None,
+      commit_fee_rate: None,
+      compress: false,
+      origin: Some(origin.clone()),
+      destination: Some(origin),
+      change: self.change.clone(),
+      dry_run: false,
+      fee_rate: Some(fee_rate),
+      file: None,
+      instruction: Some(OrdDeFiInstruction::Transfer {
+        tick: self.tick,
+        amt: self.amt,
+        to: None,
+      }),
+      json_metadata: None,
+      max_slippage: None,
+      metaprotocol: None,
+      no_auth_check: false,
+      no_backup: self.no_backup,
+      no_limit: false,
+      parent: None,
+      postage: self.postage,
+      psbt_out: None,
+      reinscribe: false,
+      satpoint: None,
+      sat: None,
+      skip_validation: false,
+      subcommand: None,
+    }
+    .inscribe(wallet.clone(), options.clone())?;
+
+    let inscription = &inscribed.inscriptions[0];
+
+    if let Some(psbt) = &inscribed.psbt {
+      bail!(
+        "commit transaction of transfer {} must be signed externally, sign this PSBT, broadcast it with `ord wallet inscribe finalize` and then send the inscription with `ord wallet send`:\n{psbt}",
+        inscription.id
+      );
+    }
+
+    let send = Self::send(
+      wallet,
+      &options,
+      inscription.id,
+      inscription.location,
+      recipient,
+      self.change,
+      fee_rate,
+    )
+    .with_context(|| {
+      format!(
+        "inscribed transfer {} but failed to send it, send it with `ord wallet send`",
+        inscription.id
+      )
+    })?;
+
+    Ok(Box::new(Output {
+      commit: inscribed.commit,
+      inscription: inscription.id,
+      reveal: inscribed.reveal,
+      send,
+    }))
+  }
+
+  /// The reveal transaction is still unconfirmed and thus neither indexed nor
+  /// listed among the wallet's unspent outputs, so its inscription output is
+  /// added explicitly and the send transaction chains off it.
+  fn send(
+    wallet: String,
+    options: &Options,
+    id: InscriptionId,
+    location: SatPoint,
+    recipient: Address,
+    change: Option<Address<NetworkUnchecked>>,
+    fee_rate: FeeRateArgument,
+  ) -> Result<Txid> {
+    let chain = options.chain();
+
+    let index = Index::open(options)?;
+
+    let client = bitcoin_rpc_client_for_wallet_command(wallet, options)?;
+
+    let fee_rate = fee_rate.resolve(&client, options)?;
+
+    let mut unspent_outputs = get_unspent_outputs(&client, &index)?;
+
+    let mut inscriptions = index.get_inscriptions(&unspent_outputs)?;
+
+    let runic_outputs =
+      index.get_runic_outputs(&unspent_outputs.keys().cloned().collect::<Vec<OutPoint>>())?;
+
+    let reveal = client.get_raw_transaction(&location.outpoint.txid, None)?;
+
+    unspent_outputs.insert(
+      location.outpoint,
+      Amount::from_sat(reveal.output[usize::try_from(location.outpoint.vout).unwrap()].value),
+    );
+
+    inscriptions.insert(location, id);
+
+    let change = match change {
+      Some(change) => change.require_network(chain.network())?,
+      None => get_change_address(&client, chain)?,
+    };
+
+    let unsigned_transaction = TransactionBuilder::new(
+      location,
+      inscriptions,
+      unspent_outputs,
+      get_locked_outputs(&client)?,
+      runic_outputs,
+      recipient,
+      change,
+      fee_rate,
+      Target::Postage,
+    )
+    .build_transaction()?;
+
+    let signed_transaction = client
+      .sign_raw_transaction_with_wallet(&unsigned_transaction, None, None)?
+      .hex;
+
+    Ok(client.send_raw_transaction(&signed_transaction)?)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse_transfer_args(args: &str) -> Result<Transfer, clap::Error> {
+    match Arguments::try_parse_from(args.split_whitespace())?.subcommand {
+      crate::subcommand::Subcommand::Wallet(Wallet {
+        subcommand: Subcommand::Transfer(transfer),
+        ..
+      }) => Ok(transfer),
+      subcommand => panic!("unexpected subcommand: {subcommand:?}"),
+    }
+  }
+
+  #[test]
+  fn parse() {
+    let transfer = parse_transfer_args(
+      "ord wallet transfer --tick odgv --amt 1000 --fee-rate 1 \
+       --origin bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 \
+       --to bc1pm8wv7dwnzs5dd6fhgdnurhhpat0zzgly6yugtr472nqhlxatlhdsq6t3ku",
+    )
+    .unwrap();
+
+    assert_eq!(transfer.tick, "odgv");
+    assert_eq!(transfer.amt, "1000");
+    assert_eq!(transfer.fee_rate, Some("1".parse().unwrap()));
+    assert_eq!(transfer.postage, None);
+  }
+
+  #[test]
+  fn origin_may_come_from_profile() {
+    let transfer = parse_transfer_args(
+      "ord wallet transfer --tick odgv --amt 1000 \
+       --to bc1pm8wv7dwnzs5dd6fhgdnurhhpat0zzgly6yugtr472nqhlxatlhdsq6t3ku",
+    )
+    .unwrap()
+    .with_profile(&Profile {
+      fee_rate: Some("2".parse().unwrap()),
+      origin: Some(address().to_string().parse().unwrap()),
+      ..Default::default()
+    });
+
+    assert_eq!(transfer.fee_rate, Some("2".parse().unwrap()));
+    assert_eq!(
+      transfer.origin,
+      Some(address().to_string().parse().unwrap())
+    );
+  }
+
+  #[test]
+  fn recipient_is_required() {
+    assert!(parse_transfer_args(
+      "ord wallet transfer --tick odgv --amt 1000 --fee-rate 1 \
+       --origin bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+    )
+    .is_err());
+  }
+
+  #[test]
+  fn send_spends_unconfirmed_reveal_output() {
+    let rpc_server = test_bitcoincore_rpc::spawn();
+    let tempdir = TempDir::new().unwrap();
+
+    let options = Options::try_parse_from([
+      "ord",
+      "--cookie-file",
+      rpc_server.cookie_file().to_str().unwrap(),
+      "--rpc-url",
+      &rpc_server.url(),
+      "--data-dir",
+      tempdir.path().to_str().unwrap(),
+    ])
+    .unwrap();
+
+    super::super::create::Create {
+      passphrase: String::new(),
+    }
+    .run("ord".into(), options.clone())
+    .unwrap();
+
+    rpc_server.mine_blocks(2);
+
+    let reveal = rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..Default::default()
+    });
+
+    let send = Transfer::send(
+      "ord".into(),
+      &options,
+      InscriptionId {
+        txid: reveal,
+        index: 0,
+      },
+      SatPoint {
+        outpoint: OutPoint {
+          txid: reveal,
+          vout: 0,
+        },
+        offset: 0,
+      },
+      recipient(),
+      None,
+      "1".parse().unwrap(),
+    )
+    .unwrap();
+
+    let mempool = rpc_server.mempool();
+
+    assert_eq!(mempool.len(), 2);
+    assert_eq!(mempool[0].txid(), reveal);
+    assert_eq!(mempool[1].txid(), send);
+    assert_eq!(
+      mempool[1].input[0].previous_output,
+      OutPoint {
+        txid: reveal,
+        vout: 0,
+      }
+    );
+    assert_eq!(
+      mempool[1].output[0].script_pubkey,
+      recipient().script_pubkey()
+    );
+    assert!(mempool[1]
+      .output
+      .iter()
+      .all(|output| !output.script_pubkey.is_op_return()));
+  }
+}
@@ -0,0 +1,202 @@
+use super::*;
+
+/// Bitcoin Core refuses to import descriptors without private keys into a
+/// wallet with private keys, so watch-only origins are kept in a separate
+/// wallet named after the `ord` wallet.
+#[derive(Debug, Parser)]
+pub(crate) enum Watch {
+  #[command(about = "Watch an origin address or output descriptor")]
+  Add(Add),
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Add {
+  #[arg(help = "Watch <ADDRESS_OR_DESCRIPTOR>.")]
+  address_or_descriptor: String,
+  #[arg(
+    long,
+    help = "Rescan the whole chain for outputs of <ADDRESS_OR_DESCRIPTOR> instead of only watching new ones."
+  )]
+  rescan: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Output {
+  pub descriptor: String,
+  pub wallet: String,
+}
+
+impl Watch {
+  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+    match self {
+      Self::Add(add) => add.run(wallet, options),
+    }
+  }
+}
+
+impl Add {
+  fn run(self, wallet: String, options: Options) -> SubcommandResult {
+    bitcoin_rpc_client_for_wallet_command(wallet.clone(), &options)?;
+
+    let name = wallet_name(&wallet);
+
+    let client = match client(&wallet, &options)? {
+      Some(client) => client,
+      None => {
+        check_version(options.bitcoin_rpc_client(None)?)?.create_wallet(
+          &name,
+          Some(true),
+          Some(true),
+          None,
+          None,
+        )?;
+
+        options.bitcoin_rpc_client(Some(name.clone()))?
+      }
+    };
+
+    let descriptor = Self::descriptor(&self.address_or_descriptor, options.chain())?;
+
+    let info = client.get_descriptor_info(&descriptor)?;
+
+    ensure!(
+      !info.has_private_keys,
+      "descriptor `{descriptor}` contains private keys, only watch-only descriptors can be watched",
+    );
+
+    let descriptor = format!("{descriptor}#{}", info.checksum);
+
+    let response = client.import_descriptors(ImportDescriptors {
+      descriptor: descriptor.clone(),
+      timestamp: if self.rescan {
+        Timestamp::Time(0)
+      } else {
+        Timestamp::Now
+      },
+      active: Some(false),
+      range: None,
+      next_index: None,
+      internal: Some(false),
+      // Bitcoin Core rejects labels on ranged descriptors
+      label: (!info.is_range).then(|| "watch-only origin".into()),
+    })?;
+
+    for result in response {
+      if !result.success {
+        bail!(
+          "failed to import descriptor `{descriptor}`{}",
+          result
+            .error
+            .map(|error| format!(": {}", error.message))
+            .unwrap_or_default()
+        );
+      }
+    }
+
+    Ok(Box::new(Output {
+      descriptor,
+      wallet: name,
+    }))
+  }
+
+  /// Addresses are watched with an `addr()` descriptor. Checksums are
+  /// stripped from descriptors, since Bitcoin Core computes them again.
+  fn descriptor(address_or_descriptor: &str, chain: Chain) -> Result<String> {
+    match address_or_descriptor.parse::<Address<NetworkUnchecked>>() {
+      Ok(address) => Ok(format!(
+        "addr({})",
+        address.require_network(chain.network())?
+      )),
+      Err(_) => Ok(
+        address_or_descriptor
+          .split('#')
+          .next()
+          .unwrap_or_default()
+          .into(),
+      ),
+    }
+  }
+}
+
+pub(crate) fn wallet_name(wallet: &str) -> String {
+  format!("{wallet}-watch")
+}
+
+/// The watch-only wallet of `wallet`, if `wallet watch add` has created one.
+pub(crate) fn client(wallet: &str, options: &Options) -> Result<Option<Client>> {
+  let name = wallet_name(wallet);
+
+  let client = check_version(options.bitcoin_rpc_client(None)?)?;
+
+  if !client.list_wallets()?.contains(&name) {
+    if !client.list_wallet_dir()?.contains(&name) {
+      return Ok(None);
+    }
+
+    client.load_wallet(&name)?;
+  }
+
+  Ok(Some(options.bitcoin_rpc_client(Some(name))?))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse() {
+    match Arguments::try_parse_from([
+      "ord",
+      "wallet",
+      "watch",
+      "add",
+      "--rescan",
+      "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+    ])
+    .unwrap()
+    .subcommand
+    {
+      crate::subcommand::Subcommand::Wallet(Wallet {
+        subcommand: Subcommand::Watch(Watch::Add(add)),
+        ..
+      }) => {
+        assert_eq!(
+          add.address_or_descriptor,
+          "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+        );
+        assert!(add.rescan);
+      }
+      subcommand => panic!("unexpected subcommand: {subcommand:?}"),
+    }
+  }
+
+  #[test]
+  fn addresses_are_watched_with_addr_descriptors() {
+    assert_eq!(
+      Add::descriptor("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4", Chain::Mainnet).unwrap(),
+      "addr(bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4)"
+    );
+  }
+
+  #[test]
+  fn addresses_must_match_chain() {
+    assert!(Add::descriptor("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4", Chain::Regtest).is_err());
+  }
+
+  #[test]
+  fn descriptor_checksums_are_stripped() {
+    assert_eq!(
+      Add::descriptor(
+        "wpkh(02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9)#8zl0zxma",
+        Chain::Mainnet
+      )
+      .unwrap(),
+      "wpkh(02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9)"
+    );
+  }
+
+  #[test]
+  fn wallet_name_is_derived_from_ord_wallet() {
+    assert_eq!(wallet_name("ord"), "ord-watch");
+  }
+}
@@ -1,6 +1,7 @@
 use {super::*, boilerplate::Boilerplate};
 
 pub(crate) use {
+  address::{AddressHtml, AddressJson},
   block::{BlockHtml, BlockJson},
   blocks::BlocksHtml,
   children::{ChildrenHtml, ChildrenJson},
@@ -13,6 +14,10 @@ pub(crate) use {
   inscriptions::{InscriptionsHtml, InscriptionsJson},
   inscriptions_block::InscriptionsBlockHtml,
   metadata::MetadataHtml,
+  orddefi_balance::{OrdDeFiBalanceHtml, OrdDeFiBalanceJson},
+  orddefi_instruction::{OrdDeFiInstructionHtml, OrdDeFiInstructionJson},
+  orddefi_pool::{OrdDeFiPoolHtml, OrdDeFiPoolJson},
+  orddefi_ticks::{OrdDeFiTicksHtml, OrdDeFiTicksJson},
   output::{OutputHtml, OutputJson},
   preview::{
     PreviewAudioHtml, PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml, PreviewMarkdownHtml,
@@ -28,6 +33,7 @@ pub(crate) use {
   transaction::TransactionHtml,
 };
 
+pub mod address;
 pub mod block;
 mod blocks;
 mod children;
@@ -40,6 +46,10 @@ pub mod inscription;
 pub mod inscriptions;
 mod inscriptions_block;
 mod metadata;
+pub mod orddefi_balance;
+pub mod orddefi_instruction;
+pub mod orddefi_pool;
+pub mod orddefi_ticks;
 pub mod output;
 mod preview;
 mod range;
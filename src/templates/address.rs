@@ -0,0 +1,122 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct AddressHtml {
+  pub(crate) address: Address,
+  pub(crate) inscriptions: Vec<InscriptionId>,
+  pub(crate) outputs: BTreeMap<OutPoint, Amount>,
+  pub(crate) runes: BTreeMap<SpacedRune, Pile>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct AddressJson {
+  pub address: Address<NetworkUnchecked>,
+  pub inscriptions: Vec<InscriptionId>,
+  pub outputs: BTreeMap<OutPoint, u64>,
+  pub runes: BTreeMap<Rune, u128>,
+  pub sat_balance: u64,
+}
+
+impl AddressHtml {
+  pub(crate) fn sat_balance(&self) -> u64 {
+    self.outputs.values().map(|value| value.to_sat()).sum()
+  }
+}
+
+impl PageContent for AddressHtml {
+  fn title(&self) -> String {
+    format!("Address {}", self.address)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn address() -> Address {
+    "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+      .parse::<Address<NetworkUnchecked>>()
+      .unwrap()
+      .assume_checked()
+  }
+
+  #[test]
+  fn display() {
+    assert_regex_match!(
+      AddressHtml {
+        address: address(),
+        inscriptions: vec![inscription_id(1)],
+        outputs: [
+          (outpoint(1), Amount::from_sat(1000)),
+          (outpoint(2), Amount::from_sat(546)),
+        ]
+        .into_iter()
+        .collect(),
+        runes: [(
+          SpacedRune {
+            rune: Rune(26),
+            spacers: 1
+          },
+          Pile {
+            amount: 11,
+            divisibility: 1,
+            symbol: None,
+          }
+        )]
+        .into_iter()
+        .collect(),
+      },
+      "
+        <h1>Address <span class=monospace>bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4</span></h1>
+        <dl>
+          <dt>sat balance</dt><dd>1546</dd>
+          <dt>inscriptions</dt>
+          <dd class=thumbnails>
+            <a href=/inscription/1{64}i1><iframe .* src=/preview/1{64}i1></iframe></a>
+          </dd>
+          <dt>runes</dt>
+          <dd>
+            <table>
+              <tr>
+                <th>rune</th>
+                <th>balance</th>
+              </tr>
+              <tr>
+                <td><a href=/rune/A•A>A•A</a></td>
+                <td>1.1</td>
+              </tr>
+            </table>
+          </dd>
+        </dl>
+        <h2>2 Outputs</h2>
+        <ul class=monospace>
+          <li><a href=/output/1{64}:1>1{64}:1</a> 1000</li>
+          <li><a href=/output/2{64}:2>2{64}:2</a> 546</li>
+        </ul>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn empty() {
+    assert_regex_match!(
+      AddressHtml {
+        address: address(),
+        inscriptions: Vec::new(),
+        outputs: BTreeMap::new(),
+        runes: BTreeMap::new(),
+      },
+      "
+        <h1>.*</h1>
+        <dl>
+          <dt>sat balance</dt><dd>0</dd>
+        </dl>
+        <h2>0 Outputs</h2>
+        <ul class=monospace>
+        </ul>
+      "
+      .unindent()
+    );
+  }
+}
@@ -0,0 +1,80 @@
+use super::*;
+
+#[derive(Boilerplate)]
+#[boilerplate(filename = "orddefi-balance.html")]
+pub(crate) struct OrdDeFiBalanceHtml {
+  pub(crate) address: Address,
+  pub(crate) balances: BTreeMap<String, orddefi::Balance>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct OrdDeFiBalanceJson {
+  pub address: Address<NetworkUnchecked>,
+  pub balances: BTreeMap<String, orddefi::Balance>,
+}
+
+impl PageContent for OrdDeFiBalanceHtml {
+  fn title(&self) -> String {
+    format!("OrdDeFi Balance {}", self.address)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn display() {
+    assert_regex_match!(
+      OrdDeFiBalanceHtml {
+        address: "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+          .parse::<Address<NetworkUnchecked>>()
+          .unwrap()
+          .assume_checked(),
+        balances: [(
+          "odfi".to_string(),
+          orddefi::Balance {
+            available: "900".parse().unwrap(),
+            transferable: "100.5".parse().unwrap(),
+          },
+        )]
+        .into_iter()
+        .collect(),
+      },
+      "
+        <h1>OrdDeFi Balance <span class=monospace>bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4</span></h1>
+        <table>
+          <tr>
+            <th>tick</th>
+            <th>available</th>
+            <th>transferable</th>
+          </tr>
+          <tr>
+            <td>odfi</td>
+            <td>900</td>
+            <td>100.5</td>
+          </tr>
+        </table>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn empty() {
+    assert_regex_match!(
+      OrdDeFiBalanceHtml {
+        address: "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+          .parse::<Address<NetworkUnchecked>>()
+          .unwrap()
+          .assume_checked(),
+        balances: BTreeMap::new(),
+      },
+      "
+        <h1>.*</h1>
+        <p>No balances.</p>
+      "
+      .unindent()
+    );
+  }
+}
@@ -0,0 +1,78 @@
+use super::*;
+
+#[derive(Boilerplate)]
+#[boilerplate(filename = "orddefi-instruction.html")]
+pub(crate) struct OrdDeFiInstructionHtml {
+  pub(crate) chain: Chain,
+  pub(crate) inscription_id: InscriptionId,
+  pub(crate) receipt: orddefi::Receipt,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct OrdDeFiInstructionJson {
+  pub destination: Option<String>,
+  pub height: u32,
+  pub inscription_id: InscriptionId,
+  pub instruction: OrdDeFiInstruction,
+  #[serde(flatten)]
+  pub status: orddefi::Status,
+}
+
+impl From<OrdDeFiInstructionHtml> for OrdDeFiInstructionJson {
+  fn from(html: OrdDeFiInstructionHtml) -> Self {
+    Self {
+      destination: html
+        .chain
+        .address_from_script(&html.receipt.destination)
+        .ok()
+        .map(|address| address.to_string()),
+      height: html.receipt.height,
+      inscription_id: html.inscription_id,
+      instruction: html.receipt.instruction,
+      status: html.receipt.status,
+    }
+  }
+}
+
+impl PageContent for OrdDeFiInstructionHtml {
+  fn title(&self) -> String {
+    format!("OrdDeFi Instruction {}", self.inscription_id)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn display() {
+    assert_regex_match!(
+      OrdDeFiInstructionHtml {
+        chain: Chain::Mainnet,
+        inscription_id: inscription_id(1),
+        receipt: orddefi::Receipt {
+          destination: ScriptBuf::new(),
+          height: 3,
+          instruction: r#"{"p":"orddefi","op":"mint","tick":"odfi","amt":"1000"}"#
+            .parse()
+            .unwrap(),
+          status: orddefi::Status::Aborted(orddefi::AbortReason::BadAuth),
+        },
+      },
+      r#"
+        <h1>OrdDeFi Instruction</h1>
+        <dl>
+          <dt>inscription</dt>
+          <dd><a class=monospace href=/inscription/1{64}i1>1{64}i1</a></dd>
+          <dt>instruction</dt>
+          <dd class=monospace>\{&quot;p&quot;:&quot;orddefi&quot;,&quot;op&quot;:&quot;mint&quot;,&quot;tick&quot;:&quot;odfi&quot;,&quot;amt&quot;:&quot;1000&quot;\}</dd>
+          <dt>height</dt>
+          <dd><a href=/block/3>3</a></dd>
+          <dt>status</dt>
+          <dd>aborted: bad auth</dd>
+        </dl>
+      "#
+      .unindent()
+    );
+  }
+}
@@ -0,0 +1,86 @@
+use super::*;
+
+#[derive(Boilerplate)]
+#[boilerplate(filename = "orddefi-pool.html")]
+pub(crate) struct OrdDeFiPoolHtml {
+  pub(crate) ltick: String,
+  pub(crate) rtick: String,
+  pub(crate) pool: orddefi::Pool,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct OrdDeFiPoolJson {
+  pub ltick: String,
+  pub lreserve: orddefi::Quantity,
+  pub rtick: String,
+  pub rreserve: orddefi::Quantity,
+  pub shares: orddefi::Quantity,
+}
+
+impl OrdDeFiPoolHtml {
+  /// Pool reserves are stored in pair order, so the ticks are sorted the
+  /// same way.
+  pub(crate) fn new(ltick: &str, rtick: &str, pool: orddefi::Pool) -> Self {
+    let (ltick, rtick) = if ltick <= rtick {
+      (ltick, rtick)
+    } else {
+      (rtick, ltick)
+    };
+
+    Self {
+      ltick: ltick.into(),
+      rtick: rtick.into(),
+      pool,
+    }
+  }
+}
+
+impl From<OrdDeFiPoolHtml> for OrdDeFiPoolJson {
+  fn from(html: OrdDeFiPoolHtml) -> Self {
+    Self {
+      ltick: html.ltick,
+      lreserve: html.pool.left,
+      rtick: html.rtick,
+      rreserve: html.pool.right,
+      shares: html.pool.shares,
+    }
+  }
+}
+
+impl PageContent for OrdDeFiPoolHtml {
+  fn title(&self) -> String {
+    format!("OrdDeFi Pool {}-{}", self.ltick, self.rtick)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn display() {
+    assert_regex_match!(
+      OrdDeFiPoolHtml::new(
+        "odgv",
+        "odfi",
+        orddefi::Pool {
+          left: "100".parse().unwrap(),
+          right: "400".parse().unwrap(),
+          shares: "200".parse().unwrap(),
+        }
+      ),
+      "
+        <h1>OrdDeFi Pool odfi-odgv</h1>
+        <dl>
+          <dt>odfi reserve</dt>
+          <dd>100</dd>
+          <dt>odgv reserve</dt>
+          <dd>400</dd>
+          <dt>LP shares</dt>
+          <dd>200</dd>
+        </dl>
+      "
+      .unindent()
+    );
+  }
+}
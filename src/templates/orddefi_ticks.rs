@@ -0,0 +1,76 @@
+use super::*;
+
+#[derive(Boilerplate)]
+#[boilerplate(filename = "orddefi-ticks.html")]
+pub(crate) struct OrdDeFiTicksHtml {
+  pub(crate) ticks: Vec<(String, orddefi::Quantity)>,
+  pub(crate) prev: Option<usize>,
+  pub(crate) next: Option<usize>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct OrdDeFiTicksJson {
+  pub ticks: BTreeMap<String, orddefi::Quantity>,
+  pub more: bool,
+  pub page_index: usize,
+}
+
+impl PageContent for OrdDeFiTicksHtml {
+  fn title(&self) -> String {
+    "OrdDeFi Ticks".into()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn without_prev_and_next() {
+    assert_regex_match!(
+      OrdDeFiTicksHtml {
+        ticks: vec![("odfi".into(), "1000".parse().unwrap())],
+        prev: None,
+        next: None,
+      },
+      "
+        <h1>OrdDeFi Ticks</h1>
+        <table>
+          <tr>
+            <th>tick</th>
+            <th>supply</th>
+          </tr>
+          <tr>
+            <td>odfi</td>
+            <td>1000</td>
+          </tr>
+        </table>
+        <div class=center>
+        prev
+        next
+        </div>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn with_prev_and_next() {
+    assert_regex_match!(
+      OrdDeFiTicksHtml {
+        ticks: Vec::new(),
+        prev: Some(1),
+        next: Some(3),
+      },
+      "
+        <h1>OrdDeFi Ticks</h1>
+        .*
+        <div class=center>
+        <a class=prev href=/orddefi/ticks/1>prev</a>
+        <a class=next href=/orddefi/ticks/3>next</a>
+        </div>
+      "
+      .unindent()
+    );
+  }
+}
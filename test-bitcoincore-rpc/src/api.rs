@@ -9,6 +9,9 @@ pub trait Api {
   #[rpc(name = "getnetworkinfo")]
   fn get_network_info(&self) -> Result<GetNetworkInfoResult, jsonrpc_core::Error>;
 
+  #[rpc(name = "getindexinfo")]
+  fn get_index_info(&self) -> Result<GetIndexInfoResult, jsonrpc_core::Error>;
+
   #[rpc(name = "getbalances")]
   fn get_balances(&self) -> Result<GetBalancesResult, jsonrpc_core::Error>;
 
@@ -92,6 +95,9 @@ pub trait Api {
     include_watchonly: Option<bool>,
   ) -> Result<Value, jsonrpc_core::Error>;
 
+  #[rpc(name = "getrawmempool")]
+  fn get_raw_mempool(&self) -> Result<Vec<Txid>, jsonrpc_core::Error>;
+
   #[rpc(name = "getrawtransaction")]
   fn get_raw_transaction(
     &self,
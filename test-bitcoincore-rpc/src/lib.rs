@@ -19,11 +19,11 @@ use {
   bitcoincore_rpc::json::{
     Bip125Replaceable, CreateRawTransactionInput, Descriptor, EstimateMode, GetBalancesResult,
     GetBalancesResultEntry, GetBlockHeaderResult, GetBlockchainInfoResult, GetDescriptorInfoResult,
-    GetNetworkInfoResult, GetRawTransactionResult, GetTransactionResult,
+    GetIndexInfoResult, GetNetworkInfoResult, GetRawTransactionResult, GetTransactionResult,
     GetTransactionResultDetail, GetTransactionResultDetailCategory, GetWalletInfoResult,
-    ImportDescriptors, ImportMultiResult, ListDescriptorsResult, ListTransactionResult,
-    ListUnspentResultEntry, LoadWalletResult, SignRawTransactionInput, SignRawTransactionResult,
-    Timestamp, WalletTxInfo,
+    ImportDescriptors, ImportMultiResult, IndexStatus, ListDescriptorsResult,
+    ListTransactionResult, ListUnspentResultEntry, LoadWalletResult, SignRawTransactionInput,
+    SignRawTransactionResult, Timestamp, WalletTxInfo,
   },
   jsonrpc_core::{IoHandler, Value},
   jsonrpc_http_server::{CloseHandle, ServerBuilder},
@@ -49,6 +49,7 @@ pub fn builder() -> Builder {
   Builder {
     fail_lock_unspent: false,
     network: Network::Bitcoin,
+    txindex: true,
     version: 240000,
   }
 }
@@ -56,6 +57,7 @@ pub fn builder() -> Builder {
 pub struct Builder {
   fail_lock_unspent: bool,
   network: Network,
+  txindex: bool,
   version: usize,
 }
 
@@ -71,6 +73,10 @@ impl Builder {
     Self { network, ..self }
   }
 
+  pub fn txindex(self, txindex: bool) -> Self {
+    Self { txindex, ..self }
+  }
+
   pub fn version(self, version: usize) -> Self {
     Self { version, ..self }
   }
@@ -80,6 +86,7 @@ impl Builder {
       self.network,
       self.version,
       self.fail_lock_unspent,
+      self.txindex,
     )));
     let server = Server::new(state.clone());
     let mut io = IoHandler::default();
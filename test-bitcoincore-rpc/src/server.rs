@@ -91,6 +91,19 @@ impl Api for Server {
     })
   }
 
+  fn get_index_info(&self) -> Result<GetIndexInfoResult, jsonrpc_core::Error> {
+    let state = self.state();
+
+    Ok(GetIndexInfoResult {
+      txindex: state.txindex.then(|| IndexStatus {
+        synced: true,
+        best_block_height: (state.hashes.len() - 1).try_into().unwrap(),
+      }),
+      coinstatsindex: None,
+      basic_block_filter_index: None,
+    })
+  }
+
   fn get_block_hash(&self, height: usize) -> Result<BlockHash, jsonrpc_core::Error> {
     match self.state().hashes.get(height) {
       Some(block_hash) => Ok(*block_hash),
@@ -475,6 +488,10 @@ impl Api for Server {
     }
   }
 
+  fn get_raw_mempool(&self) -> Result<Vec<Txid>, jsonrpc_core::Error> {
+    Ok(self.state().mempool.iter().map(Transaction::txid).collect())
+  }
+
   fn get_raw_transaction(
     &self,
     txid: Txid,
@@ -506,7 +523,12 @@ impl Api for Server {
         None => Err(Self::not_found()),
       }
     } else {
-      match self.state().transactions.get(&txid) {
+      let state = self.state();
+      match state
+        .transactions
+        .get(&txid)
+        .or_else(|| state.mempool.iter().find(|tx| tx.txid() == txid))
+      {
         Some(tx) => Ok(Value::String(hex::encode(serialize(tx)))),
         None => Err(Self::not_found()),
       }
@@ -13,13 +13,19 @@ pub(crate) struct State {
   pub(crate) nonce: u32,
   pub(crate) sent: Vec<Sent>,
   pub(crate) transactions: BTreeMap<Txid, Transaction>,
+  pub(crate) txindex: bool,
   pub(crate) utxos: BTreeMap<OutPoint, Amount>,
   pub(crate) version: usize,
   pub(crate) wallets: BTreeSet<String>,
 }
 
 impl State {
-  pub(crate) fn new(network: Network, version: usize, fail_lock_unspent: bool) -> Self {
+  pub(crate) fn new(
+    network: Network,
+    version: usize,
+    fail_lock_unspent: bool,
+    txindex: bool,
+  ) -> Self {
     let mut hashes = Vec::new();
     let mut blocks = BTreeMap::new();
 
@@ -40,6 +46,7 @@ impl State {
       nonce: 0,
       sent: Vec::new(),
       transactions: BTreeMap::new(),
+      txindex,
       utxos: BTreeMap::new(),
       version,
       wallets: BTreeSet::new(),
@@ -48,7 +55,12 @@ impl State {
   }
 
   pub(crate) fn clear(&mut self) {
-    *self = Self::new(self.network, self.version, self.fail_lock_unspent);
+    *self = Self::new(
+      self.network,
+      self.version,
+      self.fail_lock_unspent,
+      self.txindex,
+    );
   }
 
   pub(crate) fn push_block(&mut self, subsidy: u64) -> Block {
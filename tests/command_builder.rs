@@ -103,7 +103,7 @@ impl CommandBuilder {
   }
 
   pub(crate) fn command(&self) -> Command {
-    let mut command = Command::new(executable_path("ord"));
+    let mut command = Command::new(executable_path("OrdDeFi-Inscribe"));
 
     if let Some(rpc_server_url) = &self.rpc_server_url {
       command.args([
@@ -253,10 +253,12 @@ fn etch_does_not_select_inscribed_utxos() {
 
   assert_eq!(output.cardinal, 5000000000);
 
-  CommandBuilder::new("--regtest wallet inscribe --fee-rate 0 --file foo.txt --postage 50btc")
-    .write("foo.txt", "FOO")
-    .rpc_server(&rpc_server)
-    .run_and_deserialize_output::<Inscribe>();
+  CommandBuilder::new(
+    "--regtest wallet inscribe --skip-validation --fee-rate 0 --file foo.txt --postage 50btc",
+  )
+  .write("foo.txt", "FOO")
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Inscribe>();
 
   rpc_server.mine_blocks_with_subsidy(1, 0);
 
@@ -307,12 +309,14 @@ fn inscribe_does_not_select_runic_utxos() {
   assert_eq!(output.ordinal, 0);
   assert_eq!(output.runic, Some(10000));
 
-  CommandBuilder::new("--regtest --index-runes wallet inscribe --fee-rate 0 --file foo.txt")
-    .write("foo.txt", "FOO")
-    .rpc_server(&rpc_server)
-    .expected_exit_code(1)
-    .expected_stderr("error: wallet contains no cardinal utxos\n")
-    .run_and_extract_stdout();
+  CommandBuilder::new(
+    "--regtest --index-runes wallet inscribe --skip-validation --fee-rate 0 --file foo.txt",
+  )
+  .write("foo.txt", "FOO")
+  .rpc_server(&rpc_server)
+  .expected_exit_code(1)
+  .expected_stderr("error: wallet contains no cardinal utxos\n")
+  .run_and_extract_stdout();
 }
 
 #[test]
@@ -389,11 +393,12 @@ fn send_inscription_does_not_select_runic_utxos() {
 
   rpc_server.mine_blocks_with_subsidy(1, 10000);
 
-  let inscribe =
-    CommandBuilder::new("--regtest --index-runes wallet inscribe --fee-rate 0 --file foo.txt")
-      .write("foo.txt", "FOO")
-      .rpc_server(&rpc_server)
-      .run_and_deserialize_output::<Inscribe>();
+  let inscribe = CommandBuilder::new(
+    "--regtest --index-runes wallet inscribe --skip-validation --fee-rate 0 --file foo.txt",
+  )
+  .write("foo.txt", "FOO")
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Inscribe>();
 
   rpc_server.mine_blocks_with_subsidy(1, 0);
 
@@ -106,7 +106,7 @@ fn runes(rpc_server: &test_bitcoincore_rpc::Handle) -> BTreeMap<Rune, RuneInfo>
 fn inscribe(rpc_server: &test_bitcoincore_rpc::Handle) -> (InscriptionId, Txid) {
   rpc_server.mine_blocks(1);
 
-  let output = CommandBuilder::new("wallet inscribe --fee-rate 1 --file foo.txt")
+  let output = CommandBuilder::new("wallet inscribe --skip-validation --fee-rate 1 --file foo.txt")
     .write("foo.txt", "FOO")
     .rpc_server(rpc_server)
     .run_and_deserialize_output::<Inscribe>();
@@ -132,6 +132,7 @@ mod index;
 mod info;
 mod json_api;
 mod list;
+mod orddefi;
 mod parse;
 mod runes;
 mod server;
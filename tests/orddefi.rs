@@ -0,0 +1,92 @@
+use {
+  super::*,
+  ord::subcommand::orddefi::{balance, history, pools},
+};
+
+const ADDRESS: &str = "bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw";
+
+#[test]
+fn flag_is_required() {
+  let rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  CommandBuilder::new("--regtest orddefi pools")
+    .rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr(
+      "error: `ord orddefi pools` requires index created with `--index-orddefi` flag\n",
+    )
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn address_must_match_network() {
+  let rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  CommandBuilder::new(
+    "--regtest --index-orddefi orddefi balance bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+  )
+  .rpc_server(&rpc_server)
+  .expected_exit_code(1)
+  .stderr_regex("error: .*network.*\n")
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn index_requires_txindex() {
+  let rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .txindex(false)
+    .build();
+
+  CommandBuilder::new("--regtest --index-orddefi orddefi pools")
+    .rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr("error: `--index-orddefi` requires bitcoind to be run with `-txindex`\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn empty_index() {
+  let rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  let address = ADDRESS.parse::<Address<NetworkUnchecked>>().unwrap();
+
+  assert_eq!(
+    CommandBuilder::new(format!(
+      "--regtest --index-orddefi orddefi balance {ADDRESS}"
+    ))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<balance::Output>(),
+    balance::Output {
+      address: address.clone(),
+      balances: BTreeMap::new(),
+    }
+  );
+
+  assert_eq!(
+    CommandBuilder::new("--regtest --index-orddefi orddefi pools")
+      .rpc_server(&rpc_server)
+      .run_and_deserialize_output::<pools::Output>(),
+    pools::Output {
+      pools: BTreeMap::new(),
+    }
+  );
+
+  assert_eq!(
+    CommandBuilder::new(format!(
+      "--regtest --index-orddefi orddefi history {ADDRESS}"
+    ))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<history::Output>(),
+    history::Output {
+      address,
+      instructions: Vec::new(),
+    }
+  );
+}
@@ -115,15 +115,16 @@ fn multiple_inscriptions_appear_on_reveal_transaction_page() {
 
   rpc_server.mine_blocks(1);
 
-  let output = CommandBuilder::new("wallet inscribe --batch batch.yaml --fee-rate 55")
-    .write("inscription.txt", "Hello World")
-    .write("meow.wav", [0; 2048])
-    .write(
-      "batch.yaml",
-      "mode: shared-output\ninscriptions:\n- file: inscription.txt\n- file: meow.wav\n",
-    )
-    .rpc_server(&rpc_server)
-    .run_and_deserialize_output::<Inscribe>();
+  let output =
+    CommandBuilder::new("wallet inscribe --skip-validation --batch batch.yaml --fee-rate 55")
+      .write("inscription.txt", "Hello World")
+      .write("meow.wav", [0; 2048])
+      .write(
+        "batch.yaml",
+        "mode: shared-output\ninscriptions:\n- file: inscription.txt\n- file: meow.wav\n",
+      )
+      .rpc_server(&rpc_server)
+      .run_and_deserialize_output::<Inscribe>();
 
   rpc_server.mine_blocks(1);
 
@@ -243,7 +244,7 @@ fn inscription_metadata() {
   rpc_server.mine_blocks(1);
 
   let inscription_id = CommandBuilder::new(
-    "wallet inscribe --fee-rate 1 --json-metadata metadata.json --file foo.txt",
+    "wallet inscribe --skip-validation --fee-rate 1 --json-metadata metadata.json --file foo.txt",
   )
   .write("foo.txt", "FOO")
   .write("metadata.json", metadata)
@@ -349,7 +350,7 @@ fn server_runs_with_rpc_user_and_pass_as_env_vars() {
     .unwrap()
     .port();
 
-  let mut child = Command::new(executable_path("ord"))
+  let mut child = Command::new(executable_path("OrdDeFi-Inscribe"))
     .args(format!(
       "--rpc-url {} --bitcoin-data-dir {} --data-dir {} server --http-port {port} --address 127.0.0.1",
       rpc_server.url(),
@@ -31,7 +31,7 @@ fn inscribe_works_with_huge_expensive_inscriptions() {
   let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
 
   CommandBuilder::new(format!(
-    "wallet inscribe --file foo.txt --satpoint {txid}:0:0 --fee-rate 10"
+    "wallet inscribe --skip-validation --file foo.txt --satpoint {txid}:0:0 --fee-rate 10"
   ))
   .write("foo.txt", [0; 350_000])
   .rpc_server(&rpc_server)
@@ -45,7 +45,7 @@ fn metaprotocol_appears_on_inscription_page() {
   let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
 
   let inscribe = CommandBuilder::new(format!(
-    "wallet inscribe --file foo.txt --metaprotocol foo --satpoint {txid}:0:0 --fee-rate 10"
+    "wallet inscribe --skip-validation --file foo.txt --metaprotocol foo --satpoint {txid}:0:0 --fee-rate 10"
   ))
   .write("foo.txt", [0; 350_000])
   .rpc_server(&rpc_server)
@@ -65,7 +65,7 @@ fn metaprotocol_appears_on_inscription_page() {
 fn inscribe_fails_if_bitcoin_core_is_too_old() {
   let rpc_server = test_bitcoincore_rpc::builder().version(230000).build();
 
-  CommandBuilder::new("wallet inscribe --file hello.txt --fee-rate 1")
+  CommandBuilder::new("wallet inscribe --skip-validation --file hello.txt --fee-rate 1")
     .write("hello.txt", "HELLOWORLD")
     .expected_exit_code(1)
     .expected_stderr("error: Bitcoin Core 24.0.0 or newer required, current version is 23.0.0\n")
@@ -81,10 +81,12 @@ fn inscribe_no_backup() {
   create_wallet(&rpc_server);
   assert_eq!(rpc_server.descriptors().len(), 2);
 
-  CommandBuilder::new("wallet inscribe --file hello.txt --no-backup --fee-rate 1")
-    .write("hello.txt", "HELLOWORLD")
-    .rpc_server(&rpc_server)
-    .run_and_deserialize_output::<Inscribe>();
+  CommandBuilder::new(
+    "wallet inscribe --skip-validation --file hello.txt --no-backup --fee-rate 1",
+  )
+  .write("hello.txt", "HELLOWORLD")
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Inscribe>();
 
   assert_eq!(rpc_server.descriptors().len(), 2);
 }
@@ -95,7 +97,7 @@ fn inscribe_unknown_file_extension() {
   create_wallet(&rpc_server);
   rpc_server.mine_blocks(1);
 
-  CommandBuilder::new("wallet inscribe --file pepe.xyz --fee-rate 1")
+  CommandBuilder::new("wallet inscribe --skip-validation --file pepe.xyz --fee-rate 1")
     .write("pepe.xyz", [1; 520])
     .rpc_server(&rpc_server)
     .expected_exit_code(1)
@@ -111,14 +113,16 @@ fn inscribe_exceeds_chain_limit() {
   create_wallet(&rpc_server);
   rpc_server.mine_blocks(1);
 
-  CommandBuilder::new("--chain signet wallet inscribe --file degenerate.png --fee-rate 1")
-    .write("degenerate.png", [1; 1025])
-    .rpc_server(&rpc_server)
-    .expected_exit_code(1)
-    .expected_stderr(
-      "error: content size of 1025 bytes exceeds 1024 byte limit for signet inscriptions\n",
-    )
-    .run_and_extract_stdout();
+  CommandBuilder::new(
+    "--chain signet wallet inscribe --skip-validation --file degenerate.png --fee-rate 1",
+  )
+  .write("degenerate.png", [1; 1025])
+  .rpc_server(&rpc_server)
+  .expected_exit_code(1)
+  .expected_stderr(
+    "error: content size of 1025 bytes exceeds 1024 byte limit for signet inscriptions\n",
+  )
+  .run_and_extract_stdout();
 }
 
 #[test]
@@ -129,11 +133,13 @@ fn regtest_has_no_content_size_limit() {
   create_wallet(&rpc_server);
   rpc_server.mine_blocks(1);
 
-  CommandBuilder::new("--chain regtest wallet inscribe --file degenerate.png --fee-rate 1")
-    .write("degenerate.png", [1; 1025])
-    .rpc_server(&rpc_server)
-    .stdout_regex(".*")
-    .run_and_extract_stdout();
+  CommandBuilder::new(
+    "--chain regtest wallet inscribe --skip-validation --file degenerate.png --fee-rate 1",
+  )
+  .write("degenerate.png", [1; 1025])
+  .rpc_server(&rpc_server)
+  .stdout_regex(".*")
+  .run_and_extract_stdout();
 }
 
 #[test]
@@ -144,7 +150,7 @@ fn mainnet_has_no_content_size_limit() {
   create_wallet(&rpc_server);
   rpc_server.mine_blocks(1);
 
-  CommandBuilder::new("wallet inscribe --file degenerate.png --fee-rate 1")
+  CommandBuilder::new("wallet inscribe --skip-validation --file degenerate.png --fee-rate 1")
     .write("degenerate.png", [1; 1025])
     .rpc_server(&rpc_server)
     .stdout_regex(".*")
@@ -159,7 +165,7 @@ fn inscribe_does_not_use_inscribed_sats_as_cardinal_utxos() {
   rpc_server.mine_blocks_with_subsidy(1, 100);
 
   CommandBuilder::new(
-    "wallet inscribe --file degenerate.png --fee-rate 1"
+    "wallet inscribe --skip-validation --file degenerate.png --fee-rate 1"
   )
   .rpc_server(&rpc_server)
   .write("degenerate.png", [1; 100])
@@ -180,7 +186,7 @@ fn refuse_to_reinscribe_sats() {
   rpc_server.mine_blocks_with_subsidy(1, 100);
 
   CommandBuilder::new(format!(
-    "wallet inscribe --satpoint {reveal}:0:0 --file hello.txt --fee-rate 1"
+    "wallet inscribe --skip-validation --satpoint {reveal}:0:0 --file hello.txt --fee-rate 1"
   ))
   .write("hello.txt", "HELLOWORLD")
   .rpc_server(&rpc_server)
@@ -202,7 +208,7 @@ fn refuse_to_inscribe_already_inscribed_utxo() {
   };
 
   CommandBuilder::new(format!(
-    "wallet inscribe --satpoint {output}:55555 --file hello.txt --fee-rate 1"
+    "wallet inscribe --skip-validation --satpoint {output}:55555 --file hello.txt --fee-rate 1"
   ))
   .write("hello.txt", "HELLOWORLD")
   .rpc_server(&rpc_server)
@@ -220,7 +226,7 @@ fn inscribe_with_optional_satpoint_arg() {
   let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
 
   let Inscribe { inscriptions, .. } = CommandBuilder::new(format!(
-    "wallet inscribe --file foo.txt --satpoint {txid}:0:10000 --fee-rate 1"
+    "wallet inscribe --skip-validation --file foo.txt --satpoint {txid}:0:10000 --fee-rate 1"
   ))
   .write("foo.txt", "FOO")
   .rpc_server(&rpc_server)
@@ -244,11 +250,12 @@ fn inscribe_with_fee_rate() {
   create_wallet(&rpc_server);
   rpc_server.mine_blocks(1);
 
-  let output =
-    CommandBuilder::new("--index-sats wallet inscribe --file degenerate.png --fee-rate 2.0")
-      .write("degenerate.png", [1; 520])
-      .rpc_server(&rpc_server)
-      .run_and_deserialize_output::<Inscribe>();
+  let output = CommandBuilder::new(
+    "--index-sats wallet inscribe --skip-validation --file degenerate.png --fee-rate 2.0",
+  )
+  .write("degenerate.png", [1; 520])
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Inscribe>();
 
   let tx1 = &rpc_server.mempool()[0];
   let mut fee = 0;
@@ -294,7 +301,7 @@ fn inscribe_with_commit_fee_rate() {
   rpc_server.mine_blocks(1);
 
   CommandBuilder::new(
-    "--index-sats wallet inscribe --file degenerate.png --commit-fee-rate 2.0 --fee-rate 1",
+    "--index-sats wallet inscribe --skip-validation --file degenerate.png --commit-fee-rate 2.0 --fee-rate 1",
   )
   .write("degenerate.png", [1; 520])
   .rpc_server(&rpc_server)
@@ -352,14 +359,16 @@ fn inscribe_with_dry_run_flag() {
   create_wallet(&rpc_server);
   rpc_server.mine_blocks(1);
 
-  CommandBuilder::new("wallet inscribe --dry-run --file degenerate.png --fee-rate 1")
-    .write("degenerate.png", [1; 520])
-    .rpc_server(&rpc_server)
-    .run_and_deserialize_output::<Inscribe>();
+  CommandBuilder::new(
+    "wallet inscribe --skip-validation --dry-run --file degenerate.png --fee-rate 1",
+  )
+  .write("degenerate.png", [1; 520])
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Inscribe>();
 
   assert!(rpc_server.mempool().is_empty());
 
-  CommandBuilder::new("wallet inscribe --file degenerate.png --fee-rate 1")
+  CommandBuilder::new("wallet inscribe --skip-validation --file degenerate.png --fee-rate 1")
     .write("degenerate.png", [1; 520])
     .rpc_server(&rpc_server)
     .run_and_deserialize_output::<Inscribe>();
@@ -367,25 +376,53 @@ fn inscribe_with_dry_run_flag() {
   assert_eq!(rpc_server.mempool().len(), 2);
 }
 
+#[test]
+fn inscribe_with_dry_run_flag_does_not_import_recovery_key() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  assert_eq!(rpc_server.descriptors().len(), 2);
+
+  let output = CommandBuilder::new(
+    "wallet inscribe --dry-run --skip-validation --file degenerate.png --fee-rate 1",
+  )
+  .write("degenerate.png", [1; 520])
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Inscribe>();
+
+  assert!(rpc_server.mempool().is_empty());
+  assert_eq!(rpc_server.descriptors().len(), 2);
+  assert!(rpc_server.change_addresses().is_empty());
+  assert_eq!(output.total_fees, output.commit_fee + output.reveal_fee);
+  assert!(output.commit_vsize > 0);
+  assert!(output.reveal_vsize > 0);
+  assert!(!output.commit_hex.is_empty());
+  assert!(!output.reveal_hex.is_empty());
+  assert!(!output.inputs.is_empty());
+}
+
 #[test]
 fn inscribe_with_dry_run_flag_fees_increase() {
   let rpc_server = test_bitcoincore_rpc::spawn();
   create_wallet(&rpc_server);
   rpc_server.mine_blocks(1);
 
-  let total_fee_dry_run =
-    CommandBuilder::new("wallet inscribe --dry-run --file degenerate.png --fee-rate 1")
-      .write("degenerate.png", [1; 520])
-      .rpc_server(&rpc_server)
-      .run_and_deserialize_output::<Inscribe>()
-      .total_fees;
+  let total_fee_dry_run = CommandBuilder::new(
+    "wallet inscribe --skip-validation --dry-run --file degenerate.png --fee-rate 1",
+  )
+  .write("degenerate.png", [1; 520])
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Inscribe>()
+  .total_fees;
 
-  let total_fee_normal =
-    CommandBuilder::new("wallet inscribe --dry-run --file degenerate.png --fee-rate 1.1")
-      .write("degenerate.png", [1; 520])
-      .rpc_server(&rpc_server)
-      .run_and_deserialize_output::<Inscribe>()
-      .total_fees;
+  let total_fee_normal = CommandBuilder::new(
+    "wallet inscribe --skip-validation --dry-run --file degenerate.png --fee-rate 1.1",
+  )
+  .write("degenerate.png", [1; 520])
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Inscribe>()
+  .total_fees;
 
   assert!(total_fee_dry_run < total_fee_normal);
 }
@@ -402,7 +439,7 @@ fn inscribe_to_specific_destination() {
     .address;
 
   let txid = CommandBuilder::new(format!(
-    "wallet inscribe --destination {} --file degenerate.png --fee-rate 1",
+    "wallet inscribe --skip-validation --destination {} --file degenerate.png --fee-rate 1",
     destination.clone().assume_checked()
   ))
   .write("degenerate.png", [1; 520])
@@ -425,7 +462,7 @@ fn inscribe_to_address_on_different_network() {
   rpc_server.mine_blocks(1);
 
   CommandBuilder::new(
-    "wallet inscribe --destination tb1qsgx55dp6gn53tsmyjjv4c2ye403hgxynxs0dnm --file degenerate.png --fee-rate 1"
+    "wallet inscribe --skip-validation --destination tb1qsgx55dp6gn53tsmyjjv4c2ye403hgxynxs0dnm --file degenerate.png --fee-rate 1"
   )
   .write("degenerate.png", [1; 520])
   .rpc_server(&rpc_server)
@@ -441,7 +478,7 @@ fn inscribe_with_no_limit() {
   rpc_server.mine_blocks(1);
 
   let four_megger = std::iter::repeat(0).take(4_000_000).collect::<Vec<u8>>();
-  CommandBuilder::new("wallet inscribe --no-limit degenerate.png --fee-rate 1")
+  CommandBuilder::new("wallet inscribe --skip-validation --no-limit degenerate.png --fee-rate 1")
     .write("degenerate.png", four_megger)
     .rpc_server(&rpc_server);
 }
@@ -452,10 +489,12 @@ fn inscribe_works_with_postage() {
   create_wallet(&rpc_server);
   rpc_server.mine_blocks(1);
 
-  CommandBuilder::new("wallet inscribe --file foo.txt --postage 5btc --fee-rate 10".to_string())
-    .write("foo.txt", [0; 350])
-    .rpc_server(&rpc_server)
-    .run_and_deserialize_output::<Inscribe>();
+  CommandBuilder::new(
+    "wallet inscribe --skip-validation --file foo.txt --postage 5btc --fee-rate 10".to_string(),
+  )
+  .write("foo.txt", [0; 350])
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Inscribe>();
 
   rpc_server.mine_blocks(1);
 
@@ -476,7 +515,7 @@ fn inscribe_with_non_existent_parent_inscription() {
   let parent_id = "0000000000000000000000000000000000000000000000000000000000000000i0";
 
   CommandBuilder::new(format!(
-    "wallet inscribe --fee-rate 1.0 --parent {parent_id} --file child.png"
+    "wallet inscribe --skip-validation --fee-rate 1.0 --parent {parent_id} --file child.png"
   ))
   .write("child.png", [1; 520])
   .rpc_server(&rpc_server)
@@ -491,10 +530,11 @@ fn inscribe_with_parent_inscription_and_fee_rate() {
   create_wallet(&rpc_server);
   rpc_server.mine_blocks(1);
 
-  let parent_output = CommandBuilder::new("wallet inscribe --fee-rate 5.0 --file parent.png")
-    .write("parent.png", [1; 520])
-    .rpc_server(&rpc_server)
-    .run_and_deserialize_output::<Inscribe>();
+  let parent_output =
+    CommandBuilder::new("wallet inscribe --skip-validation --fee-rate 5.0 --file parent.png")
+      .write("parent.png", [1; 520])
+      .rpc_server(&rpc_server)
+      .run_and_deserialize_output::<Inscribe>();
 
   assert_eq!(rpc_server.descriptors().len(), 3);
   let parent_id = parent_output.inscriptions[0].id;
@@ -513,7 +553,7 @@ fn inscribe_with_parent_inscription_and_fee_rate() {
   rpc_server.mine_blocks(1);
 
   let child_output = CommandBuilder::new(format!(
-    "wallet inscribe --fee-rate 7.3 --parent {parent_id} --file child.png"
+    "wallet inscribe --skip-validation --fee-rate 7.3 --parent {parent_id} --file child.png"
   ))
   .write("child.png", [1; 520])
   .rpc_server(&rpc_server)
@@ -563,10 +603,11 @@ fn reinscribe_with_flag() {
 
   create_wallet(&rpc_server);
 
-  let inscribe = CommandBuilder::new("wallet inscribe --file tulip.png --fee-rate 5.0 ")
-    .write("tulip.png", [1; 520])
-    .rpc_server(&rpc_server)
-    .run_and_deserialize_output::<Inscribe>();
+  let inscribe =
+    CommandBuilder::new("wallet inscribe --skip-validation --file tulip.png --fee-rate 5.0 ")
+      .write("tulip.png", [1; 520])
+      .rpc_server(&rpc_server)
+      .run_and_deserialize_output::<Inscribe>();
 
   assert_eq!(rpc_server.descriptors().len(), 3);
 
@@ -578,7 +619,7 @@ fn reinscribe_with_flag() {
   assert_eq!(request.status(), 200);
 
   let reinscribe = CommandBuilder::new(format!(
-    "wallet inscribe --file orchid.png --fee-rate 1.1 --reinscribe --satpoint {txid}:0:0"
+    "wallet inscribe --skip-validation --file orchid.png --fee-rate 1.1 --reinscribe --satpoint {txid}:0:0"
   ))
   .write("orchid.png", [1; 520])
   .rpc_server(&rpc_server)
@@ -608,7 +649,7 @@ fn with_reinscribe_flag_but_not_actually_a_reinscription() {
 
   create_wallet(&rpc_server);
 
-  CommandBuilder::new("wallet inscribe --file tulip.png --fee-rate 5.0 ")
+  CommandBuilder::new("wallet inscribe --skip-validation --file tulip.png --fee-rate 5.0 ")
     .write("tulip.png", [1; 520])
     .rpc_server(&rpc_server)
     .run_and_deserialize_output::<Inscribe>();
@@ -616,7 +657,7 @@ fn with_reinscribe_flag_but_not_actually_a_reinscription() {
   let coinbase = rpc_server.mine_blocks(1)[0].txdata[0].txid();
 
   CommandBuilder::new(format!(
-    "wallet inscribe --file orchid.png --fee-rate 1.1 --reinscribe --satpoint {coinbase}:0:0"
+    "wallet inscribe --skip-validation --file orchid.png --fee-rate 1.1 --reinscribe --satpoint {coinbase}:0:0"
   ))
   .write("orchid.png", [1; 520])
   .rpc_server(&rpc_server)
@@ -634,18 +675,19 @@ fn try_reinscribe_without_flag() {
 
   create_wallet(&rpc_server);
 
-  let reveal_txid = CommandBuilder::new("wallet inscribe --file tulip.png --fee-rate 5.0 ")
-    .write("tulip.png", [1; 520])
-    .rpc_server(&rpc_server)
-    .run_and_deserialize_output::<Inscribe>()
-    .reveal;
+  let reveal_txid =
+    CommandBuilder::new("wallet inscribe --skip-validation --file tulip.png --fee-rate 5.0 ")
+      .write("tulip.png", [1; 520])
+      .rpc_server(&rpc_server)
+      .run_and_deserialize_output::<Inscribe>()
+      .reveal;
 
   assert_eq!(rpc_server.descriptors().len(), 3);
 
   rpc_server.mine_blocks(1);
 
   CommandBuilder::new(format!(
-    "wallet inscribe --file orchid.png --fee-rate 1.1 --satpoint {reveal_txid}:0:0"
+    "wallet inscribe --skip-validation --file orchid.png --fee-rate 1.1 --satpoint {reveal_txid}:0:0"
   ))
   .write("orchid.png", [1; 520])
   .rpc_server(&rpc_server)
@@ -663,7 +705,7 @@ fn no_metadata_appears_on_inscription_page_if_no_metadata_is_passed() {
   rpc_server.mine_blocks(1);
 
   let Inscribe { inscriptions, .. } =
-    CommandBuilder::new("wallet inscribe --fee-rate 1 --file content.png")
+    CommandBuilder::new("wallet inscribe --skip-validation --fee-rate 1 --file content.png")
       .write("content.png", [1; 520])
       .rpc_server(&rpc_server)
       .run_and_deserialize_output();
@@ -688,7 +730,7 @@ fn json_metadata_appears_on_inscription_page() {
   rpc_server.mine_blocks(1);
 
   let Inscribe { inscriptions, .. } = CommandBuilder::new(
-    "wallet inscribe --fee-rate 1 --json-metadata metadata.json --file content.png",
+    "wallet inscribe --skip-validation --fee-rate 1 --json-metadata metadata.json --file content.png",
   )
   .write("content.png", [1; 520])
   .write("metadata.json", r#"{"foo": "bar", "baz": 1}"#)
@@ -714,7 +756,7 @@ fn cbor_metadata_appears_on_inscription_page() {
   rpc_server.mine_blocks(1);
 
   let Inscribe { inscriptions, .. } = CommandBuilder::new(
-    "wallet inscribe --fee-rate 1 --cbor-metadata metadata.cbor --file content.png",
+    "wallet inscribe --skip-validation --fee-rate 1 --cbor-metadata metadata.cbor --file content.png",
   )
   .write("content.png", [1; 520])
   .write(
@@ -741,7 +783,7 @@ fn cbor_metadata_appears_on_inscription_page() {
 #[test]
 fn error_message_when_parsing_json_metadata_is_reasonable() {
   CommandBuilder::new(
-    "wallet inscribe --fee-rate 1 --json-metadata metadata.json --file content.png",
+    "wallet inscribe --skip-validation --fee-rate 1 --json-metadata metadata.json --file content.png",
   )
   .write("content.png", [1; 520])
   .write("metadata.json", "{")
@@ -753,7 +795,7 @@ fn error_message_when_parsing_json_metadata_is_reasonable() {
 #[test]
 fn error_message_when_parsing_cbor_metadata_is_reasonable() {
   CommandBuilder::new(
-    "wallet inscribe --fee-rate 1 --cbor-metadata metadata.cbor --file content.png",
+    "wallet inscribe --skip-validation --fee-rate 1 --cbor-metadata metadata.cbor --file content.png",
   )
   .write("content.png", [1; 520])
   .write("metadata.cbor", [0x61])
@@ -771,7 +813,7 @@ fn batch_inscribe_fails_if_batchfile_has_no_inscriptions() {
 
   create_wallet(&rpc_server);
 
-  CommandBuilder::new("wallet inscribe --fee-rate 2.1 --batch batch.yaml")
+  CommandBuilder::new("wallet inscribe --skip-validation --fee-rate 2.1 --batch batch.yaml")
     .write("inscription.txt", "Hello World")
     .write("batch.yaml", "mode: shared-output\ninscriptions: []\n")
     .rpc_server(&rpc_server)
@@ -789,7 +831,7 @@ fn batch_inscribe_can_create_one_inscription() {
 
   create_wallet(&rpc_server);
 
-  let output = CommandBuilder::new("wallet inscribe --fee-rate 2.1 --batch batch.yaml")
+  let output = CommandBuilder::new("wallet inscribe --skip-validation --fee-rate 2.1 --batch batch.yaml")
     .write("inscription.txt", "Hello World")
     .write(
       "batch.yaml",
@@ -828,7 +870,7 @@ fn batch_inscribe_with_multiple_inscriptions() {
 
   create_wallet(&rpc_server);
 
-  let output = CommandBuilder::new("wallet inscribe --batch batch.yaml --fee-rate 55")
+  let output = CommandBuilder::new("wallet inscribe --skip-validation --batch batch.yaml --fee-rate 55")
     .write("inscription.txt", "Hello World")
     .write("tulip.png", [0; 555])
     .write("meow.wav", [0; 2048])
@@ -872,10 +914,11 @@ fn batch_inscribe_with_multiple_inscriptions_with_parent() {
 
   create_wallet(&rpc_server);
 
-  let parent_output = CommandBuilder::new("wallet inscribe --fee-rate 5.0 --file parent.png")
-    .write("parent.png", [1; 520])
-    .rpc_server(&rpc_server)
-    .run_and_deserialize_output::<Inscribe>();
+  let parent_output =
+    CommandBuilder::new("wallet inscribe --skip-validation --fee-rate 5.0 --file parent.png")
+      .write("parent.png", [1; 520])
+      .rpc_server(&rpc_server)
+      .run_and_deserialize_output::<Inscribe>();
 
   rpc_server.mine_blocks(1);
 
@@ -883,7 +926,7 @@ fn batch_inscribe_with_multiple_inscriptions_with_parent() {
 
   let parent_id = parent_output.inscriptions[0].id;
 
-  let output = CommandBuilder::new("wallet inscribe --fee-rate 1 --batch batch.yaml")
+  let output = CommandBuilder::new("wallet inscribe --skip-validation --fee-rate 1 --batch batch.yaml")
     .write("inscription.txt", "Hello World")
     .write("tulip.png", [0; 555])
     .write("meow.wav", [0; 2048])
@@ -923,14 +966,16 @@ fn batch_inscribe_respects_dry_run_flag() {
 
   create_wallet(&rpc_server);
 
-  let output = CommandBuilder::new("wallet inscribe --fee-rate 2.1 --batch batch.yaml --dry-run")
-    .write("inscription.txt", "Hello World")
-    .write(
-      "batch.yaml",
-      "mode: shared-output\ninscriptions:\n- file: inscription.txt\n",
-    )
-    .rpc_server(&rpc_server)
-    .run_and_deserialize_output::<Inscribe>();
+  let output = CommandBuilder::new(
+    "wallet inscribe --skip-validation --fee-rate 2.1 --batch batch.yaml --dry-run",
+  )
+  .write("inscription.txt", "Hello World")
+  .write(
+    "batch.yaml",
+    "mode: shared-output\ninscriptions:\n- file: inscription.txt\n",
+  )
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Inscribe>();
 
   rpc_server.mine_blocks(1);
 
@@ -949,7 +994,7 @@ fn batch_in_same_output_but_different_satpoints() {
 
   create_wallet(&rpc_server);
 
-  let output = CommandBuilder::new("wallet inscribe --fee-rate 1 --batch batch.yaml")
+  let output = CommandBuilder::new("wallet inscribe --skip-validation --fee-rate 1 --batch batch.yaml")
     .write("inscription.txt", "Hello World")
     .write("tulip.png", [0; 555])
     .write("meow.wav", [0; 2048])
@@ -1014,7 +1059,7 @@ fn batch_in_same_output_with_non_default_postage() {
 
   create_wallet(&rpc_server);
 
-  let output = CommandBuilder::new("wallet inscribe --fee-rate 1 --batch batch.yaml")
+  let output = CommandBuilder::new("wallet inscribe --skip-validation --fee-rate 1 --batch batch.yaml")
     .write("inscription.txt", "Hello World")
     .write("tulip.png", [0; 555])
     .write("meow.wav", [0; 2048])
@@ -1081,10 +1126,11 @@ fn batch_in_separate_outputs_with_parent() {
 
   create_wallet(&rpc_server);
 
-  let parent_output = CommandBuilder::new("wallet inscribe --fee-rate 5.0 --file parent.png")
-    .write("parent.png", [1; 520])
-    .rpc_server(&rpc_server)
-    .run_and_deserialize_output::<Inscribe>();
+  let parent_output =
+    CommandBuilder::new("wallet inscribe --skip-validation --fee-rate 5.0 --file parent.png")
+      .write("parent.png", [1; 520])
+      .rpc_server(&rpc_server)
+      .run_and_deserialize_output::<Inscribe>();
 
   rpc_server.mine_blocks(1);
 
@@ -1092,7 +1138,7 @@ fn batch_in_separate_outputs_with_parent() {
 
   let parent_id = parent_output.inscriptions[0].id;
 
-  let output = CommandBuilder::new("wallet inscribe --fee-rate 1 --batch batch.yaml")
+  let output = CommandBuilder::new("wallet inscribe --skip-validation --fee-rate 1 --batch batch.yaml")
     .write("inscription.txt", "Hello World")
     .write("tulip.png", [0; 555])
     .write("meow.wav", [0; 2048])
@@ -1157,10 +1203,11 @@ fn batch_in_separate_outputs_with_parent_and_non_default_postage() {
 
   create_wallet(&rpc_server);
 
-  let parent_output = CommandBuilder::new("wallet inscribe --fee-rate 5.0 --file parent.png")
-    .write("parent.png", [1; 520])
-    .rpc_server(&rpc_server)
-    .run_and_deserialize_output::<Inscribe>();
+  let parent_output =
+    CommandBuilder::new("wallet inscribe --skip-validation --fee-rate 5.0 --file parent.png")
+      .write("parent.png", [1; 520])
+      .rpc_server(&rpc_server)
+      .run_and_deserialize_output::<Inscribe>();
 
   rpc_server.mine_blocks(1);
 
@@ -1168,7 +1215,7 @@ fn batch_in_separate_outputs_with_parent_and_non_default_postage() {
 
   let parent_id = parent_output.inscriptions[0].id;
 
-  let output = CommandBuilder::new("wallet inscribe --fee-rate 1 --batch batch.yaml")
+  let output = CommandBuilder::new("wallet inscribe --skip-validation --fee-rate 1 --batch batch.yaml")
     .write("inscription.txt", "Hello World")
     .write("tulip.png", [0; 555])
     .write("meow.wav", [0; 2048])
@@ -1235,7 +1282,7 @@ fn inscribe_does_not_pick_locked_utxos() {
 
   rpc_server.lock(outpoint);
 
-  CommandBuilder::new("wallet inscribe --file hello.txt --fee-rate 1")
+  CommandBuilder::new("wallet inscribe --skip-validation --file hello.txt --fee-rate 1")
     .rpc_server(&rpc_server)
     .write("hello.txt", "HELLOWORLD")
     .expected_exit_code(1)
@@ -1250,11 +1297,12 @@ fn inscribe_can_compress() {
 
   create_wallet(&rpc_server);
 
-  let Inscribe { inscriptions, .. } =
-    CommandBuilder::new("wallet inscribe --compress --file foo.txt --fee-rate 1".to_string())
-      .write("foo.txt", [0; 350_000])
-      .rpc_server(&rpc_server)
-      .run_and_deserialize_output();
+  let Inscribe { inscriptions, .. } = CommandBuilder::new(
+    "wallet inscribe --skip-validation --compress --file foo.txt --fee-rate 1".to_string(),
+  )
+  .write("foo.txt", [0; 350_000])
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output();
 
   let inscription = inscriptions[0].id;
 
@@ -1311,11 +1359,12 @@ fn inscriptions_are_not_compressed_if_no_space_is_saved_by_compression() {
 
   create_wallet(&rpc_server);
 
-  let Inscribe { inscriptions, .. } =
-    CommandBuilder::new("wallet inscribe --compress --file foo.txt --fee-rate 1".to_string())
-      .write("foo.txt", "foo")
-      .rpc_server(&rpc_server)
-      .run_and_deserialize_output();
+  let Inscribe { inscriptions, .. } = CommandBuilder::new(
+    "wallet inscribe --skip-validation --compress --file foo.txt --fee-rate 1".to_string(),
+  )
+  .write("foo.txt", "foo")
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output();
 
   let inscription = inscriptions[0].id;
 
@@ -1356,7 +1405,7 @@ fn batch_inscribe_fails_if_invalid_network_destination_address() {
 
   create_wallet(&rpc_server);
 
-  CommandBuilder::new("--regtest wallet inscribe --fee-rate 2.1 --batch batch.yaml")
+  CommandBuilder::new("--regtest wallet inscribe --skip-validation --fee-rate 2.1 --batch batch.yaml")
     .write("inscription.txt", "Hello World")
     .write("batch.yaml", "mode: separate-outputs\ninscriptions:\n- file: inscription.txt\n  destination: bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4")
     .rpc_server(&rpc_server)
@@ -1374,7 +1423,7 @@ fn batch_inscribe_fails_with_shared_output_and_destination_set() {
 
   create_wallet(&rpc_server);
 
-  CommandBuilder::new("wallet inscribe --fee-rate 2.1 --batch batch.yaml")
+  CommandBuilder::new("wallet inscribe --skip-validation --fee-rate 2.1 --batch batch.yaml")
     .write("inscription.txt", "Hello World")
     .write("tulip.png", "")
     .write("batch.yaml", "mode: shared-output\ninscriptions:\n- file: inscription.txt\n  destination: bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4\n- file: tulip.png")
@@ -1393,7 +1442,7 @@ fn batch_inscribe_works_with_some_destinations_set_and_others_not() {
 
   create_wallet(&rpc_server);
 
-  let output = CommandBuilder::new("wallet inscribe --batch batch.yaml --fee-rate 55")
+  let output = CommandBuilder::new("wallet inscribe --skip-validation --batch batch.yaml --fee-rate 55")
     .write("inscription.txt", "Hello World")
     .write("tulip.png", [0; 555])
     .write("meow.wav", [0; 2048])
@@ -1442,16 +1491,18 @@ fn batch_same_sat() {
 
   create_wallet(&rpc_server);
 
-  let output = CommandBuilder::new("wallet inscribe --fee-rate 1 --batch batch.yaml")
-    .write("inscription.txt", "Hello World")
-    .write("tulip.png", [0; 555])
-    .write("meow.wav", [0; 2048])
-    .write(
-      "batch.yaml",
-      "mode: same-sat\ninscriptions:\n- file: inscription.txt\n- file: tulip.png\n- file: meow.wav\n"
-    )
-    .rpc_server(&rpc_server)
-    .run_and_deserialize_output::<Inscribe>();
+  let output = CommandBuilder::new(
+    "wallet inscribe --skip-validation --fee-rate 1 --batch batch.yaml",
+  )
+  .write("inscription.txt", "Hello World")
+  .write("tulip.png", [0; 555])
+  .write("meow.wav", [0; 2048])
+  .write(
+    "batch.yaml",
+    "mode: same-sat\ninscriptions:\n- file: inscription.txt\n- file: tulip.png\n- file: meow.wav\n",
+  )
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Inscribe>();
 
   assert_eq!(
     output.inscriptions[0].location,
@@ -1505,16 +1556,17 @@ fn batch_same_sat_with_parent() {
 
   create_wallet(&rpc_server);
 
-  let parent_output = CommandBuilder::new("wallet inscribe --fee-rate 5.0 --file parent.png")
-    .write("parent.png", [1; 520])
-    .rpc_server(&rpc_server)
-    .run_and_deserialize_output::<Inscribe>();
+  let parent_output =
+    CommandBuilder::new("wallet inscribe --skip-validation --fee-rate 5.0 --file parent.png")
+      .write("parent.png", [1; 520])
+      .rpc_server(&rpc_server)
+      .run_and_deserialize_output::<Inscribe>();
 
   rpc_server.mine_blocks(1);
 
   let parent_id = parent_output.inscriptions[0].id;
 
-  let output = CommandBuilder::new("wallet inscribe --fee-rate 1 --batch batch.yaml")
+  let output = CommandBuilder::new("wallet inscribe --skip-validation --fee-rate 1 --batch batch.yaml")
     .write("inscription.txt", "Hello World")
     .write("tulip.png", [0; 555])
     .write("meow.wav", [0; 2048])
@@ -1585,7 +1637,7 @@ fn inscribe_with_sat_arg() {
   rpc_server.mine_blocks(2);
 
   let Inscribe { inscriptions, .. } = CommandBuilder::new(
-    "--index-sats wallet inscribe --file foo.txt --sat 5010000000 --fee-rate 1",
+    "--index-sats wallet inscribe --skip-validation --file foo.txt --sat 5010000000 --fee-rate 1",
   )
   .write("foo.txt", "FOO")
   .rpc_server(&rpc_server)
@@ -1609,19 +1661,23 @@ fn inscribe_with_sat_arg_fails_if_no_index_or_not_found() {
   let rpc_server = test_bitcoincore_rpc::spawn();
   create_wallet(&rpc_server);
 
-  CommandBuilder::new("wallet inscribe --file foo.txt --sat 5010000000 --fee-rate 1")
-    .write("foo.txt", "FOO")
-    .rpc_server(&rpc_server)
-    .expected_exit_code(1)
-    .expected_stderr("error: index must be built with `--index-sats` to use `--sat`\n")
-    .run_and_extract_stdout();
+  CommandBuilder::new(
+    "wallet inscribe --skip-validation --file foo.txt --sat 5010000000 --fee-rate 1",
+  )
+  .write("foo.txt", "FOO")
+  .rpc_server(&rpc_server)
+  .expected_exit_code(1)
+  .expected_stderr("error: index must be built with `--index-sats` to use `--sat`\n")
+  .run_and_extract_stdout();
 
-  CommandBuilder::new("--index-sats wallet inscribe --sat 5000000000 --file foo.txt --fee-rate 1")
-    .write("foo.txt", "FOO")
-    .rpc_server(&rpc_server)
-    .expected_exit_code(1)
-    .expected_stderr("error: could not find sat `5000000000`\n")
-    .run_and_extract_stdout();
+  CommandBuilder::new(
+    "--index-sats wallet inscribe --skip-validation --sat 5000000000 --file foo.txt --fee-rate 1",
+  )
+  .write("foo.txt", "FOO")
+  .rpc_server(&rpc_server)
+  .expected_exit_code(1)
+  .expected_stderr("error: could not find sat `5000000000`\n")
+  .run_and_extract_stdout();
 }
 
 #[test]
@@ -1633,11 +1689,12 @@ fn batch_inscribe_with_sat_argument_with_parent() {
 
   create_wallet(&rpc_server);
 
-  let parent_output =
-    CommandBuilder::new("--index-sats wallet inscribe --fee-rate 5.0 --file parent.png")
-      .write("parent.png", [1; 520])
-      .rpc_server(&rpc_server)
-      .run_and_deserialize_output::<Inscribe>();
+  let parent_output = CommandBuilder::new(
+    "--index-sats wallet inscribe --skip-validation --fee-rate 5.0 --file parent.png",
+  )
+  .write("parent.png", [1; 520])
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Inscribe>();
 
   rpc_server.mine_blocks(1);
 
@@ -1645,7 +1702,7 @@ fn batch_inscribe_with_sat_argument_with_parent() {
 
   let parent_id = parent_output.inscriptions[0].id;
 
-  let output = CommandBuilder::new("--index-sats wallet inscribe --fee-rate 1 --batch batch.yaml")
+  let output = CommandBuilder::new("--index-sats wallet inscribe --skip-validation --fee-rate 1 --batch batch.yaml")
     .write("inscription.txt", "Hello World")
     .write("tulip.png", [0; 555])
     .write("meow.wav", [0; 2048])
@@ -1673,7 +1730,7 @@ fn batch_inscribe_with_sat_arg_fails_if_wrong_mode() {
   create_wallet(&rpc_server);
   rpc_server.mine_blocks(1);
 
-  CommandBuilder::new("wallet inscribe --fee-rate 1 --batch batch.yaml")
+  CommandBuilder::new("wallet inscribe --skip-validation --fee-rate 1 --batch batch.yaml")
     .write("inscription.txt", "Hello World")
     .write("tulip.png", [0; 555])
     .write("meow.wav", [0; 2048])
@@ -1695,7 +1752,7 @@ fn batch_inscribe_with_fee_rate() {
 
   let set_fee_rate = 1.0;
 
-  let output = CommandBuilder::new(format!("--index-sats wallet inscribe --fee-rate {set_fee_rate} --batch batch.yaml"))
+  let output = CommandBuilder::new(format!("--index-sats wallet inscribe --skip-validation --fee-rate {set_fee_rate} --batch batch.yaml"))
     .write("inscription.txt", "Hello World")
     .write("tulip.png", [0; 555])
     .write("meow.wav", [0; 2048])
@@ -1747,11 +1804,12 @@ fn server_can_decompress_brotli() {
 
   create_wallet(&rpc_server);
 
-  let Inscribe { inscriptions, .. } =
-    CommandBuilder::new("wallet inscribe --compress --file foo.txt --fee-rate 1".to_string())
-      .write("foo.txt", [0; 350_000])
-      .rpc_server(&rpc_server)
-      .run_and_deserialize_output();
+  let Inscribe { inscriptions, .. } = CommandBuilder::new(
+    "wallet inscribe --skip-validation --compress --file foo.txt --fee-rate 1".to_string(),
+  )
+  .write("foo.txt", [0; 350_000])
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output();
 
   let inscription = inscriptions[0].id;
 
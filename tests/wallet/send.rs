@@ -143,7 +143,7 @@ fn send_does_not_use_inscribed_sats_as_cardinal_utxos() {
 
   let txid = rpc_server.mine_blocks_with_subsidy(1, 10_000)[0].txdata[0].txid();
   CommandBuilder::new(format!(
-    "wallet inscribe --satpoint {txid}:0:0 --file degenerate.png --fee-rate 0"
+    "wallet inscribe --skip-validation --satpoint {txid}:0:0 --file degenerate.png --fee-rate 0"
   ))
   .write("degenerate.png", [1; 100])
   .rpc_server(&rpc_server)
@@ -901,10 +901,12 @@ fn sending_rune_does_not_send_inscription() {
 
   let rune = Rune(RUNE);
 
-  CommandBuilder::new("--chain regtest --index-runes wallet inscribe --fee-rate 0 --file foo.txt")
-    .write("foo.txt", "FOO")
-    .rpc_server(&rpc_server)
-    .run_and_deserialize_output::<Inscribe>();
+  CommandBuilder::new(
+    "--chain regtest --index-runes wallet inscribe --skip-validation --fee-rate 0 --file foo.txt",
+  )
+  .write("foo.txt", "FOO")
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Inscribe>();
 
   rpc_server.mine_blocks_with_subsidy(1, 10000);
 